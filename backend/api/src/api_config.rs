@@ -81,6 +81,7 @@ impl ApiConfig {
                     .with_list_parse_key("infrastructure.traefik.entrypoints")
                     .with_list_parse_key("infrastructure.traefik.auth_middlewares")
                     .with_list_parse_key("infrastructure.traefik.default_middlewares")
+                    .with_list_parse_key("infrastructure.caddy.listen")
                     .try_parsing(true),
             )
             .build()?;
@@ -1,16 +1,20 @@
 use crate::api_config::ApiConfig;
 use crate::auth::Authenticator;
+use crate::management_routers::auth::models::{ForwardAuthDecision, ForwardAuthKey};
+use crate::workers::discord::queue::DiscordSyncQueue;
 use crate::ApiResult;
-use hackathon_portal_repositories::discord::DiscordConfig;
+use hackathon_portal_repositories::discord::{DiscordConfig, DiscordRepository};
 use hackathon_portal_repositories::lite_llm::LiteLLMRepository;
 use hackathon_portal_repositories::s3::S3Repository;
 use hackathon_portal_repositories::DbRepository;
 use hackathon_portal_services::appointment::AppointmentService;
 use hackathon_portal_services::authorization::AuthorizationService;
 use hackathon_portal_services::crypto::CryptoService;
+use hackathon_portal_services::discord::DiscordService;
 use hackathon_portal_services::event::EventService;
 use hackathon_portal_services::health::HealthService;
 use hackathon_portal_services::infrastructure::InfrastructureService;
+use hackathon_portal_services::notification::NotificationService;
 use hackathon_portal_services::project::ProjectService;
 use hackathon_portal_services::rating::RatingService;
 use hackathon_portal_services::sidequest::SidequestService;
@@ -28,6 +32,8 @@ use std::time::Duration;
 pub struct ApiState {
     pub authenticator: Authenticator,
     pub discord_config: Arc<DiscordConfig>,
+    pub discord_sync_queue: Arc<DiscordSyncQueue>,
+    pub discord_service: Arc<DiscordService>,
     pub health_service: Arc<HealthService>,
     pub authorization_service: Arc<AuthorizationService>,
     pub user_service: Arc<UserService>,
@@ -37,9 +43,11 @@ pub struct ApiState {
     pub project_service: Arc<ProjectService>,
     pub sidequest_service: Arc<SidequestService>,
     pub appointment_service: Arc<AppointmentService>,
+    pub notification_service: Arc<NotificationService>,
     pub upload_service: Arc<UploadService>,
     pub infrastructure_service: Arc<InfrastructureService>,
     pub host_to_team_cache: Cache<(), Arc<HashMap<String, Team>>>,
+    pub forward_auth_cache: Cache<ForwardAuthKey, ForwardAuthDecision>,
 }
 
 impl ApiState {
@@ -47,6 +55,8 @@ impl ApiState {
     pub fn new(
         authenticator: Authenticator,
         discord_config: Arc<DiscordConfig>,
+        discord_sync_queue: Arc<DiscordSyncQueue>,
+        discord_service: Arc<DiscordService>,
         health_service: Arc<HealthService>,
         authorization_service: Arc<AuthorizationService>,
         user_service: Arc<UserService>,
@@ -56,13 +66,17 @@ impl ApiState {
         project_service: Arc<ProjectService>,
         sidequest_service: Arc<SidequestService>,
         appointment_service: Arc<AppointmentService>,
+        notification_service: Arc<NotificationService>,
         upload_service: Arc<UploadService>,
         infrastructure_service: Arc<InfrastructureService>,
         host_to_team_cache: Cache<(), Arc<HashMap<String, Team>>>,
+        forward_auth_cache: Cache<ForwardAuthKey, ForwardAuthDecision>,
     ) -> Self {
         Self {
             authenticator,
             discord_config,
+            discord_sync_queue,
+            discord_service,
             health_service,
             authorization_service,
             user_service,
@@ -72,9 +86,11 @@ impl ApiState {
             project_service,
             sidequest_service,
             appointment_service,
+            notification_service,
             upload_service,
             infrastructure_service,
             host_to_team_cache,
+            forward_auth_cache,
         }
     }
 
@@ -86,7 +102,9 @@ impl ApiState {
         let s3_repo = S3Repository::from_config(&config.s3);
 
         let discord_config = Arc::new(config.discord.clone());
-        let lite_llm_repo = LiteLLMRepository::from_config(&config.litellm);
+        let discord_sync_queue = Arc::new(DiscordSyncQueue::default());
+        let ai_gateway_repo = Arc::new(LiteLLMRepository::from_config(&config.litellm));
+        let discord_repo = Arc::new(DiscordRepository::new(&config.discord));
 
         let crypto_service = Arc::new(CryptoService::from_config(&config.crypto)?);
 
@@ -100,12 +118,13 @@ impl ApiState {
             upload_service.clone(),
             crypto_service.clone(),
             db_repo.clone(),
-            lite_llm_repo,
+            ai_gateway_repo,
         ));
 
         let infrastructure_service = Arc::new(InfrastructureService::new(
             config.infrastructure.clone(),
             team_service.clone(),
+            db_repo.clone(),
         ));
 
         let sidequest_service = Arc::new(SidequestService::new(
@@ -119,9 +138,17 @@ impl ApiState {
             team_service.clone(),
         ));
 
-        let project_service = Arc::new(ProjectService::new(db_repo.clone()));
+        let project_service =
+            Arc::new(ProjectService::new(upload_service.clone(), db_repo.clone()));
 
         let appointment_service = Arc::new(AppointmentService::new(db_repo.clone()));
+        let notification_service = Arc::new(NotificationService::new(db_repo.clone()));
+        let discord_service = Arc::new(DiscordService::new(
+            user_service.clone(),
+            crypto_service.clone(),
+            db_repo.clone(),
+            discord_repo,
+        ));
 
         let event_service = Arc::new(EventService::new(
             authorization_service.clone(),
@@ -132,14 +159,23 @@ impl ApiState {
             db_repo,
         ));
 
+        // Invalidated whenever teams change, the TTL only guards against missed invalidations
         let host_to_team_cache = Cache::builder()
-            .time_to_live(Duration::from_secs(10))
+            .time_to_live(Duration::from_mins(5))
             .name("host_to_team_cache")
             .build();
 
+        let forward_auth_cache = Cache::builder()
+            .time_to_live(Duration::from_secs(30))
+            .max_capacity(10_000)
+            .name("forward_auth_cache")
+            .build();
+
         let state = Self::new(
             authenticator,
             discord_config,
+            discord_sync_queue,
+            discord_service,
             health_service,
             authorization_service,
             user_service,
@@ -149,11 +185,19 @@ impl ApiState {
             project_service,
             sidequest_service,
             appointment_service,
+            notification_service,
             upload_service,
             infrastructure_service,
             host_to_team_cache,
+            forward_auth_cache,
         );
 
         Ok(state)
     }
+
+    /// Must be called after changes to teams, their ingress config, addresses or roles.
+    pub fn invalidate_ingress_caches(&self) {
+        self.host_to_team_cache.invalidate_all();
+        self.forward_auth_cache.invalidate_all();
+    }
 }
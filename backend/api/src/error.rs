@@ -1,3 +1,4 @@
+use crate::workers::discord::config::DiscordConfigValidationError;
 use axum::http::header::{InvalidHeaderValue, ToStrError};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -39,6 +40,21 @@ pub enum ApiError {
         reason: String,
     },
 
+    Discord {
+        message: String,
+    },
+
+    DiscordSyncNotConfirmed {
+        destructive_changes: usize,
+        max_destructive_changes: usize,
+    },
+
+    DiscordSyncRunning,
+
+    InvalidDiscordConfig {
+        errors: Vec<DiscordConfigValidationError>,
+    },
+
     // region: internal library errors
     #[from]
     Service(ServiceError),
@@ -97,6 +113,10 @@ pub struct PublicError {
     #[schema(value_type = u16)]
     pub status: StatusCode,
     pub message: String,
+    /// Structured information for errors that the client can act upon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<serde_json::Value>,
 }
 
 impl PublicError {
@@ -104,8 +124,15 @@ impl PublicError {
         Self {
             status,
             message: message.into(),
+            details: None,
         }
     }
+
+    #[must_use]
+    pub fn with_details(mut self, details: &impl Serialize) -> Self {
+        self.details = serde_json::to_value(details).ok();
+        self
+    }
 }
 
 impl fmt::Display for PublicError {
@@ -148,6 +175,41 @@ impl From<&RepositoryError> for PublicError {
             RepositoryError::Timeout { message } => {
                 (StatusCode::SERVICE_UNAVAILABLE, message.clone())
             }
+            RepositoryError::AIGatewayUnauthorized { .. } => (
+                StatusCode::BAD_GATEWAY,
+                "The AI gateway rejected the master AI API key".to_string(),
+            ),
+            RepositoryError::AIGatewayNotFound { message } => {
+                (StatusCode::NOT_FOUND, format!("AI gateway: {message}"))
+            }
+            RepositoryError::AIGatewayRejected { message, .. } => {
+                (StatusCode::BAD_REQUEST, format!("AI gateway: {message}"))
+            }
+            RepositoryError::AIGatewayUnavailable { status, .. } => (
+                StatusCode::BAD_GATEWAY,
+                format!("The AI gateway is unavailable ({status})"),
+            ),
+            RepositoryError::DiscordInvalidCode { .. } => (
+                StatusCode::BAD_REQUEST,
+                "The Discord authorization code is invalid or expired, please try again"
+                    .to_string(),
+            ),
+            RepositoryError::DiscordGuildLimitReached { .. } => (
+                StatusCode::BAD_REQUEST,
+                "You joined the maximum number of Discord servers, leave one and try again"
+                    .to_string(),
+            ),
+            RepositoryError::DiscordUnauthorized { .. } => (
+                StatusCode::BAD_GATEWAY,
+                "Discord rejected the bot, check its token and permissions".to_string(),
+            ),
+            RepositoryError::DiscordRejected { message, .. } => {
+                (StatusCode::BAD_REQUEST, format!("Discord: {message}"))
+            }
+            RepositoryError::DiscordUnavailable { status, .. } => (
+                StatusCode::BAD_GATEWAY,
+                format!("Discord is unavailable ({status})"),
+            ),
             RepositoryError::SeaORM(_)
             | RepositoryError::S3Build(_)
             | RepositoryError::S3PresigningConfig(_)
@@ -204,9 +266,13 @@ impl From<&ServiceError> for PublicError {
                                         StatusCode::BAD_REQUEST,
                                         "Project preferences must be unique".to_string(),
                                     ),
-            ServiceError::ProjectPreferenceWrongCount { expected, actual } => (
+            ServiceError::ProjectPreferenceInvalid { project_id } => (
                                         StatusCode::BAD_REQUEST,
-                                        format!("Wrong number of project preferences, expected {expected}, got {actual}"),
+                                        format!("Project '{project_id}' does not belong to the event of this team"),
+                                    ),
+            ServiceError::ProjectOwnerNotStakeholder { user_id } => (
+                                        StatusCode::BAD_REQUEST,
+                                        format!("User '{user_id}' is not a stakeholder of this event"),
                                     ),
             ServiceError::SidequestCooldown { expires_at } => {
                                         let expires_at_local = Zurich.from_utc_datetime(expires_at);
@@ -254,18 +320,53 @@ impl From<&ServiceError> for PublicError {
                                         ),
                                     ),
             ServiceError::Repository(e) => return e.into(),
+            ServiceError::ProjectTeamLimits {
+                min_teams,
+                max_teams,
+            } => (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Project requires at least {min_teams} teams, but allows at most {max_teams} teams"
+                ),
+            ),
             ServiceError::Matching { message } => (StatusCode::BAD_REQUEST, message.clone()),
+            ServiceError::MatchingInfeasible { diagnostics } => {
+                let message = format!(
+                    "No feasible matching found: {}",
+                    diagnostics.issues.join("; ")
+                );
+
+                return Self::new(StatusCode::BAD_REQUEST, message).with_details(diagnostics);
+            }
             ServiceError::DependencyMissing { .. } |
             ServiceError::Io(_) |
             ServiceError::TracingSetGlobalDefault(_) |
             ServiceError::TracingAppenderRollingInit(_) |
             ServiceError::Crypto(_) |
+            ServiceError::SshKey(_) |
+            ServiceError::SerdeYaml(_) |
             ServiceError::SeaORM(_) => ise,
             ServiceError::MissingMasterAIAPIKey => (
                 StatusCode::BAD_REQUEST,
                 "The master AI API key is missing".to_string(),
             ),
+            ServiceError::MissingTeamAIAPIKey => (
+                StatusCode::BAD_REQUEST,
+                "This team has no AI API key yet".to_string(),
+            ),
+            ServiceError::MissingAIBudget => (
+                StatusCode::BAD_REQUEST,
+                "No budget given and the event has no default AI budget".to_string(),
+            ),
+            ServiceError::IncompleteDiscordCredentials => (
+                StatusCode::BAD_REQUEST,
+                "Discord client ID, client secret and bot token must be set together".to_string(),
+            ),
             ServiceError::Parsing { message } => (StatusCode::BAD_REQUEST, message.clone()),
+            ServiceError::InvalidTemplate { field, message } => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid template for '{field}': {message}"),
+            ),
             ServiceError::SerdeJson(error) => (StatusCode::BAD_REQUEST, error.to_string()),
             ServiceError::WrongTechnicalQuestionPoints { given_score, allowed_scores } => {
                                 (
@@ -314,6 +415,28 @@ impl From<&ApiError> for PublicError {
                 StatusCode::UNAUTHORIZED,
                 "Authentication failed".to_string(),
             ),
+            ApiError::Discord { message } => (
+                StatusCode::BAD_GATEWAY,
+                format!("Discord request failed: {message}"),
+            ),
+            ApiError::DiscordSyncNotConfirmed {
+                destructive_changes,
+                max_destructive_changes,
+            } => (
+                StatusCode::CONFLICT,
+                format!(
+                    "Discord sync deletes {destructive_changes} roles, categories or channels (limit {max_destructive_changes}), the deletions were held back and must be confirmed with the deletions digest of the current plan"
+                ),
+            ),
+            ApiError::DiscordSyncRunning => (
+                StatusCode::CONFLICT,
+                "Discord sync already running, try again later".to_string(),
+            ),
+            ApiError::InvalidDiscordConfig { errors } => {
+                let message = format!("Invalid Discord configuration ({} errors)", errors.len());
+
+                return Self::new(StatusCode::BAD_REQUEST, message).with_details(errors);
+            }
             ApiError::Service(e) => return e.into(),
             ApiError::Repository(e) => return e.into(),
             ApiError::Arc(e) => return e.as_ref().into(),
@@ -1,12 +1,20 @@
+pub mod models;
+
 use crate::api_state::ApiState;
-use crate::ctx::Ctx;
+use crate::auth::AuthenticationResult;
+use crate::management_routers::auth::models::{ForwardAuthDecision, ForwardAuthGrant};
 use crate::{ApiError, ApiResult};
-use axum::extract::State;
+use axum::body::Body;
+use axum::extract::{Request, State};
 use axum::http::{HeaderMap, HeaderValue};
 use axum::routing::get;
 use axum::Router;
 use hackathon_portal_repositories::db::{EventRole, TeamRole};
-use hackathon_portal_services::infrastructure::models::{AccessControlMode, IngressMode};
+use hackathon_portal_services::infrastructure::models::{
+    AccessControlMode, IngressMode, IngressRoute,
+};
+use hackathon_portal_services::team::models::Team;
+use itertools::Itertools;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{info, warn};
@@ -26,10 +34,11 @@ pub fn get_router(state: &ApiState) -> Router {
     ),
 )]
 pub async fn check_authorization(
-    ctx: Ctx,
-    headers: HeaderMap,
     State(state): State<ApiState>,
+    req: Request<Body>,
 ) -> ApiResult<HeaderMap> {
+    let headers = req.headers();
+
     let host = headers
         .get("X-Forwarded-Host")
         .ok_or_else(|| ApiError::BadRequest {
@@ -37,6 +46,70 @@ pub async fn check_authorization(
         })?
         .to_str()?;
 
+    let path = headers
+        .get("X-Forwarded-Uri")
+        .map(HeaderValue::to_str)
+        .transpose()?
+        .unwrap_or("/");
+
+    let host_to_team = get_host_to_team(&state).await?;
+
+    let Some(team) = host_to_team.get(host) else {
+        return Err(ApiError::Forbidden {
+            action: format!("access the host {host} as it does not match known host"),
+        });
+    };
+
+    let managed_config = match &team.ingress_config.mode {
+        IngressMode::Managed(c) => c,
+        IngressMode::Custom(_) => {
+            return Err(ApiError::Forbidden {
+                action: format!("access the host {host} as it does not match known host"),
+            });
+        }
+    };
+
+    let route = team
+        .managed_address
+        .as_deref()
+        .and_then(|managed_address| managed_config.resolve_route(managed_address, host, path));
+
+    let Some(route) = route else {
+        return Err(ApiError::Forbidden {
+            action: format!("access {path} on the host {host} as it does not match any route"),
+        });
+    };
+
+    if matches!(route.access_control_mode, AccessControlMode::None) {
+        warn!(host = ?host, "Authorization attempted on host with access control mode 'None'");
+        return Ok(HeaderMap::new());
+    }
+
+    let Some(auth_result) = state.authenticator.validate(&req)? else {
+        return Err(ApiError::NoCtxInRequest);
+    };
+
+    let key = (
+        auth_result.auth_id.clone(),
+        host.to_string(),
+        route.name.clone(),
+    );
+
+    let decision = state
+        .forward_auth_cache
+        .try_get_with(key, get_decision(&state, &auth_result, team, route))
+        .await?;
+
+    let Some(grant) = decision else {
+        return Err(ApiError::Forbidden {
+            action: format!("access the host {host} as you do not have the required roles"),
+        });
+    };
+
+    grant.to_headers()
+}
+
+async fn get_host_to_team(state: &ApiState) -> ApiResult<Arc<HashMap<String, Team>>> {
     let host_to_team = state
         .host_to_team_cache
         .try_get_with::<_, ApiError>((), async {
@@ -47,16 +120,25 @@ pub async fn check_authorization(
                 .get_all_teams()
                 .await?
                 .into_iter()
-                .filter_map(|t| {
-                    if !t.ingress_enabled {
-                        return None;
-                    }
-
+                .filter(|t| t.ingress_enabled)
+                .flat_map(|t| {
                     let Some(managed_address) = t.managed_address.clone() else {
-                        return None;
+                        return vec![];
                     };
 
-                    Some((managed_address, t))
+                    let hosts = match &t.ingress_config.mode {
+                        IngressMode::Managed(c) => c
+                            .routes
+                            .iter()
+                            .map(|route| route.host(&managed_address))
+                            .collect(),
+                        IngressMode::Custom(_) => vec![managed_address],
+                    };
+
+                    hosts
+                        .into_iter()
+                        .map(|host| (host, t.clone()))
+                        .collect::<Vec<_>>()
                 })
                 .collect::<HashMap<_, _>>();
 
@@ -64,57 +146,54 @@ pub async fn check_authorization(
         })
         .await?;
 
-    let Some(team) = host_to_team.get(host) else {
-        return Err(ApiError::Forbidden {
-            action: format!("access the host {host} as it does not match known host"),
-        });
-    };
+    Ok(host_to_team)
+}
 
-    let managed_config = match &team.ingress_config.mode {
-        IngressMode::Managed(c) => c,
-        IngressMode::Custom(_) => {
-            return Err(ApiError::Forbidden {
-                action: format!("access the host {host} as it does not match known host"),
-            });
-        }
-    };
+async fn get_decision(
+    state: &ApiState,
+    auth_result: &AuthenticationResult,
+    team: &Team,
+    route: &IngressRoute,
+) -> ApiResult<ForwardAuthDecision> {
+    let user = state
+        .user_service
+        .create_or_get_user(&auth_result.auth_id, &auth_result.name)
+        .await?;
 
-    match managed_config.access_control_mode {
-        AccessControlMode::AuthenticationAuthorization => {
-            let event_roles = ctx
-                .roles()
-                .event
-                .get(&team.event_id)
-                .cloned()
-                .unwrap_or_default();
-
-            let team_roles = ctx.roles().team.get(&team.id).cloned().unwrap_or_default();
-
-            let has_event_permissions =
-                [EventRole::Admin, EventRole::Stakeholder, EventRole::Mentor]
-                    .iter()
-                    .any(|r| event_roles.contains(r));
-
-            let has_team_permissions = [TeamRole::Mentor, TeamRole::Member]
-                .iter()
-                .any(|r| team_roles.contains(r));
-
-            if !has_event_permissions && !has_team_permissions {
-                return Err(ApiError::Forbidden {
-                    action: format!("access the host {host} as you do not have the required roles"),
-                });
-            }
-        }
-        AccessControlMode::Authentication => {}
-        AccessControlMode::None => {
-            warn!(host = ?host, "Authorization attempted on host with access control mode 'None'");
-            return Ok(HeaderMap::new());
+    let roles = state.authorization_service.get_roles(user.id).await?;
+
+    let event_roles = roles.get_event_roles(&team.event_id);
+    let team_roles = roles.get_team_roles(&team.id);
+
+    if matches!(
+        route.access_control_mode,
+        AccessControlMode::AuthenticationAuthorization
+    ) {
+        let has_event_permissions = [EventRole::Admin, EventRole::Stakeholder, EventRole::Mentor]
+            .iter()
+            .any(|r| event_roles.contains(r));
+
+        let has_team_permissions = [TeamRole::Mentor, TeamRole::Member]
+            .iter()
+            .any(|r| team_roles.contains(r));
+
+        if !has_event_permissions && !has_team_permissions {
+            return Ok(ForwardAuthDecision::None);
         }
     }
 
-    let mut response_headers = HeaderMap::new();
-    response_headers.insert("X-User-Id", HeaderValue::from_str(&ctx.user().auth_id)?);
-    response_headers.insert("X-User-Name", HeaderValue::from_str(&ctx.user().name)?);
-
-    Ok(response_headers)
+    Ok(Some(ForwardAuthGrant {
+        auth_id: user.auth_id,
+        name: user.name,
+        team_id: team.id,
+        team_slug: team.slug.clone(),
+        event_roles: event_roles
+            .into_iter()
+            .sorted_by_key(ToString::to_string)
+            .collect(),
+        team_roles: team_roles
+            .into_iter()
+            .sorted_by_key(ToString::to_string)
+            .collect(),
+    }))
 }
@@ -0,0 +1,45 @@
+use crate::ApiResult;
+use axum::http::{HeaderMap, HeaderValue};
+use hackathon_portal_repositories::db::{EventRole, TeamRole};
+use itertools::Itertools;
+use uuid::Uuid;
+
+/// Authenticated user, requested host and resolved ingress route
+pub type ForwardAuthKey = (String, String, String);
+
+/// Cached outcome of a forward authentication check, `None` if access is denied.
+pub type ForwardAuthDecision = Option<ForwardAuthGrant>;
+
+#[derive(Debug, Clone)]
+pub struct ForwardAuthGrant {
+    pub auth_id: String,
+    pub name: String,
+    pub team_id: Uuid,
+    pub team_slug: String,
+    pub event_roles: Vec<EventRole>,
+    pub team_roles: Vec<TeamRole>,
+}
+
+impl ForwardAuthGrant {
+    pub fn to_headers(&self) -> ApiResult<HeaderMap> {
+        let mut headers = HeaderMap::new();
+
+        headers.insert("X-User-Id", HeaderValue::from_str(&self.auth_id)?);
+        headers.insert("X-User-Name", HeaderValue::from_str(&self.name)?);
+        headers.insert(
+            "X-Team-Id",
+            HeaderValue::from_str(&self.team_id.to_string())?,
+        );
+        headers.insert("X-Team-Slug", HeaderValue::from_str(&self.team_slug)?);
+        headers.insert(
+            "X-Event-Roles",
+            HeaderValue::from_str(&self.event_roles.iter().join(","))?,
+        );
+        headers.insert(
+            "X-Team-Roles",
+            HeaderValue::from_str(&self.team_roles.iter().join(","))?,
+        );
+
+        Ok(headers)
+    }
+}
@@ -1,13 +1,24 @@
+pub mod models;
+
 use crate::api_state::ApiState;
 use crate::error::ApiJson;
-use axum::extract::State;
+use crate::management_routers::infrastructure::models::InventoryQuery;
+use crate::ApiResult;
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Json, Router};
-use hackathon_portal_services::infrastructure::models::TraefikDynamicConfig;
+use hackathon_portal_services::infrastructure::inventory::InventoryFormat;
+use hackathon_portal_services::infrastructure::models::{ProxySkippedTeam, TraefikDynamicConfig};
 
 pub fn get_router(state: &ApiState) -> Router {
     Router::new()
         .route("/traefik", get(get_traefik_dynamic_config))
+        .route("/caddy", get(get_caddy_config))
+        .route("/nginx", get(get_nginx_config))
+        .route("/skipped-teams", get(get_proxy_skipped_teams))
+        .route("/inventory", get(get_inventory))
         .with_state(state.clone())
 }
 
@@ -28,3 +39,81 @@ pub async fn get_traefik_dynamic_config(
         .await?;
     Ok(Json(config))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/infrastructure/caddy",
+    responses(
+        (status = StatusCode::OK, body = Object),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_caddy_config(State(state): State<ApiState>) -> ApiJson<serde_json::Value> {
+    let config = state.infrastructure_service.get_caddy_config().await?;
+    Ok(Json(config))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/infrastructure/nginx",
+    responses(
+        (status = StatusCode::OK, body = String, content_type = "text/plain"),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_nginx_config(State(state): State<ApiState>) -> ApiResult<String> {
+    let config = state.infrastructure_service.get_nginx_config().await?;
+    Ok(config)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/infrastructure/skipped-teams",
+    responses(
+        (status = StatusCode::OK, body = Vec<ProxySkippedTeam>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_proxy_skipped_teams(
+    State(state): State<ApiState>,
+) -> ApiJson<Vec<ProxySkippedTeam>> {
+    let skipped_teams = state
+        .infrastructure_service
+        .get_proxy_skipped_teams()
+        .await?;
+    Ok(Json(skipped_teams))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/infrastructure/inventory",
+    responses(
+        (status = StatusCode::OK, body = String),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+    params(
+        ("event_id" = Uuid, Query, description = "Event to export the team servers of"),
+        ("format" = Option<InventoryFormat>, Query, description = "Defaults to json"),
+        ("include_credentials" = Option<bool>, Query, description = "Include decrypted team credentials"),
+    )
+)]
+pub async fn get_inventory(
+    State(state): State<ApiState>,
+    Query(query): Query<InventoryQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let inventory = state
+        .infrastructure_service
+        .get_inventory(query.event_id, query.include_credentials)
+        .await?;
+
+    let content_type = match query.format {
+        InventoryFormat::Json => "application/json",
+        InventoryFormat::AnsibleYaml => "application/yaml",
+        InventoryFormat::AnsibleIni => "text/plain",
+    };
+
+    Ok((
+        [(header::CONTENT_TYPE, content_type)],
+        inventory.render(query.format)?,
+    ))
+}
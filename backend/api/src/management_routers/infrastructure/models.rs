@@ -0,0 +1,13 @@
+use hackathon_portal_services::infrastructure::inventory::InventoryFormat;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct InventoryQuery {
+    pub event_id: Uuid,
+    #[serde(default)]
+    pub format: InventoryFormat,
+    #[serde(default)]
+    pub include_credentials: bool,
+}
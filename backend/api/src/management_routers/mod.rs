@@ -1,7 +1,7 @@
-mod auth;
+pub mod auth;
 mod docs;
 mod health;
-mod infrastructure;
+pub mod infrastructure;
 
 use crate::api_state::ApiState;
 use crate::mw::mw_resolve_ctx;
@@ -11,11 +11,14 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 pub fn get_router(state: ApiState) -> Router {
+    // Authenticates on its own to answer cached decisions without resolving roles
+    let auth_router = auth::get_router(&state);
+
     Router::new()
-        .nest("/auth", auth::get_router(&state))
         .nest("/health", health::get_router(&state))
         .nest("/infrastructure", infrastructure::get_router(&state))
         .layer(middleware::from_fn_with_state(state, mw_resolve_ctx))
+        .nest("/auth", auth_router)
 }
 
 pub fn get_docs() -> Router {
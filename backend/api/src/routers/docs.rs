@@ -2,7 +2,7 @@ use utoipa::OpenApi;
 use utoipauto::utoipauto;
 
 #[utoipauto(
-    paths = "api/src/error.rs, api/src/models.rs, api/src/routers, services/src from hackathon_portal_services, repositories/src from hackathon_portal_repositories"
+    paths = "api/src/error.rs, api/src/models.rs, api/src/routers, api/src/workers/discord/config.rs, api/src/workers/discord/plan.rs, services/src from hackathon_portal_services, repositories/src from hackathon_portal_repositories"
 )]
 #[derive(OpenApi)]
 #[openapi(
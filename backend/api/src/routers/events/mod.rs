@@ -5,11 +5,15 @@ use crate::ctx::Ctx;
 use crate::error::{ApiJson, ApiJsonVec};
 use crate::models::AffectedRows;
 use crate::routers::events::models::{
-    CreateTechnicalQuestionDTO, DiscordOauthBody, EventDiscordResponse, InviteUsersDTO,
+    CreateTechnicalQuestionDTO, DiscordOauthBody, DiscordSyncApply, EventDiscordLink,
+    EventDiscordLinkBody, EventDiscordLinksQuery, EventDiscordResponse, InviteUsersDTO,
     SidequestsHistoryQuery, UpdateTechnicalQuestionDTO,
 };
 use crate::routers::sidequests::models::SidequestIdQuery;
 use crate::routers::users::models::EventRoleOptQuery;
+use crate::workers::discord;
+use crate::workers::discord::config::DiscordConfig;
+use crate::workers::discord::plan::DiscordSyncPlan;
 use crate::{ApiError, ApiResult};
 use axum::extract::{Path, Query, State};
 use axum::routing::{delete, get, patch, post, put};
@@ -17,7 +21,14 @@ use axum::{Json, Router};
 use hackathon_portal_repositories::db::EventRole;
 use hackathon_portal_services::authorization::groups::Groups;
 use hackathon_portal_services::authorization::models::{EventAffiliate, EventRoles, EventRolesMap};
+use hackathon_portal_services::discord::models::{
+    DiscordGuild, DiscordGuildForUpdate, DiscordSyncStatus,
+};
 use hackathon_portal_services::event::models::{Event, EventForUpdate};
+use hackathon_portal_services::infrastructure::models::EventHealthOverview;
+use hackathon_portal_services::project::models::{
+    MatchingDiagnostics, MatchingParameters, ProjectMatching, ProjectMatchingForUpdate,
+};
 use hackathon_portal_services::rating::models::{
     CreateTechnicalQuestion, ExpertRatingLeaderboardEntry, ScoreNormalized, TechnicalQuestion,
     UpdateTechnicalQuestion,
@@ -25,12 +36,14 @@ use hackathon_portal_services::rating::models::{
 use hackathon_portal_services::sidequest::models::{
     HistoryEntry, TeamLeaderboardEntry, UserLeaderboardEntry,
 };
+use hackathon_portal_services::team::models::{
+    TeamAIAPIKeyProvisionResult, TeamAIAPIKeysForProvision, TeamCredentialsForGenerate,
+};
 use hackathon_portal_services::user::models::ReducedUser;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+#[allow(clippy::too_many_lines)]
 pub fn get_router(state: &ApiState) -> Router {
     Router::new()
         .route("/", get(get_events))
@@ -40,13 +53,68 @@ pub fn get_router(state: &ApiState) -> Router {
         .route("/:event_id", patch(update_event))
         .route("/:event_id/discord", get(get_event_discord_oauth))
         .route("/:event_id/discord", post(post_event_discord_oauth))
+        .route("/:event_id/discord/sync", get(get_event_discord_sync_plan))
+        .route("/:event_id/discord/sync", post(apply_event_discord_sync))
+        .route(
+            "/:event_id/discord/sync/status",
+            get(get_event_discord_sync_status),
+        )
+        .route("/:event_id/discord/guilds", get(get_event_discord_guilds))
+        .route(
+            "/:event_id/discord/guilds/:guild_id",
+            put(put_event_discord_guild),
+        )
+        .route(
+            "/:event_id/discord/guilds/:guild_id",
+            delete(delete_event_discord_guild),
+        )
+        .route("/:event_id/discord/links", get(get_event_discord_links))
+        .route(
+            "/:event_id/discord/links/:user_id",
+            put(put_event_discord_link),
+        )
+        .route(
+            "/:event_id/discord/links/:user_id",
+            delete(delete_event_discord_link),
+        )
         .route("/:event_id/roles", get(get_event_roles))
         .route("/:event_id/roles", put(put_event_roles))
         .route("/:event_id/roles", delete(delete_event_roles))
         .route("/:event_id/invite", post(invite_users))
         .route("/:event_id/affiliates", get(get_event_affiliates))
         .route("/:event_id/teams/index", post(index_teams))
+        .route(
+            "/:event_id/teams/credentials",
+            post(generate_event_team_credentials),
+        )
+        .route(
+            "/:event_id/teams/ai-api-keys",
+            post(provision_event_ai_api_keys),
+        )
+        .route("/:event_id/teams/health", get(get_event_teams_health))
         .route("/:event_id/projects/matching", get(get_projects_matching))
+        .route("/:event_id/projects/matching", post(post_projects_matching))
+        .route(
+            "/:event_id/projects/matching/diagnostics",
+            post(post_projects_matching_diagnostics),
+        )
+        .route("/:event_id/projects/matchings", get(get_projects_matchings))
+        .route(
+            "/:event_id/projects/matchings",
+            post(create_projects_matching),
+        )
+        .route(
+            "/:event_id/projects/matchings/:matching_id",
+            get(get_projects_matching_run),
+        )
+        .route(
+            "/:event_id/projects/matchings/:matching_id",
+            patch(update_projects_matching),
+        )
+        .route(
+            "/:event_id/projects/matchings/:matching_id/apply",
+            post(apply_projects_matching),
+        )
         .route("/:event_id/leaderboard", get(get_leaderboard))
         .route(
             "/:event_id/leaderboard-detailed",
@@ -210,6 +278,7 @@ pub async fn get_event(
     path = "/api/events/{event_id}",
     responses(
         (status = StatusCode::OK, body = Event),
+        (status = StatusCode::BAD_REQUEST, body = PublicError),
         (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
     )
 )]
@@ -227,7 +296,15 @@ pub async fn update_event(
         });
     }
 
+    if let Some(discord_config) = body.discord_config.as_deref() {
+        if !discord_config.is_empty() {
+            DiscordConfig::validate(discord_config)
+                .map_err(|errors| ApiError::InvalidDiscordConfig { errors })?;
+        }
+    }
+
     let event = state.event_service.update_event(event_id, body).await?;
+    state.invalidate_ingress_caches();
 
     Ok(Json(event))
 }
@@ -269,11 +346,16 @@ pub async fn put_event_roles(
         });
     }
 
+    let user_ids = body.keys().copied().collect::<Vec<_>>();
+
     let affected_rows = state
         .authorization_service
         .assign_event_roles(event_id, body)
         .await?;
 
+    state.invalidate_ingress_caches();
+    state.discord_sync_queue.enqueue(event_id, user_ids);
+
     let affected_rows = AffectedRows { affected_rows };
 
     Ok(Json(affected_rows))
@@ -302,11 +384,16 @@ pub async fn delete_event_roles(
         });
     }
 
+    let user_ids = body.keys().copied().collect::<Vec<_>>();
+
     let affected_rows = state
         .authorization_service
         .unassign_event_roles(event_id, body)
         .await?;
 
+    state.invalidate_ingress_caches();
+    state.discord_sync_queue.enqueue(event_id, user_ids);
+
     let affected_rows = AffectedRows { affected_rows };
 
     Ok(Json(affected_rows))
@@ -400,10 +487,104 @@ pub async fn index_teams(
     }
 
     state.team_service.index_teams(event_id).await?;
+    state.invalidate_ingress_caches();
+    state.discord_sync_queue.enqueue_event(event_id);
 
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/teams/credentials",
+    responses(
+        (status = StatusCode::OK, body = AffectedRows),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn generate_event_team_credentials(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Json(body): Json<TeamCredentialsForGenerate>,
+) -> ApiJson<AffectedRows> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "generate team credentials for this event".to_string(),
+        });
+    }
+
+    let affected_rows = state
+        .team_service
+        .generate_event_team_credentials(Some(ctx.user().id), event.id, &body)
+        .await?;
+
+    Ok(Json(AffectedRows { affected_rows }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/teams/ai-api-keys",
+    responses(
+        (status = StatusCode::OK, body = Vec<TeamAIAPIKeyProvisionResult>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn provision_event_ai_api_keys(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Json(body): Json<TeamAIAPIKeysForProvision>,
+) -> ApiJsonVec<TeamAIAPIKeyProvisionResult> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "provision AI API keys for this event".to_string(),
+        });
+    }
+
+    let results = state
+        .team_service
+        .provision_event_ai_api_keys(event.id, &body)
+        .await?;
+
+    Ok(Json(results))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/teams/health",
+    responses(
+        (status = StatusCode::OK, body = EventHealthOverview),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn get_event_teams_health(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+) -> ApiJson<EventHealthOverview> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_view_team_health() {
+        return Err(ApiError::Forbidden {
+            action: "view the health of teams for this event".to_string(),
+        });
+    }
+
+    let overview = state
+        .infrastructure_service
+        .get_event_health_overview(event.id)
+        .await?;
+
+    Ok(Json(overview))
+}
+
 #[utoipa::path(
     get,
     path = "/api/events/{event_id}/projects/matching",
@@ -426,7 +607,226 @@ pub async fn get_projects_matching(
         });
     }
 
-    let matching = state.project_service.get_matching(event_id).await?;
+    let matching = state
+        .project_service
+        .get_matching(event_id, MatchingParameters::default())
+        .await?;
+
+    Ok(Json(matching))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/projects/matching",
+    responses(
+        (status = StatusCode::OK, body = HashMap<Uuid, Uuid>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn post_projects_matching(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Json(body): Json<MatchingParameters>,
+) -> ApiJson<HashMap<Uuid, Uuid>> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "get the projects matching for this event".to_string(),
+        });
+    }
+
+    let matching = state.project_service.get_matching(event_id, body).await?;
+
+    Ok(Json(matching))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/projects/matching/diagnostics",
+    responses(
+        (status = StatusCode::OK, body = MatchingDiagnostics),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn post_projects_matching_diagnostics(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Json(body): Json<MatchingParameters>,
+) -> ApiJson<MatchingDiagnostics> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "get the projects matching diagnostics for this event".to_string(),
+        });
+    }
+
+    let diagnostics = state
+        .project_service
+        .get_matching_diagnostics(event_id, body)
+        .await?;
+
+    Ok(Json(diagnostics))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/projects/matchings",
+    responses(
+        (status = StatusCode::OK, body = Vec<ProjectMatching>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn get_projects_matchings(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+) -> ApiJsonVec<ProjectMatching> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the projects matchings for this event".to_string(),
+        });
+    }
+
+    let matchings = state
+        .project_service
+        .get_project_matchings(event_id)
+        .await?;
+
+    Ok(Json(matchings))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/projects/matchings",
+    responses(
+        (status = StatusCode::OK, body = ProjectMatching),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn create_projects_matching(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Json(body): Json<MatchingParameters>,
+) -> ApiJson<ProjectMatching> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "create a projects matching for this event".to_string(),
+        });
+    }
+
+    let matching = state
+        .project_service
+        .create_project_matching(event_id, body)
+        .await?;
+
+    Ok(Json(matching))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/projects/matchings/{matching_id}",
+    responses(
+        (status = StatusCode::OK, body = ProjectMatching),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn get_projects_matching_run(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, matching_id)): Path<(Uuid, Uuid)>,
+) -> ApiJson<ProjectMatching> {
+    let matching = state
+        .project_service
+        .get_project_matching(matching_id)
+        .await?;
+    let groups = Groups::from_event(ctx.roles(), matching.event_id);
+
+    if matching.event_id != event_id || !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view this projects matching".to_string(),
+        });
+    }
+
+    Ok(Json(matching))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/events/{event_id}/projects/matchings/{matching_id}",
+    responses(
+        (status = StatusCode::OK, body = ProjectMatching),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn update_projects_matching(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, matching_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<ProjectMatchingForUpdate>,
+) -> ApiJson<ProjectMatching> {
+    let matching = state
+        .project_service
+        .get_project_matching(matching_id)
+        .await?;
+    let groups = Groups::from_event(ctx.roles(), matching.event_id);
+
+    if matching.event_id != event_id || !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "override assignments of this projects matching".to_string(),
+        });
+    }
+
+    let matching = state
+        .project_service
+        .update_project_matching(matching_id, body)
+        .await?;
+
+    Ok(Json(matching))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/projects/matchings/{matching_id}/apply",
+    responses(
+        (status = StatusCode::OK, body = ProjectMatching),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    )
+)]
+pub async fn apply_projects_matching(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, matching_id)): Path<(Uuid, Uuid)>,
+) -> ApiJson<ProjectMatching> {
+    let matching = state
+        .project_service
+        .get_project_matching(matching_id)
+        .await?;
+    let groups = Groups::from_event(ctx.roles(), matching.event_id);
+
+    if matching.event_id != event_id || !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "apply this projects matching".to_string(),
+        });
+    }
+
+    let matching = state
+        .project_service
+        .apply_project_matching(matching_id)
+        .await?;
+    state.invalidate_ingress_caches();
 
     Ok(Json(matching))
 }
@@ -678,8 +1078,11 @@ pub async fn get_event_discord_oauth(
         .get_event_discord_id(user_id, event.id)
         .await?;
 
+    let credentials = state.discord_service.get_credentials(event.id).await?;
+
     let response = EventDiscordResponse {
         discord_user_id: discord_id,
+        client_id: credentials.client_id,
     };
 
     Ok(Json(response))
@@ -700,89 +1103,398 @@ pub async fn post_event_discord_oauth(
     Path(event_id): Path<Uuid>,
     Json(body): Json<DiscordOauthBody>,
 ) -> ApiResult<()> {
-    #[derive(Deserialize)]
-    struct TokenResponse {
-        access_token: String,
+    // Verify event and permissions
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_view_event(event.visibility) {
+        return Err(ApiError::Forbidden {
+            action: "join this event on Discord".to_string(),
+        });
     }
 
-    #[derive(Deserialize)]
-    struct UserInfoResponse {
-        id: String,
+    if event.discord_server_id.is_none() {
+        return Err(ApiError::BadRequest {
+            reason: "Event does not have a Discord server ID configured".to_string(),
+        })?;
     }
 
-    #[derive(Serialize)]
-    struct PutMemberRequest<'a> {
-        access_token: &'a str,
-        nick: &'a str,
+    state
+        .discord_service
+        .link_account(
+            ctx.user().id,
+            event.id,
+            &body.code,
+            &body.redirect_uri,
+            &ctx.user().name,
+        )
+        .await?;
+
+    state.discord_sync_queue.enqueue(event.id, [ctx.user().id]);
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/discord/sync",
+    responses(
+        (status = StatusCode::OK, body = Vec<DiscordSyncPlan>),
+        (status = StatusCode::BAD_REQUEST, body = PublicError),
+        (status = StatusCode::BAD_GATEWAY, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_event_discord_sync_plan(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+) -> ApiJsonVec<DiscordSyncPlan> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the Discord sync plans of this event".to_string(),
+        });
     }
 
-    // Verify event and permissions
+    let plans = discord::plan_event_sync(&state, &event).await?;
+
+    Ok(Json(plans))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/events/{event_id}/discord/sync",
+    responses(
+        (status = StatusCode::OK, body = Vec<DiscordSyncStatus>),
+        (status = StatusCode::BAD_REQUEST, body = PublicError),
+        (status = StatusCode::CONFLICT, body = PublicError),
+        (status = StatusCode::BAD_GATEWAY, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn apply_event_discord_sync(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Json(body): Json<DiscordSyncApply>,
+) -> ApiJsonVec<DiscordSyncStatus> {
     let event = state.event_service.get_event(event_id).await?;
     let groups = Groups::from_event(ctx.roles(), event.id);
 
-    if !groups.can_view_event(event.visibility) {
+    if !groups.can_manage_event() {
         return Err(ApiError::Forbidden {
-            action: "join this event on Discord".to_string(),
+            action: "sync the Discord servers of this event".to_string(),
         });
     }
 
-    // TODO: move out of the handler at some point
+    let statuses = discord::apply_event_sync(&state, &event, &body.confirmed_deletions).await?;
+
+    Ok(Json(statuses))
+}
 
-    let Some(discord_server_id) = event.discord_server_id else {
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/discord/sync/status",
+    responses(
+        (status = StatusCode::OK, body = Vec<DiscordSyncStatus>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_event_discord_sync_status(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+) -> ApiJsonVec<DiscordSyncStatus> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the Discord sync status of this event".to_string(),
+        });
+    }
+
+    let statuses = state.discord_service.get_sync_statuses(event.id).await?;
+
+    Ok(Json(statuses))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/discord/guilds",
+    responses(
+        (status = StatusCode::OK, body = Vec<DiscordGuild>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_event_discord_guilds(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+) -> ApiJsonVec<DiscordGuild> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the Discord servers of this event".to_string(),
+        });
+    }
+
+    let guilds = state.discord_service.get_guilds(event.id).await?;
+
+    Ok(Json(guilds))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/events/{event_id}/discord/guilds/{guild_id}",
+    responses(
+        (status = StatusCode::OK, body = DiscordGuild),
+        (status = StatusCode::BAD_REQUEST, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn put_event_discord_guild(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, guild_id)): Path<(Uuid, String)>,
+    Json(body): Json<DiscordGuildForUpdate>,
+) -> ApiJson<DiscordGuild> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "add Discord servers to this event".to_string(),
+        });
+    }
+
+    if guild_id.parse::<u64>().is_err() {
         return Err(ApiError::BadRequest {
-            reason: "Event does not have a Discord server ID configured".to_string(),
-        })?;
+            reason: "Discord server ID must be numeric".to_string(),
+        });
+    }
+
+    if event.discord_server_id.as_ref() == Some(&guild_id) {
+        return Err(ApiError::BadRequest {
+            reason: "Discord server is already the event's own server".to_string(),
+        });
+    }
+
+    if let Some(discord_config) = body.discord_config.as_deref() {
+        DiscordConfig::validate(discord_config)
+            .map_err(|errors| ApiError::InvalidDiscordConfig { errors })?;
+    }
+
+    let guild = state
+        .discord_service
+        .update_guild(event.id, guild_id, body)
+        .await?;
+
+    Ok(Json(guild))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/events/{event_id}/discord/guilds/{guild_id}",
+    responses(
+        (status = StatusCode::OK, body = ()),
+        (status = StatusCode::NOT_FOUND, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn delete_event_discord_guild(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, guild_id)): Path<(Uuid, String)>,
+) -> ApiResult<()> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "remove Discord servers from this event".to_string(),
+        });
+    }
+
+    state
+        .discord_service
+        .delete_guild(event.id, &guild_id)
+        .await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/events/{event_id}/discord/links",
+    responses(
+        (status = StatusCode::OK, body = Vec<EventDiscordLink>),
+        (status = StatusCode::BAD_GATEWAY, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+    params(
+        ("check_guild" = Option<bool>, Query, description = "Check whether linked accounts are still on the Discord server"),
+    )
+)]
+pub async fn get_event_discord_links(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(event_id): Path<Uuid>,
+    Query(query): Query<EventDiscordLinksQuery>,
+) -> ApiJsonVec<EventDiscordLink> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the Discord accounts of this event".to_string(),
+        });
+    }
+
+    let affiliates = state
+        .authorization_service
+        .get_event_affiliates(event.id, None)
+        .await?;
+
+    let mut discord_ids = state.user_service.get_event_discord_ids(event.id).await?;
+
+    let guild_member_ids = if query.check_guild {
+        Some(discord::get_guild_member_ids(&state, &event, discord_ids.values()).await?)
+    } else {
+        None
     };
 
-    let client = Client::new();
-    let params = [
-        ("client_id", state.discord_config.client_id.as_str()),
-        ("client_secret", state.discord_config.client_secret.as_str()),
-        ("grant_type", "authorization_code"),
-        ("code", body.code.as_str()),
-        ("redirect_uri", body.redirect_uri.as_str()),
-    ];
-
-    let token = client
-        .post("https://discord.com/api/oauth2/token")
-        .form(&params)
-        .send()
-        .await?
-        .json::<TokenResponse>()
-        .await?;
-
-    // Get user info from Discord
-    let user_info = client
-        .get("https://discord.com/api/users/@me")
-        .bearer_auth(&token.access_token)
-        .send()
-        .await?
-        .json::<UserInfoResponse>()
-        .await?;
-
-    // Add user to guild
-    client
-        .put(format!(
-            "https://discord.com/api/guilds/{}/members/{}",
-            discord_server_id, user_info.id
-        ))
-        .header(
-            "Authorization",
-            format!("Bot {}", state.discord_config.bot_token),
-        )
-        .json(&PutMemberRequest {
-            access_token: &token.access_token,
-            nick: &ctx.user().name,
+    let links = affiliates
+        .into_iter()
+        .map(|affiliate| {
+            let discord_user_id = discord_ids.remove(&affiliate.id);
+
+            let in_guild = guild_member_ids
+                .as_ref()
+                .zip(discord_user_id.as_ref())
+                .map(|(member_ids, discord_user_id)| member_ids.contains(discord_user_id));
+
+            EventDiscordLink {
+                user_id: affiliate.id,
+                name: affiliate.name,
+                roles: affiliate.roles,
+                discord_user_id,
+                in_guild,
+            }
         })
-        .send()
-        .await?
-        .error_for_status()?;
+        .collect();
+
+    Ok(Json(links))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/events/{event_id}/discord/links/{user_id}",
+    responses(
+        (status = StatusCode::OK, body = ()),
+        (status = StatusCode::BAD_REQUEST, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn put_event_discord_link(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<EventDiscordLinkBody>,
+) -> ApiResult<()> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "link Discord accounts of this event".to_string(),
+        });
+    }
+
+    let discord_user_id = body.discord_user_id.trim().to_string();
 
+    if discord_user_id.parse::<u64>().is_err() {
+        return Err(ApiError::BadRequest {
+            reason: "Discord user ID must be numeric".to_string(),
+        });
+    }
+
+    let affiliates = state
+        .authorization_service
+        .get_event_affiliates(event.id, None)
+        .await?;
+
+    if !affiliates.iter().any(|affiliate| affiliate.id == user_id) {
+        return Err(ApiError::BadRequest {
+            reason: "User is not affiliated with this event".to_string(),
+        });
+    }
+
+    let discord_ids = state.user_service.get_event_discord_ids(event.id).await?;
+
+    if let Some((other_user_id, _)) = discord_ids
+        .iter()
+        .find(|(id, discord_id)| **id != user_id && **discord_id == discord_user_id)
+    {
+        let name = affiliates
+            .iter()
+            .find(|affiliate| affiliate.id == *other_user_id)
+            .map_or_else(
+                || other_user_id.to_string(),
+                |affiliate| affiliate.name.clone(),
+            );
+
+        return Err(ApiError::BadRequest {
+            reason: format!("Discord account is already linked to {name}"),
+        });
+    }
+
+    state
+        .user_service
+        .update_discord_id(user_id, event.id, discord_user_id)
+        .await?;
+
+    state.discord_sync_queue.enqueue(event.id, [user_id]);
+
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/events/{event_id}/discord/links/{user_id}",
+    responses(
+        (status = StatusCode::OK, body = ()),
+        (status = StatusCode::NOT_FOUND, body = PublicError),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn delete_event_discord_link(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((event_id, user_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<()> {
+    let event = state.event_service.get_event(event_id).await?;
+    let groups = Groups::from_event(ctx.roles(), event.id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "unlink Discord accounts of this event".to_string(),
+        });
+    }
+
+    // Managed roles are removed by a full sync, the user is no longer known to a member sync
     state
         .user_service
-        .update_discord_id(ctx.user().id, event.id, user_info.id)
+        .delete_discord_id(user_id, event.id)
         .await?;
 
+    state.discord_sync_queue.enqueue_event(event.id);
+
     Ok(())
 }
 
@@ -2,7 +2,7 @@ use chrono::NaiveDateTime;
 use hackathon_portal_repositories::db::EventRole;
 use hackathon_portal_services::user::models::UserForCreate;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -33,6 +33,40 @@ pub struct DiscordOauthBody {
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct EventDiscordResponse {
     pub discord_user_id: Option<String>, // or U64 depending on your DB schema
+    /// OAuth application of the event's bot
+    pub client_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct EventDiscordLinksQuery {
+    /// Checks whether linked accounts are still members of the Discord server, one request per
+    /// linked account
+    #[serde(default)]
+    pub check_guild: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct EventDiscordLink {
+    pub user_id: Uuid,
+    pub name: String,
+    pub roles: Vec<EventRole>,
+    pub discord_user_id: Option<String>,
+    /// `false` if the linked account left the Discord server, `None` if not checked or linked
+    pub in_guild: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct EventDiscordLinkBody {
+    pub discord_user_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordSyncApply {
+    /// Deletions digest per Discord server as previewed in the sync plan. Required if a server
+    /// deletes more roles, categories or channels than allowed, the deletions are held back if
+    /// the current plan deletes anything else.
+    #[serde(default)]
+    pub confirmed_deletions: HashMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
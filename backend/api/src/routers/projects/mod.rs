@@ -3,13 +3,17 @@ pub mod models;
 use crate::api_state::ApiState;
 use crate::ctx::Ctx;
 use crate::error::{ApiJson, ApiJsonVec};
+use crate::models::AffectedRows;
 use crate::routers::events::models::EventIdQuery;
 use crate::ApiError;
 use axum::extract::{Path, Query, State};
-use axum::routing::{delete, get, patch, post};
+use axum::routing::{delete, get, patch, post, put};
 use axum::{Json, Router};
 use hackathon_portal_services::authorization::groups::Groups;
-use hackathon_portal_services::project::models::{Project, ProjectForCreate, ProjectForUpdate};
+use hackathon_portal_services::project::models::{
+    Project, ProjectAttachmentForCreate, ProjectForCreate, ProjectForUpdate,
+};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 pub fn get_router(state: &ApiState) -> Router {
@@ -20,6 +24,13 @@ pub fn get_router(state: &ApiState) -> Router {
         .route("/:project_id", get(get_project))
         .route("/:project_id", patch(update_project))
         .route("/:project_id", delete(delete_project))
+        .route("/:project_id/owners", put(put_project_owners))
+        .route("/:project_id/owners", delete(delete_project_owners))
+        .route("/:project_id/attachments", post(create_project_attachment))
+        .route(
+            "/:project_id/attachments/:upload_id",
+            delete(delete_project_attachment),
+        )
         .with_state(state.clone())
 }
 
@@ -45,7 +56,13 @@ pub async fn create_project(
         });
     }
 
-    let project = state.project_service.create_project(body).await?;
+    // Stakeholders pitching a project become its owners
+    let creator_id = (!groups.can_manage_event()).then(|| ctx.user().id);
+
+    let project = state
+        .project_service
+        .create_project(creator_id, body)
+        .await?;
 
     Ok(Json(project))
 }
@@ -152,7 +169,11 @@ pub async fn update_project(
 ) -> ApiJson<Project> {
     let project = state.project_service.get_project(project_id).await?;
     let event = state.event_service.get_event(project.event_id).await?;
-    let groups = Groups::from_event(ctx.roles(), event.id);
+    let groups = Groups::from_event_and_project(
+        ctx.roles(),
+        event.id,
+        project.owners.contains(&ctx.user().id),
+    );
 
     if !groups.can_manage_project(event.visibility, event.phase, event.read_only) {
         return Err(ApiError::Forbidden {
@@ -160,10 +181,18 @@ pub async fn update_project(
         });
     }
 
+    // Team limits determine the capacity of the matching
+    if (body.min_teams.is_some() || body.max_teams.is_some()) && !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "edit the team limits of this project".to_string(),
+        });
+    }
+
     let project = state
         .project_service
         .update_project(project_id, body)
         .await?;
+    state.invalidate_ingress_caches();
 
     Ok(Json(project))
 }
@@ -185,7 +214,7 @@ pub async fn delete_project(
     let event = state.event_service.get_event(project.event_id).await?;
     let groups = Groups::from_event(ctx.roles(), event.id);
 
-    if !groups.can_manage_project(event.visibility, event.phase, event.read_only) {
+    if !groups.can_manage_event() {
         return Err(ApiError::Forbidden {
             action: "delete this project".to_string(),
         });
@@ -195,3 +224,140 @@ pub async fn delete_project(
 
     Ok(Json(project))
 }
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/owners",
+    responses(
+        (status = StatusCode::OK, body = AffectedRows),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn put_project_owners(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(project_id): Path<Uuid>,
+    Json(body): Json<HashSet<Uuid>>,
+) -> ApiJson<AffectedRows> {
+    let project = state.project_service.get_project(project_id).await?;
+    let groups = Groups::from_event(ctx.roles(), project.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "assign owners to this project".to_string(),
+        });
+    }
+
+    let affected_rows = state
+        .project_service
+        .add_project_owners(project_id, body)
+        .await?;
+
+    let affected_rows = AffectedRows { affected_rows };
+
+    Ok(Json(affected_rows))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/owners",
+    responses(
+        (status = StatusCode::OK, body = AffectedRows),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn delete_project_owners(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(project_id): Path<Uuid>,
+    Json(body): Json<HashSet<Uuid>>,
+) -> ApiJson<AffectedRows> {
+    let project = state.project_service.get_project(project_id).await?;
+    let groups = Groups::from_event(ctx.roles(), project.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "unassign owners from this project".to_string(),
+        });
+    }
+
+    let affected_rows = state
+        .project_service
+        .remove_project_owners(project_id, body)
+        .await?;
+
+    let affected_rows = AffectedRows { affected_rows };
+
+    Ok(Json(affected_rows))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/attachments",
+    responses(
+        (status = StatusCode::OK, body = Project),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn create_project_attachment(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(project_id): Path<Uuid>,
+    Json(body): Json<ProjectAttachmentForCreate>,
+) -> ApiJson<Project> {
+    let project = state.project_service.get_project(project_id).await?;
+    let event = state.event_service.get_event(project.event_id).await?;
+    let groups = Groups::from_event_and_project(
+        ctx.roles(),
+        event.id,
+        project.owners.contains(&ctx.user().id),
+    );
+
+    if !groups.can_manage_project(event.visibility, event.phase, event.read_only) {
+        return Err(ApiError::Forbidden {
+            action: "add attachments to this project".to_string(),
+        });
+    }
+
+    let project = state
+        .project_service
+        .add_project_attachment(project_id, body)
+        .await?;
+
+    Ok(Json(project))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/attachments/{upload_id}",
+    responses(
+        (status = StatusCode::OK, body = Project),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn delete_project_attachment(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path((project_id, upload_id)): Path<(Uuid, Uuid)>,
+) -> ApiJson<Project> {
+    let project = state.project_service.get_project(project_id).await?;
+    let event = state.event_service.get_event(project.event_id).await?;
+    let groups = Groups::from_event_and_project(
+        ctx.roles(),
+        event.id,
+        project.owners.contains(&ctx.user().id),
+    );
+
+    if !groups.can_manage_project(event.visibility, event.phase, event.read_only) {
+        return Err(ApiError::Forbidden {
+            action: "delete attachments of this project".to_string(),
+        });
+    }
+
+    let project = state
+        .project_service
+        .delete_project_attachment(project_id, upload_id)
+        .await?;
+
+    Ok(Json(project))
+}
@@ -7,7 +7,7 @@ use crate::models::AffectedRows;
 use crate::routers::events::models::EventIdQuery;
 use crate::routers::teams::models::{AdminTeam, CreateTeamAPIKey, Team, TeamCredentials};
 use crate::routers::users::models::TeamRoleOptQuery;
-use crate::ApiError;
+use crate::{ApiError, ApiResult};
 use axum::extract::{Path, Query, State};
 use axum::routing::{delete, get, patch, post, put};
 use axum::{Json, Router};
@@ -15,7 +15,10 @@ use hackathon_portal_repositories::db::{ExpertRatingCategory, TeamRole};
 use hackathon_portal_services::authorization::groups::Groups;
 use hackathon_portal_services::authorization::models::{TeamAffiliate, TeamRoles, TeamRolesMap};
 use hackathon_portal_services::rating::models::ScoreNormalized;
-use hackathon_portal_services::team::models::{TeamForCreate, TeamForUpdate};
+use hackathon_portal_services::team::models::{
+    TeamAIAPIKeyForUpdate, TeamAIUsage, TeamCredentialLogEntry, TeamCredentialsForGenerate,
+    TeamForCreate, TeamForUpdate,
+};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
@@ -43,9 +46,22 @@ pub fn get_router(state: &ApiState) -> Router {
             patch(update_team_project_preferences),
         )
         .route("/:team_id/credentials", get(get_team_credentials))
+        .route("/:team_id/credentials", post(generate_team_credentials))
+        .route(
+            "/:team_id/credentials/confirm-rotation",
+            post(confirm_team_credentials_rotation),
+        )
+        .route("/:team_id/credentials/log", get(get_team_credential_log))
         .route("/:team_id/expert-ratings", get(get_team_expert_ratings))
         .route("/:team_id/rating", get(get_team_rating))
         .route("/:team_id/ai-api-keys", post(create_team_ai_api_key))
+        .route("/:team_id/ai-api-keys", patch(update_team_ai_api_key))
+        .route("/:team_id/ai-api-keys", delete(revoke_team_ai_api_key))
+        .route(
+            "/:team_id/ai-api-keys/regenerate",
+            post(regenerate_team_ai_api_key),
+        )
+        .route("/:team_id/ai-api-keys/usage", get(get_team_ai_usage))
         .with_state(state.clone())
 }
 
@@ -72,6 +88,7 @@ pub async fn create_team(
     }
 
     let team = state.team_service.create_team(ctx.user().id, body).await?;
+    state.invalidate_ingress_caches();
 
     Ok(Json(Team::from((team, false, false))))
 }
@@ -145,12 +162,20 @@ pub async fn get_admin_teams(
         });
     }
 
+    let mut health_checks = state
+        .infrastructure_service
+        .get_event_health_checks(event.id)
+        .await?;
+
     let teams = state
         .team_service
         .get_teams(event.id)
         .await?
         .into_iter()
-        .map(AdminTeam::from)
+        .map(|team| {
+            let team_health_checks = health_checks.remove(&team.id).unwrap_or_default();
+            AdminTeam::new(team, team_health_checks)
+        })
         .collect();
 
     Ok(Json(teams))
@@ -301,7 +326,12 @@ pub async fn get_admin_team(
         });
     }
 
-    Ok(Json(AdminTeam::from(team)))
+    let health_checks = state
+        .infrastructure_service
+        .get_team_health_checks(team.id)
+        .await?;
+
+    Ok(Json(AdminTeam::new(team, health_checks)))
 }
 
 #[utoipa::path(
@@ -364,7 +394,14 @@ pub async fn update_team(
         });
     }
 
+    let renamed = body.name.is_some();
     let team = state.team_service.update_team(team_id, body).await?;
+    state.invalidate_ingress_caches();
+
+    // Team roles and channels are named after the team
+    if renamed {
+        state.discord_sync_queue.enqueue_event(event.id);
+    }
 
     let can_view_project_assignment = groups.can_view_project_assignment(
         event.visibility,
@@ -405,6 +442,8 @@ pub async fn delete_team(
     }
 
     state.team_service.delete_team(team_id).await?;
+    state.invalidate_ingress_caches();
+    state.discord_sync_queue.enqueue_event(event.id);
 
     let can_view_project_assignment = groups.can_view_project_assignment(
         event.visibility,
@@ -479,11 +518,16 @@ pub async fn put_team_roles(
         });
     }
 
+    let user_ids = body.keys().copied().collect::<Vec<_>>();
+
     let affected_rows = state
         .authorization_service
         .assign_team_roles(team_id, body)
         .await?;
 
+    state.invalidate_ingress_caches();
+    state.discord_sync_queue.enqueue(event.id, user_ids);
+
     let affected_rows = AffectedRows { affected_rows };
 
     Ok(Json(affected_rows))
@@ -534,11 +578,16 @@ pub async fn delete_team_roles(
         });
     }
 
+    let user_ids = body.keys().copied().collect::<Vec<_>>();
+
     let affected_rows = state
         .authorization_service
         .unassign_team_roles(team_id, body)
         .await?;
 
+    state.invalidate_ingress_caches();
+    state.discord_sync_queue.enqueue(event.id, user_ids);
+
     let affected_rows = AffectedRows { affected_rows };
 
     Ok(Json(affected_rows))
@@ -665,9 +714,102 @@ pub async fn get_team_credentials(
         });
     }
 
+    let team = state
+        .team_service
+        .reveal_team_credentials(ctx.user().id, team.id)
+        .await?;
+
     Ok(Json(TeamCredentials::from(team)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/teams/{team_id}/credentials",
+    responses(
+        (status = StatusCode::OK, body = ()),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn generate_team_credentials(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+    Json(body): Json<TeamCredentialsForGenerate>,
+) -> ApiResult<()> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "generate credentials for this team".to_string(),
+        });
+    }
+
+    state
+        .team_service
+        .generate_team_credentials(Some(ctx.user().id), team.id, &body)
+        .await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/teams/{team_id}/credentials/confirm-rotation",
+    responses(
+        (status = StatusCode::OK, body = ()),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn confirm_team_credentials_rotation(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+) -> ApiResult<()> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "confirm the credentials rotation for this team".to_string(),
+        });
+    }
+
+    state
+        .team_service
+        .confirm_team_credentials_rotation(Some(ctx.user().id), team.id)
+        .await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/teams/{team_id}/credentials/log",
+    responses(
+        (status = StatusCode::OK, body = Vec<TeamCredentialLogEntry>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_team_credential_log(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+) -> ApiJsonVec<TeamCredentialLogEntry> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the credential log for this team".to_string(),
+        });
+    }
+
+    let log = state.team_service.get_team_credential_log(team.id).await?;
+
+    Ok(Json(log))
+}
+
 #[utoipa::path(
     get,
     path = "/api/teams/{team_id}/expert-ratings",
@@ -730,6 +872,121 @@ pub async fn create_team_ai_api_key(
     Ok(Json(key))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/teams/{team_id}/ai-api-keys",
+    responses(
+        (status = StatusCode::OK, body = TeamAIUsage),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn update_team_ai_api_key(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+    Json(body): Json<TeamAIAPIKeyForUpdate>,
+) -> ApiJson<TeamAIUsage> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "update the AI API key of this team".to_string(),
+        });
+    }
+
+    let usage = state
+        .team_service
+        .update_team_ai_api_key(team_id, &body)
+        .await?;
+
+    Ok(Json(usage))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/teams/{team_id}/ai-api-keys",
+    responses(
+        (status = StatusCode::OK, body = ()),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn revoke_team_ai_api_key(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+) -> ApiResult<()> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "revoke the AI API key of this team".to_string(),
+        });
+    }
+
+    state.team_service.revoke_team_ai_api_key(team_id).await?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/teams/{team_id}/ai-api-keys/regenerate",
+    responses(
+        (status = StatusCode::OK, body = String),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn regenerate_team_ai_api_key(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+) -> ApiJson<String> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "regenerate the AI API key of this team".to_string(),
+        });
+    }
+
+    let key = state
+        .team_service
+        .regenerate_team_ai_api_key(team_id)
+        .await?;
+
+    Ok(Json(key))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/teams/{team_id}/ai-api-keys/usage",
+    responses(
+        (status = StatusCode::OK, body = Option<TeamAIUsage>),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, body = PublicError),
+    ),
+)]
+pub async fn get_team_ai_usage(
+    ctx: Ctx,
+    State(state): State<ApiState>,
+    Path(team_id): Path<Uuid>,
+) -> ApiJson<Option<TeamAIUsage>> {
+    let team = state.team_service.get_team(team_id).await?;
+    let groups = Groups::from_event(ctx.roles(), team.event_id);
+
+    if !groups.can_manage_event() {
+        return Err(ApiError::Forbidden {
+            action: "view the AI usage of this team".to_string(),
+        });
+    }
+
+    let usage = state.team_service.get_team_ai_usage(team_id).await?;
+
+    Ok(Json(usage))
+}
+
 #[utoipa::path(
     get,
     path = "/api/teams/{team_id}/rating",
@@ -1,4 +1,4 @@
-use hackathon_portal_services::infrastructure::models::IngressConfig;
+use hackathon_portal_services::infrastructure::models::{IngressConfig, TeamHealthCheck};
 use hackathon_portal_services::team::models::Team as TeamBO;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -73,8 +73,6 @@ pub struct AdminTeam {
     pub slug: String,
     pub index: i32,
     pub photo_url: Option<String>,
-    pub password: Option<String>,
-    pub ai_api_key: Option<String>,
     pub extra_score: Option<f64>,
     pub comment: Option<String>,
     pub managed_address: Option<String>,
@@ -89,10 +87,12 @@ pub struct AdminTeam {
     pub ingress_config: IngressConfig,
     pub ingress_url: Option<String>,
     pub finalist: bool,
+    pub health_checks: Vec<TeamHealthCheck>,
 }
 
-impl From<TeamBO> for AdminTeam {
-    fn from(value: TeamBO) -> Self {
+impl AdminTeam {
+    #[must_use]
+    pub fn new(value: TeamBO, health_checks: Vec<TeamHealthCheck>) -> Self {
         Self {
             id: value.id,
             event_id: value.event_id,
@@ -101,8 +101,6 @@ impl From<TeamBO> for AdminTeam {
             slug: value.slug,
             index: value.index,
             photo_url: value.photo_url,
-            password: value.password,
-            ai_api_key: value.ai_api_key,
             extra_score: value.extra_score,
             comment: value.comment,
             managed_address: value.managed_address,
@@ -117,6 +115,7 @@ impl From<TeamBO> for AdminTeam {
             ingress_config: value.ingress_config,
             ingress_url: value.ingress_url,
             finalist: value.finalist,
+            health_checks,
         }
     }
 }
@@ -124,6 +123,12 @@ impl From<TeamBO> for AdminTeam {
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct TeamCredentials {
     pub vm_password: Option<String>,
+    /// Still deployed on the team server until the rotation is confirmed
+    pub previous_vm_password: Option<String>,
+    pub ssh_public_key: Option<String>,
+    pub ssh_private_key: Option<String>,
+    pub previous_ssh_public_key: Option<String>,
+    pub previous_ssh_private_key: Option<String>,
     pub ai_api_key: Option<String>,
 }
 
@@ -131,6 +136,11 @@ impl From<TeamBO> for TeamCredentials {
     fn from(value: TeamBO) -> Self {
         Self {
             vm_password: value.password,
+            previous_vm_password: value.previous_password,
+            ssh_public_key: value.ssh_public_key,
+            ssh_private_key: value.ssh_private_key,
+            previous_ssh_public_key: value.previous_ssh_public_key,
+            previous_ssh_private_key: value.previous_ssh_private_key,
             ai_api_key: value.ai_api_key,
         }
     }
@@ -0,0 +1,263 @@
+use crate::api_state::ApiState;
+use crate::workers::discord::client::{DiscordClient, DiscordClients};
+use crate::workers::discord::config::{AnnouncementConfig, DiscordConfig};
+use crate::ApiResult;
+use chrono::{NaiveDateTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Europe::Zurich;
+use hackathon_portal_services::appointment::models::Appointment;
+use hackathon_portal_services::event::models::Event;
+use tokio::sync::Mutex;
+use tokio_cron_scheduler::Job;
+use tracing::{error, info, info_span, Instrument};
+
+static ANNOUNCEMENT_LOCK: std::sync::LazyLock<Mutex<()>> =
+    std::sync::LazyLock::new(|| Mutex::new(()));
+
+struct Announcement {
+    /// Unique per event, recorded once sent
+    key: String,
+    content: String,
+    /// Announces a change of the event, not recorded changes are skipped on the first run
+    state_change: bool,
+}
+
+pub fn create_job(api_state: ApiState) -> ApiResult<Job> {
+    let job = Job::new_async("45 * * * * *", move |job_id, _| {
+        // every minute
+        Box::pin({
+            let api_state = api_state.clone();
+            let span = info_span!("discord_announcements", job_id = %job_id);
+
+            async move {
+                if let Err(e) = run(api_state).await {
+                    error!(error = %e, "Failed");
+                }
+            }
+            .instrument(span)
+        })
+    })?;
+
+    Ok(job)
+}
+
+async fn run(api_state: ApiState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Ok(_guard) = ANNOUNCEMENT_LOCK.try_lock() else {
+        info!("Announcement job already running, skipping");
+        return Ok(());
+    };
+
+    let events = api_state
+        .event_service
+        .get_events()
+        .await?
+        .into_iter()
+        .filter_map(|event| {
+            let config = DiscordConfig::parse(event.discord_config.as_deref()?).ok()?;
+            event.discord_server_id.as_ref()?;
+
+            Some((event, config.announcements?))
+        })
+        .collect::<Vec<_>>();
+
+    let mut clients = DiscordClients::default();
+
+    // Announcements go to the event's own Discord server only
+    for (event, config) in events {
+        let result = match clients.get(&api_state, event.id).await {
+            Ok(client) => announce(&api_state, client, &event, &config).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Failed to send announcements for event: {} (ID: {}): {}",
+                event.name, event.id, e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn announce(
+    api_state: &ApiState,
+    client: &DiscordClient,
+    event: &Event,
+    config: &AnnouncementConfig,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(guild_id) = &event.discord_server_id else {
+        return Ok(());
+    };
+
+    let notification_service = &api_state.notification_service;
+    let sent = notification_service
+        .get_sent_notifications(event.id)
+        .await?;
+
+    // The current state is recorded without announcing it when an event is seen for the first time
+    let first_run = sent.is_empty();
+
+    let appointments = api_state
+        .appointment_service
+        .get_appointments(event.id)
+        .await?;
+
+    let mut announcements = state_announcements(event, config);
+    announcements.extend(appointment_announcements(
+        &event.name,
+        &appointments,
+        config,
+        Utc::now().naive_utc(),
+    ));
+
+    for announcement in announcements {
+        if sent.contains(&announcement.key)
+            || !notification_service
+                .claim_notification(event.id, &announcement.key)
+                .await?
+        {
+            continue;
+        }
+
+        if (first_run && announcement.state_change) || announcement.content.trim().is_empty() {
+            continue;
+        }
+
+        if let Err(e) = client
+            .send_message(guild_id, &config.channel, &announcement.content)
+            .await
+        {
+            notification_service
+                .release_notification(event.id, &announcement.key)
+                .await?;
+
+            return Err(e);
+        }
+
+        info!(
+            key = announcement.key,
+            "Sent announcement for event {}", event.id
+        );
+    }
+
+    Ok(())
+}
+
+fn state_announcements(event: &Event, config: &AnnouncementConfig) -> Vec<Announcement> {
+    let render = |template: &str| {
+        template
+            .replace("{event}", &event.name)
+            .replace("{phase}", &event.phase.to_string())
+    };
+
+    let mut announcements = vec![Announcement {
+        key: format!("phase:{}", event.phase),
+        content: render(&config.messages.phase),
+        state_change: true,
+    }];
+
+    if event.vote_enabled {
+        announcements.push(Announcement {
+            key: "voting_open".to_string(),
+            content: render(&config.messages.voting_open),
+            state_change: true,
+        });
+    }
+
+    if event.finalists_visible {
+        announcements.push(Announcement {
+            key: "finalists".to_string(),
+            content: render(&config.messages.finalists),
+            state_change: true,
+        });
+    }
+
+    announcements
+}
+
+/// Announces appointments starting within the configured number of minutes. Rescheduled
+/// appointments are announced again.
+fn appointment_announcements(
+    event_name: &str,
+    appointments: &[Appointment],
+    config: &AnnouncementConfig,
+    now: NaiveDateTime,
+) -> Vec<Announcement> {
+    let reminder = TimeDelta::minutes(config.appointment_reminder_minutes);
+
+    appointments
+        .iter()
+        .filter(|appointment| appointment.start > now && appointment.start - now <= reminder)
+        .map(|appointment| {
+            // Rounded up, an appointment in 30 seconds starts in 1 minute
+            let minutes = ((appointment.start - now).num_seconds() + 59) / 60;
+            let start = Zurich.from_utc_datetime(&appointment.start).format("%H:%M");
+
+            let content = config
+                .messages
+                .appointment
+                .replace("{event}", event_name)
+                .replace("{title}", &appointment.title)
+                .replace(
+                    "{description}",
+                    appointment.description.as_deref().unwrap_or_default(),
+                )
+                .replace("{start}", &start.to_string())
+                .replace("{minutes}", &minutes.to_string());
+
+            Announcement {
+                key: format!(
+                    "appointment:{}:{}",
+                    appointment.id,
+                    appointment.start.and_utc().timestamp()
+                ),
+                content: content.trim().to_string(),
+                state_change: false,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workers::discord::config::AnnouncementMessages;
+    use uuid::Uuid;
+
+    #[test]
+    fn announce_appointments_within_reminder() -> Result<(), Box<dyn std::error::Error>> {
+        let now = NaiveDateTime::parse_from_str("2025-10-17 10:00:00", "%Y-%m-%d %H:%M:%S")?;
+
+        let appointment = |title: &str, minutes: i64| Appointment {
+            id: Uuid::new_v4(),
+            event_id: Uuid::nil(),
+            title: title.to_string(),
+            description: None,
+            content: None,
+            start: now + TimeDelta::minutes(minutes),
+            end: None,
+        };
+
+        let config = AnnouncementConfig {
+            channel: "announcements".to_string(),
+            appointment_reminder_minutes: 15,
+            messages: AnnouncementMessages::default(),
+        };
+
+        let appointments = [
+            appointment("Past", -5),
+            appointment("Lunch", 10),
+            appointment("Later", 30),
+        ];
+
+        let announcements = appointment_announcements("VIScon", &appointments, &config, now);
+
+        assert_eq!(announcements.len(), 1);
+        assert_eq!(
+            announcements[0].content,
+            "⏰ **Lunch** starts in 10 minutes (12:10)"
+        );
+
+        Ok(())
+    }
+}
@@ -1,43 +1,88 @@
-use crate::workers::discord::config::CategoryConfig;
-use crate::workers::discord::config::ChannelConfig;
-use crate::workers::discord::config::DiscordConfig;
-use crate::workers::discord::config::NotificationSetting;
-use crate::workers::discord::config::PermissionRole;
-use crate::workers::discord::config::PermissionTarget;
-use crate::workers::discord::config::RoleConfig;
-use crate::workers::discord::config::SpecialRole;
+use crate::workers::discord::config::{slugify_channel_name, DiscordConfig, PermissionRole};
+use crate::workers::discord::plan::{
+    self, DesiredGuild, DiscordCategoryChange, DiscordChannelChange, DiscordRoleChange,
+    DiscordSyncPlan, GuildChannel, GuildOverwrite, GuildRole, GuildSnapshot, EVERYONE_ROLE,
+};
 use crate::ApiState;
+use hackathon_portal_repositories::discord::DiscordCredentials;
+use hackathon_portal_services::discord::models::{DiscordGuild, DiscordMemberRoleFailure};
 use hackathon_portal_services::event::models::Event;
-use serenity::all::ChannelId;
+use hackathon_portal_services::team::models::Team;
 use serenity::all::ChannelType;
 use serenity::all::CreateChannel;
+use serenity::all::CreateMessage;
 use serenity::all::EditChannel;
 use serenity::all::PermissionOverwrite;
 use serenity::all::PermissionOverwriteType;
 use serenity::builder::EditRole;
-use serenity::client::Client;
-use serenity::model::guild::Role;
-use serenity::model::id::RoleId;
+use serenity::client::{Client, ClientBuilder};
+use serenity::http::HttpBuilder;
+use serenity::model::colour::Colour;
+use serenity::model::id::{ChannelId, RoleId};
 use serenity::model::permissions::Permissions;
 use serenity::prelude::GatewayIntents;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::string::ToString;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 pub struct DiscordClient {
     client: Client,
 }
 
+/// Clients by credentials, events with their own bot get their own client.
+#[derive(Default)]
+pub struct DiscordClients {
+    clients: HashMap<DiscordCredentials, DiscordClient>,
+}
+
+impl DiscordClients {
+    /// Returns the client of the event's bot, creating it on first use.
+    pub async fn get(
+        &mut self,
+        api_state: &ApiState,
+        event_id: Uuid,
+    ) -> Result<&DiscordClient, Box<dyn std::error::Error + Send + Sync>> {
+        let credentials = api_state.discord_service.get_credentials(event_id).await?;
+
+        match self.clients.entry(credentials) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let client = DiscordClient::new(api_state, &entry.key().bot_token).await?;
+                Ok(entry.insert(client))
+            }
+        }
+    }
+}
+
+/// Changes made while applying a plan, failed member role changes do not abort the sync.
+#[derive(Debug, Default)]
+pub struct DiscordSyncReport {
+    pub created: u32,
+    pub updated: u32,
+    pub deleted: u32,
+    pub member_failures: Vec<DiscordMemberRoleFailure>,
+}
+
 impl DiscordClient {
     pub async fn new(
         api_state: &ApiState,
+        token: &str,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let token = api_state.discord_config.bot_token.clone();
-
         let intents = GatewayIntents::GUILDS;
 
-        let client = Client::builder(&token, intents).await?;
+        // Serenity waits for rate limits itself, the queue uses them to back off
+        let mut http = HttpBuilder::new(token).build();
+        let queue = api_state.discord_sync_queue.clone();
+
+        if let Some(ratelimiter) = http.ratelimiter.as_mut() {
+            ratelimiter.set_ratelimit_callback(Box::new(move |info| {
+                queue.record_rate_limit(&info);
+            }));
+        }
+
+        let client = ClientBuilder::new_with_http(http, intents).await?;
 
         Ok(Self { client })
     }
@@ -61,33 +106,55 @@ impl DiscordClient {
         }
     }
 
-    pub async fn sync_configuration(
+    /// Returns the given Discord users that are members of the guild.
+    pub async fn get_guild_member_ids<'a>(
+        &self,
+        guild_id: &str,
+        discord_user_ids: impl IntoIterator<Item = &'a String>,
+    ) -> Result<HashSet<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let guild_id: u64 = guild_id.parse()?;
+        let mut member_ids = HashSet::new();
+
+        for discord_user_id in discord_user_ids {
+            let Ok(user_id) = discord_user_id.parse::<u64>() else {
+                continue;
+            };
+
+            match self
+                .client
+                .http
+                .get_member(guild_id.into(), user_id.into())
+                .await
+            {
+                Ok(_) => {
+                    member_ids.insert(discord_user_id.clone());
+                }
+                Err(serenity::Error::Http(e))
+                    if e.status_code().is_some_and(|status| status.as_u16() == 404) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(member_ids)
+    }
+
+    /// Computes the changes required to sync the guild without applying them. Returns `None` if
+    /// the guild has no configuration.
+    pub async fn plan(
         &self,
         api_state: &ApiState,
         event: &Event,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        info!(
-            "Syncing configuration for event: {} (ID: {})",
-            event.name, event.id
-        );
+        guild: &DiscordGuild,
+    ) -> Result<Option<DiscordSyncPlan>, Box<dyn std::error::Error + Send + Sync>> {
+        let guild_id: u64 = guild.guild_id.parse()?;
 
-        // Remember guildid
-        let guild_id: u64 = if let Some(id) = &event.discord_server_id {
-            id.parse()?
-        } else {
-            info!("No Discord server ID for event, skipping sync");
-            return Ok(());
+        let Some(config_yaml) = &guild.discord_config else {
+            info!("No Discord configuration found for server, skipping sync");
+            return Ok(None);
         };
 
-        // Parse the YAML configuration
-        let mut discord_config = if let Some(config_yaml) = &event.discord_config {
-            self.parse_discord_config(config_yaml)?
-        } else {
-            info!("No Discord configuration found for event, skipping sync");
-            return Ok(());
-        };
+        let discord_config = DiscordConfig::parse(config_yaml)?;
 
-        // Log the parsed configuration details
         info!(
             "Parsed Discord configuration with {} roles, {} categories, and {} channels",
             discord_config.roles.len(),
@@ -95,552 +162,601 @@ impl DiscordClient {
             discord_config.channels.len()
         );
 
-        // Sync @everyone permissions (default_permissions)
-        self.set_everyone_permissions(guild_id, &discord_config.default_permissions)
-            .await?;
-
-        // Create, update, or delete roles based on the configuration
-        self.sync_roles(api_state, event.id, &mut discord_config, guild_id)
-            .await?;
+        let teams = Self::get_indexed_teams(api_state, event.id).await?;
 
-        // Assign users to the roles
-        let role_mapping = self
-            .sync_user_roles(api_state, &discord_config, guild_id, event.id)
+        let member_roles = self
+            .get_user_roles_from_database(api_state, event.id)
             .await?;
 
-        // Make role mapping empty for now
-        let synced_categories = self
-            .sync_categories(guild_id, &discord_config.categories, &role_mapping)
-            .await?;
+        let teams = teams
+            .into_iter()
+            .map(|team| (team.index, team.name))
+            .collect::<Vec<_>>();
 
-        // Sync channels
-        self.sync_channels(
-            api_state,
-            guild_id,
-            event.id,
-            &mut discord_config.channels,
-            &role_mapping,
-            &synced_categories,
-        )
-        .await?;
+        let desired = DesiredGuild::new(discord_config, &teams, member_roles)?;
+        let guild = self.snapshot(guild_id, &desired).await?;
 
-        Ok(())
+        Ok(Some(plan::plan(event.id, &guild, &desired)))
     }
 
-    pub async fn sync_channels(
+    /// Syncs the roles of a single user, returns `false` if a role is missing in the guild and
+    /// a full sync is required.
+    pub async fn sync_member(
         &self,
         api_state: &ApiState,
-        guild_id: u64,
-        event_id: Uuid,
-        channels: &mut Vec<ChannelConfig>,
-        role_mapping: &HashMap<String, RoleId>,
-        category_mapping: &HashMap<String, ChannelId>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        info!("Syncing channels for guild: {}", guild_id);
+        event: &Event,
+        guild: &DiscordGuild,
+        user_id: Uuid,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(config_yaml) = &guild.discord_config else {
+            return Ok(true);
+        };
 
-        // Get existing channels in the guild
-        let existing_channels = self.client.http.get_channels(guild_id.into()).await?;
-        let mut existing_channel_map: HashMap<String, ChannelId> = existing_channels
-            .into_iter()
-            .filter(|channel| channel.kind != serenity::all::ChannelType::Category)
-            .map(|channel| (channel.name.clone(), channel.id))
-            .collect();
+        let guild_id: u64 = guild.guild_id.parse()?;
 
-        let mut channels_to_keep = HashSet::new();
-        let mut team_channels_by_index = HashMap::new();
+        let Some(discord_user_id) = api_state
+            .user_service
+            .get_event_discord_id(user_id, event.id)
+            .await?
+        else {
+            debug!("User {} has not linked Discord, skipping", user_id);
+            return Ok(true);
+        };
 
-        let mut renamed_team_channels_map = HashMap::new();
-        let mut renamed_team_channels = Vec::new();
+        let discord_config = DiscordConfig::parse(config_yaml)?;
+        let teams = Self::get_indexed_teams(api_state, event.id).await?;
+        let user_roles = api_state.authorization_service.get_roles(user_id).await?;
 
-        // Add team channels
-        let teams = api_state.team_service.get_teams(event_id).await?;
-        for team in teams {
-            // Only do that if indexes have been assigned
-            if team.index > 0 {
-                let team_channel_name = format!("{:02}-{}", team.index, team.name);
-                let slug_name = team_channel_name.to_lowercase().replace(' ', "-");
-                team_channels_by_index.insert(team.index, slug_name.clone());
-
-                channels.push(ChannelConfig {
-                    name: slug_name,
-                    category: "teams".to_string(),
-                    voice: false,
-                    visible_to: PermissionTarget::Multiple(vec![
-                        PermissionRole::Admin,
-                        PermissionRole::Team(team.index),
-                    ]),
-                    writable_by: PermissionTarget::Multiple(vec![
-                        PermissionRole::Admin,
-                        PermissionRole::Team(team.index),
-                    ]),
-                    default_notification: NotificationSetting::All,
-                });
-            }
-        }
+        let mut roles = user_roles
+            .get_event_roles(&event.id)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
 
-        // Fix other channel names to slug format
-        for channel in channels.iter_mut() {
-            // Remove spaces and special characters but keep emojis
-            let result = channel.name.to_lowercase().replace(' ', "-");
-            if channel.name != result {
-                debug!("Renaming channel '{}' to slug '{}'", channel.name, result);
-                channel.name = result;
-            }
-        }
+        roles.extend(teams.iter().flat_map(|team| {
+            user_roles
+                .get_team_roles(&team.id)
+                .into_iter()
+                .map(|role| PermissionRole::from_team_role(team.index, role).to_string())
+        }));
 
-        // First pass: Handle team channel renaming
-        for (team_index, expected_channel_name) in &team_channels_by_index {
-            // Look for existing team channel with same index but different name
-            let existing_team_channel = existing_channel_map.iter().find(|(existing_name, _)| {
-                Self::is_team_channel(existing_name)
-                    && Self::get_team_index_from_channel_name(existing_name) == Some(*team_index)
-                    && *existing_name != expected_channel_name
-            });
-
-            if let Some((old_name, channel_id)) = existing_team_channel {
-                info!(
-                    "Renaming team channel from '{}' to '{}'",
-                    old_name, expected_channel_name
-                );
+        let teams = teams
+            .into_iter()
+            .map(|team| (team.index, team.name))
+            .collect::<Vec<_>>();
 
-                // Update team channel name
-                self.client
-                    .http
-                    .edit_channel(
-                        *channel_id,
-                        &EditChannel::new().name(expected_channel_name),
-                        Some("Discord sync: renaming team channel"),
-                    )
-                    .await?;
+        let member_roles = HashMap::from([(discord_user_id.clone(), roles)]);
+        let desired = DesiredGuild::new(discord_config, &teams, member_roles)?;
 
-                renamed_team_channels_map.insert(expected_channel_name.clone(), *channel_id);
-                renamed_team_channels.push(old_name.clone());
-            }
-        }
+        let guild_roles = self.get_guild_roles(guild_id).await?;
 
-        // Update existing_channel_map with renamed channels
-        for (new_name, channel_id) in &renamed_team_channels_map {
-            existing_channel_map.insert(new_name.clone(), *channel_id);
+        if desired
+            .role_names
+            .values()
+            .any(|name| !guild_roles.iter().any(|role| role.name == *name))
+        {
+            return Ok(false);
         }
 
-        // Upsert channels
-        for channel_config in channels {
-            channels_to_keep.insert(channel_config.name.clone());
-
-            if let Some(existing_id) = existing_channel_map.get(&channel_config.name) {
-                // Update existing channel
-                debug!("Updating existing channel: {} ", channel_config.name);
-
-                // Update permissions
-                self.setup_channel_permissions(
-                    guild_id,
-                    *existing_id,
-                    channel_config,
-                    role_mapping,
-                )
-                .await?;
+        let Ok(member) = self
+            .client
+            .http
+            .get_member(guild_id.into(), discord_user_id.parse::<u64>()?.into())
+            .await
+        else {
+            debug!("User {} not found in guild, skipping", discord_user_id);
+            return Ok(true);
+        };
 
-                // Move to category if needed
-                let category_slug = &channel_config.category;
-
-                if let Some(category_id) = category_mapping.get(category_slug) {
-                    self.client
-                        .http
-                        .edit_channel(
-                            *existing_id,
-                            &EditChannel::new().category(*category_id),
-                            Some("Discord sync: moving channel to category"),
-                        )
-                        .await?;
-                } else {
-                    warn!(
-                        "Category slug {} not found for channel {}, cannot move",
-                        category_slug, channel_config.name
-                    );
-                }
-            } else {
-                // Create new channel
-                info!("Creating new channel: {}", channel_config.name);
+        let guild = GuildSnapshot {
+            guild_id,
+            roles: guild_roles,
+            channels: Vec::new(),
+            members: HashMap::from([(
+                discord_user_id,
+                member.roles.iter().map(|role_id| role_id.get()).collect(),
+            )]),
+        };
 
-                let mut create_channel = CreateChannel::new(&channel_config.name)
-                    .kind(channel_config.get_channel_type());
+        let plan = plan::plan_members(event.id, &guild, &desired);
 
-                // Assign to category if specified
-                if let Some(category_id) = category_mapping.get(&channel_config.category) {
-                    create_channel = create_channel.category(*category_id);
-                } else {
-                    warn!(
-                        "Category slug {} not found for channel {}, cannot assign",
-                        channel_config.category, channel_config.name
-                    );
-                }
+        if !plan.is_empty() {
+            let mut report = DiscordSyncReport::default();
+            self.apply(&plan, &mut report).await?;
 
-                let channel = self
-                    .client
-                    .http
-                    .create_channel(
-                        guild_id.into(),
-                        &create_channel,
-                        Some("Discord sync: creating channel"),
-                    )
-                    .await?;
-                // Set up permissions
-                self.setup_channel_permissions(guild_id, channel.id, channel_config, role_mapping)
-                    .await?;
-            }
-        }
-        // Delete channels that are no longer in config
-        for (channel_name, channel_id) in existing_channel_map {
-            if !channels_to_keep.contains(&channel_name)
-                && !renamed_team_channels.contains(&channel_name)
-            {
-                info!("Deleting channel: {} (no longer in config)", channel_name);
-                match self
-                    .client
-                    .http
-                    .delete_channel(channel_id, Some("Discord Sync: Deleting chan"))
-                    .await
-                {
-                    Ok(_) => info!("Successfully deleted channel: {}", channel_name),
-                    Err(e) => warn!("Failed to delete channel {}: {}", channel_name, e),
-                }
+            if let Some(failure) = report.member_failures.first() {
+                return Err(format!(
+                    "Failed to update role '{}': {}",
+                    failure.role, failure.error
+                )
+                .into());
             }
         }
 
-        info!("Channel synchronization completed");
-        Ok(())
+        Ok(true)
     }
 
-    fn is_team_channel(channel_name: &str) -> bool {
-        let mut chars = channel_name.chars();
-
-        // Check if first two characters are digits
-        let first_char = chars.next();
-        let second_char = chars.next();
-        let third_char = chars.next();
+    /// Only teams with an assigned index get a role and channel.
+    async fn get_indexed_teams(
+        api_state: &ApiState,
+        event_id: Uuid,
+    ) -> Result<Vec<Team>, Box<dyn std::error::Error + Send + Sync>> {
+        let teams = api_state
+            .team_service
+            .get_teams(event_id)
+            .await?
+            .into_iter()
+            .filter(|team| team.index > 0)
+            .collect();
 
-        first_char.is_some_and(|c| c.is_ascii_digit())
-            && second_char.is_some_and(|c| c.is_ascii_digit())
-            && (third_char == Some('-'))
+        Ok(teams)
     }
 
-    fn get_team_index_from_channel_name(channel_name: &str) -> Option<i32> {
-        if Self::is_team_channel(channel_name) {
-            // Take the first two characters and parse them
-            let index_str: String = channel_name.chars().take(2).collect();
-            index_str.parse::<i32>().ok()
-        } else {
-            None
-        }
+    async fn get_guild_roles(
+        &self,
+        guild_id: u64,
+    ) -> Result<Vec<GuildRole>, Box<dyn std::error::Error + Send + Sync>> {
+        let roles = self
+            .client
+            .http
+            .get_guild_roles(guild_id.into())
+            .await?
+            .into_iter()
+            .map(|role| GuildRole {
+                id: role.id.get(),
+                name: role.name,
+                color: role.colour.0,
+                mentionable: role.mentionable,
+                hoist: role.hoist,
+                permissions: role.permissions.bits(),
+                managed: role.managed,
+            })
+            .collect();
+
+        Ok(roles)
     }
 
-    pub async fn sync_categories(
+    async fn snapshot(
         &self,
         guild_id: u64,
-        categories: &[CategoryConfig],
-        role_mapping: &HashMap<String, RoleId>,
-    ) -> Result<HashMap<String, ChannelId>, Box<dyn std::error::Error + Send + Sync>> {
-        info!("Syncing categories for guild: {}", guild_id);
-
-        // Get existing categories in the guild
-        let existing_channels = self.client.http.get_channels(guild_id.into()).await?;
-        let existing_categories: HashMap<String, ChannelId> = existing_channels
+        desired: &DesiredGuild,
+    ) -> Result<GuildSnapshot, Box<dyn std::error::Error + Send + Sync>> {
+        let roles = self.get_guild_roles(guild_id).await?;
+
+        let channels = self
+            .client
+            .http
+            .get_channels(guild_id.into())
+            .await?
             .into_iter()
-            .filter(|channel| channel.kind == serenity::all::ChannelType::Category)
-            .map(|channel| (channel.name, channel.id))
+            .map(|channel| GuildChannel {
+                id: channel.id.get(),
+                name: channel.name,
+                category: channel.kind == ChannelType::Category,
+                parent_id: channel.parent_id.map(ChannelId::get),
+                overwrites: channel
+                    .permission_overwrites
+                    .iter()
+                    .filter_map(|overwrite| match overwrite.kind {
+                        PermissionOverwriteType::Role(role_id) => Some(GuildOverwrite {
+                            role_id: role_id.get(),
+                            allow: overwrite.allow.bits(),
+                            deny: overwrite.deny.bits(),
+                        }),
+                        _ => None,
+                    })
+                    .collect(),
+            })
             .collect();
 
-        let mut synced_categories = HashMap::new();
-        let mut categories_to_keep = HashSet::new();
+        info!(
+            "Fetching roles of {} members...",
+            desired.member_roles.len()
+        );
 
-        // Upsert categories
-        for category_config in categories {
-            categories_to_keep.insert(category_config.name.clone());
+        let mut members = HashMap::new();
 
-            if let Some(existing_id) = existing_categories.get(&category_config.name) {
-                // Update existing category
-                debug!(
-                    "Updating existing category: {} ({})",
-                    category_config.name, category_config.slug
-                );
+        for discord_user_id in desired.member_roles.keys() {
+            let user_id: u64 = discord_user_id.parse().map_err(|e| {
+                format!("Failed to parse discord_user_id '{discord_user_id}' to u64: {e}")
+            })?;
 
-                // Update permissions
-                self.setup_category_permissions(
-                    guild_id,
-                    *existing_id,
-                    category_config,
-                    role_mapping,
-                )
-                .await?;
+            let Ok(member) = self
+                .client
+                .http
+                .get_member(guild_id.into(), user_id.into())
+                .await
+            else {
+                warn!("User {} not found in guild, skipping", discord_user_id);
+                continue;
+            };
 
-                synced_categories.insert(category_config.slug.clone(), *existing_id);
-            } else {
-                // Create new category
-                info!(
-                    "Creating new category: {} ({})",
-                    category_config.name, category_config.slug
-                );
+            members.insert(
+                discord_user_id.clone(),
+                member.roles.iter().map(|role_id| role_id.get()).collect(),
+            );
+        }
+
+        Ok(GuildSnapshot {
+            guild_id,
+            roles,
+            channels,
+            members,
+        })
+    }
+
+    /// Applies a plan, deletions are done last. Roles, categories and channels are resolved by
+    /// name, so objects created by earlier steps can be referenced by later ones. The report
+    /// also covers the changes made before an error.
+    pub async fn apply(
+        &self,
+        plan: &DiscordSyncPlan,
+        report: &mut DiscordSyncReport,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let guild_id: u64 = plan.guild_id.parse()?;
+        let http = &self.client.http;
+
+        if let Some(permissions) = plan.everyone_permissions {
+            info!("Setting @everyone permissions");
+
+            // @everyone role ID is same as guild ID
+            http.edit_role(
+                guild_id.into(),
+                RoleId::new(guild_id),
+                &EditRole::new().permissions(Permissions::from_bits_truncate(permissions)),
+                Some("Discord sync: updating @everyone permissions"),
+            )
+            .await?;
 
-                let category_channel = self
-                    .client
-                    .http
-                    .create_channel(
+            report.updated += 1;
+        }
+
+        for change in &plan.roles {
+            match change {
+                DiscordRoleChange::Create { role } => {
+                    info!(
+                        "Creating new role: {} (position: {})",
+                        role.name, role.position
+                    );
+
+                    let create_role = EditRole::new()
+                        .name(&role.name)
+                        .colour(Colour::new(role.color))
+                        .mentionable(role.mentionable)
+                        .hoist(role.hoist) // hoist means show in separate section in member list
+                        .permissions(Permissions::from_bits_truncate(role.permissions))
+                        .position(role.position);
+
+                    http.create_role(
                         guild_id.into(),
-                        &CreateChannel::new(&category_config.name).kind(ChannelType::Category),
-                        Some("Discord sync: creating category"),
+                        &create_role,
+                        Some("Discord sync: creating role"),
                     )
                     .await?;
 
-                // Set up permissions
-                self.setup_category_permissions(
-                    guild_id,
-                    category_channel.id,
-                    category_config,
-                    role_mapping,
-                )
-                .await?;
+                    report.created += 1;
+                }
+                DiscordRoleChange::Update { id, role } => {
+                    info!("Updating role: {}", role.name);
 
-                synced_categories.insert(category_config.slug.clone(), category_channel.id);
-            }
-        }
+                    let edit_role = EditRole::new()
+                        .name(&role.name)
+                        .colour(Colour::new(role.color))
+                        .mentionable(role.mentionable)
+                        .hoist(role.hoist)
+                        .permissions(Permissions::from_bits_truncate(role.permissions));
 
-        // Delete categories that are no longer in config
-        for (category_name, category_id) in existing_categories {
-            if !categories_to_keep.contains(&category_name) {
-                info!("Deleting category: {} (no longer in config)", category_name);
-                match self
-                    .client
-                    .http
-                    .delete_channel(category_id, Some("Discord Sync: Deleting cat"))
-                    .await
-                {
-                    Ok(_) => info!("Successfully deleted category: {}", category_name),
-                    Err(e) => warn!("Failed to delete category {}: {}", category_name, e),
+                    http.edit_role(
+                        guild_id.into(),
+                        RoleId::new(id.parse()?),
+                        &edit_role,
+                        Some("Discord sync: updating role"),
+                    )
+                    .await?;
+
+                    report.updated += 1;
                 }
+                DiscordRoleChange::Delete { .. } => {}
             }
         }
 
-        info!("Category synchronization completed");
-        Ok(synced_categories)
-    }
+        let role_ids = http
+            .get_guild_roles(guild_id.into())
+            .await?
+            .into_iter()
+            .map(|role| (role.name, role.id))
+            .collect::<HashMap<_, _>>();
 
-    async fn setup_category_permissions(
-        &self,
-        guild_id: u64,
-        category_id: ChannelId,
-        category_config: &CategoryConfig,
-        role_mapping: &HashMap<String, RoleId>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        self.setup_permissions(
-            guild_id,
-            category_id,
-            &category_config.name,
-            &category_config.get_visible_to_roles(),
-            &category_config.get_writable_by_roles(),
-            role_mapping,
-        )
-        .await
-    }
+        self.apply_member_roles(guild_id, plan, &role_ids, report)
+            .await?;
 
-    async fn setup_channel_permissions(
-        &self,
-        guild_id: u64,
-        channel_id: ChannelId,
-        channel_config: &ChannelConfig,
-        role_mapping: &HashMap<String, RoleId>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        self.setup_permissions(
-            guild_id,
-            channel_id,
-            &channel_config.name,
-            &channel_config.get_visible_to_roles(),
-            &channel_config.get_writable_by_roles(),
-            role_mapping,
-        )
-        .await
-    }
+        for change in &plan.categories {
+            if let DiscordCategoryChange::Create { name } = change {
+                info!("Creating new category: {}", name);
 
-    async fn setup_permissions(
-        &self,
-        guild_id: u64,
-        channel_id: ChannelId,
-        verbose_name: &str,
-        visible_to_roles: &[&PermissionRole],
-        writable_by_roles: &[&PermissionRole],
-        role_mapping: &HashMap<String, RoleId>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // First, get current permission overwrites to avoid unnecessary API calls
-        let channel = match self.client.http.get_channel(channel_id).await? {
-            serenity::all::Channel::Guild(channel) => channel,
-            _ => return Err("Category channel is not a guild channel".into()),
-        };
+                http.create_channel(
+                    guild_id.into(),
+                    &CreateChannel::new(name).kind(ChannelType::Category),
+                    Some("Discord sync: creating category"),
+                )
+                .await?;
 
-        let _current_overwrites = channel.permissions.clone();
+                report.created += 1;
+            }
+        }
 
-        // Build desired permission overwrites
-        let mut desired_overwrites = Vec::new();
+        let (category_ids, _) = self.get_channel_ids(guild_id).await?;
 
-        // Handle @everyone role first (base permissions)
-        let everyone_role_id = RoleId::from(guild_id);
+        for change in &plan.channels {
+            match change {
+                DiscordChannelChange::Create {
+                    name,
+                    category,
+                    voice,
+                } => {
+                    info!("Creating new channel: {}", name);
 
-        // Default: deny view channel for everyone
-        let mut everyone_allow = Permissions::empty();
-        let mut everyone_deny = Permissions::VIEW_CHANNEL;
+                    let kind = if *voice {
+                        ChannelType::Voice
+                    } else {
+                        ChannelType::Text
+                    };
 
-        // Log visible_to_roles and writable_by_roles for debugging
-        debug!(
-            "Cat/Chan {} visible_to roles: {:?}",
-            verbose_name, visible_to_roles
-        );
-        debug!(
-            "Cat/Chan {} writable_by roles: {:?}",
-            verbose_name, writable_by_roles
-        );
+                    let mut create_channel = CreateChannel::new(name).kind(kind);
 
-        if visible_to_roles.contains(&&PermissionRole::All) {
-            everyone_allow |= Permissions::VIEW_CHANNEL;
-            everyone_deny &= !Permissions::VIEW_CHANNEL;
-        }
+                    if let Some(category_id) = category.as_ref().and_then(|c| category_ids.get(c)) {
+                        create_channel = create_channel.category(*category_id);
+                    }
 
-        if writable_by_roles.contains(&&PermissionRole::All) {
-            everyone_allow |= Permissions::SEND_MESSAGES;
-        }
+                    http.create_channel(
+                        guild_id.into(),
+                        &create_channel,
+                        Some("Discord sync: creating channel"),
+                    )
+                    .await?;
 
-        desired_overwrites.push(PermissionOverwrite {
-            allow: everyone_allow,
-            deny: everyone_deny,
-            kind: PermissionOverwriteType::Role(everyone_role_id),
-        });
-
-        // Handle specific roles for visible_to
-        for role_name in visible_to_roles {
-            if role_name != &&PermissionRole::All {
-                if let Some(role_id) = role_mapping.get(&(role_name.to_string())) {
-                    debug!(
-                        "Adding VIEW_CHANNEL permission for role {} on cat/chan {}",
-                        role_name, verbose_name
-                    );
-                    desired_overwrites.push(PermissionOverwrite {
-                        allow: Permissions::VIEW_CHANNEL,
-                        deny: Permissions::empty(),
-                        kind: PermissionOverwriteType::Role(*role_id),
-                    });
-                } else {
-                    warn!("Role {} not found in role mapping, cannot set VIEW_CHANNEL permission for cat/chan {}", role_name, verbose_name);
+                    report.created += 1;
                 }
-            }
-        }
+                DiscordChannelChange::Rename { id, from, to } => {
+                    info!("Renaming team channel from '{}' to '{}'", from, to);
 
-        // Log role_mapping for debugging
-        debug!("Role mapping: {:?}", role_mapping);
+                    http.edit_channel(
+                        ChannelId::new(id.parse()?),
+                        &EditChannel::new().name(to),
+                        Some("Discord sync: renaming team channel"),
+                    )
+                    .await?;
 
-        // Handle specific roles for writable_by
-        for role_name in writable_by_roles {
-            if role_name != &&PermissionRole::All {
-                debug!(
-                    "Adding SEND_MESSAGES permission for role {} on cat/chan {}",
-                    role_name, verbose_name
-                );
+                    report.updated += 1;
+                }
+                DiscordChannelChange::Move { id, name, category } => {
+                    let Some(category_id) = category_ids.get(category) else {
+                        warn!(
+                            "Category {} not found for channel {}, cannot move",
+                            category, name
+                        );
+                        continue;
+                    };
+
+                    debug!("Moving channel {} to category {}", name, category);
+
+                    http.edit_channel(
+                        ChannelId::new(id.parse()?),
+                        &EditChannel::new().category(*category_id),
+                        Some("Discord sync: moving channel to category"),
+                    )
+                    .await?;
 
-                if let Some(role_id) = role_mapping.get(&(role_name.to_string())) {
-                    // Check if we already have an overwrite for this role
-                    if let Some(existing_idx) = desired_overwrites.iter().position(|ow| {
-                        if let PermissionOverwriteType::Role(rid) = ow.kind {
-                            rid == *role_id
-                        } else {
-                            false
-                        }
-                    }) {
-                        // Update existing overwrite
-                        desired_overwrites[existing_idx].allow |= Permissions::SEND_MESSAGES;
-                    } else {
-                        // Create new overwrite
-                        desired_overwrites.push(PermissionOverwrite {
-                            allow: Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
-                            deny: Permissions::empty(),
-                            kind: PermissionOverwriteType::Role(*role_id),
-                        });
-                    }
-                } else {
-                    warn!("Role {} not found in role mapping, cannot set SEND_MESSAGES permission for cat/chan {}", role_name, verbose_name);
+                    report.updated += 1;
                 }
+                DiscordChannelChange::Delete { .. } => {}
             }
         }
 
-        // Log desired overwrites for debugging
-        debug!(
-            "Desired permission overwrites for cat/chan {}: {:?}",
-            verbose_name, desired_overwrites
-        );
+        let (category_ids, channel_ids) = self.get_channel_ids(guild_id).await?;
 
-        // Apply permission changes only if they're different from current
-        // Room for improvement: Compare current_overwrites and desired_overwrites to avoid unnecessary API calls
-        self.client
-            .http
-            .edit_channel(
-                channel_id,
-                &EditChannel::new().permissions(desired_overwrites.clone()),
+        for change in &plan.overwrites {
+            let target_ids = if change.category {
+                &category_ids
+            } else {
+                &channel_ids
+            };
+
+            let Some(target_id) = target_ids.get(&change.target) else {
+                warn!(
+                    "Cat/Chan {} not found, cannot set permissions",
+                    change.target
+                );
+                continue;
+            };
+
+            let overwrites = change
+                .overwrites
+                .iter()
+                .filter_map(|overwrite| {
+                    let role_id = if overwrite.role == EVERYONE_ROLE {
+                        RoleId::new(guild_id)
+                    } else {
+                        *role_ids.get(&overwrite.role)?
+                    };
+
+                    Some(PermissionOverwrite {
+                        allow: Permissions::from_bits_truncate(overwrite.allow),
+                        deny: Permissions::from_bits_truncate(overwrite.deny),
+                        kind: PermissionOverwriteType::Role(role_id),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            debug!(
+                "Permission overwrites for cat/chan {}: {:?}",
+                change.target, overwrites
+            );
+
+            http.edit_channel(
+                *target_id,
+                &EditChannel::new().permissions(overwrites),
                 Some("Discord sync: updating cat/channel permissions"),
             )
             .await?;
 
+            report.updated += 1;
+        }
+
+        let deletions = plan
+            .channels
+            .iter()
+            .filter_map(|change| match change {
+                DiscordChannelChange::Delete { id, name } => Some((id, name)),
+                _ => None,
+            })
+            .chain(plan.categories.iter().filter_map(|change| match change {
+                DiscordCategoryChange::Delete { id, name } => Some((id, name)),
+                DiscordCategoryChange::Create { .. } => None,
+            }));
+
+        for (id, name) in deletions {
+            info!("Deleting cat/chan: {} (no longer in config)", name);
+
+            match http
+                .delete_channel(ChannelId::new(id.parse()?), Some("Discord sync: deleting"))
+                .await
+            {
+                Ok(_) => {
+                    info!("Successfully deleted cat/chan: {}", name);
+                    report.deleted += 1;
+                }
+                Err(e) => warn!("Failed to delete cat/chan {}: {}", name, e),
+            }
+        }
+
+        // Roles go last, channels and overwrites no longer refer to them by then
+        for change in &plan.roles {
+            if let DiscordRoleChange::Delete { id, name } = change {
+                info!("Deleting role: {} (not in config)", name);
+
+                http.delete_role(
+                    guild_id.into(),
+                    RoleId::new(id.parse()?),
+                    Some("Discord sync: deleting role"),
+                )
+                .await?;
+
+                report.deleted += 1;
+            }
+        }
+
+        info!("Discord sync plan applied");
         Ok(())
     }
 
-    async fn sync_user_roles(
+    /// Failed role changes are recorded in the report, the remaining members are still synced.
+    async fn apply_member_roles(
         &self,
-        api_state: &ApiState,
-        config: &DiscordConfig,
         guild_id: u64,
-        event_id: Uuid,
-    ) -> Result<HashMap<String, RoleId>, Box<dyn std::error::Error + Send + Sync>> {
-        info!("Syncing role assignment for event: {}", event_id);
-        info!("This may take a while depending on the number of users...");
+        plan: &DiscordSyncPlan,
+        role_ids: &HashMap<String, RoleId>,
+        report: &mut DiscordSyncReport,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let http = &self.client.http;
 
-        // 1. Get current roles from Discord server
-        let current_roles = self.client.http.get_guild_roles(guild_id.into()).await?;
+        for change in &plan.member_roles {
+            let user_id: u64 = change.discord_user_id.parse()?;
 
-        // 2. Get users and their assigned roles from database
-        let user_roles = self
-            .get_user_roles_from_database(api_state, event_id)
-            .await?;
+            for (role_name, add) in change
+                .remove
+                .iter()
+                .map(|role| (role, false))
+                .chain(change.add.iter().map(|role| (role, true)))
+            {
+                let Some(role_id) = role_ids.get(role_name) else {
+                    warn!(
+                        "Role '{}' not found in server for user {}",
+                        role_name, user_id
+                    );
+                    continue;
+                };
 
-        // 3. Create a mapping from config role "special" property to config role name for quick lookup
-        // That means, look in config.roles for example "admin" and add it to the admin key so below i can just use "admin"
-        let mut role_mapping: HashMap<String, RoleId> = config
-            .roles
-            .iter()
-            .filter_map(|r| {
-                // Only proceed if special is Some(...)
-                if let Some(special) = &r.special {
-                    // Find the corresponding role in current_roles to get its ID
-                    current_roles
-                        .iter()
-                        .find(|cr| cr.name == r.name)
-                        .map(|cr| (special.to_string(), cr.id))
+                let result = if add {
+                    http.add_member_role(
+                        guild_id.into(),
+                        user_id.into(),
+                        *role_id,
+                        Some("Sync, adding role"),
+                    )
+                    .await
                 } else {
-                    None
-                }
-            })
-            .collect();
+                    http.remove_member_role(
+                        guild_id.into(),
+                        user_id.into(),
+                        *role_id,
+                        Some("Sync, removing role"),
+                    )
+                    .await
+                };
 
-        // 3.1 Add team roles to mapping
-        for role in &current_roles {
-            if role.name.starts_with("team-") {
-                role_mapping.insert(role.name.clone(), role.id);
+                if let Err(e) = result {
+                    warn!(
+                        "Failed to update role '{}' of user {}: {}",
+                        role_name, user_id, e
+                    );
+
+                    report.member_failures.push(DiscordMemberRoleFailure {
+                        discord_user_id: change.discord_user_id.clone(),
+                        role: role_name.clone(),
+                        error: e.to_string(),
+                    });
+                }
             }
         }
 
-        debug!("Role mapping for sync: {:?}", role_mapping);
+        Ok(())
+    }
 
-        // 4. Sync each user's roles
-        for (user_id, expected_role_names) in user_roles {
-            self.sync_single_user_roles(&role_mapping, guild_id, user_id, expected_role_names)
-                .await?;
-        }
+    /// Posts a message to a channel of the guild, `channel` is the channel name.
+    pub async fn send_message(
+        &self,
+        guild_id: &str,
+        channel: &str,
+        content: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (_, channel_ids) = self.get_channel_ids(guild_id.parse()?).await?;
+
+        let Some(channel_id) = channel_ids.get(&slugify_channel_name(channel)) else {
+            return Err(format!("Channel {channel} not found").into());
+        };
+
+        channel_id
+            .send_message(&self.client.http, CreateMessage::new().content(content))
+            .await?;
 
-        info!("User role synchronization completed");
-        Ok(role_mapping)
+        Ok(())
+    }
+
+    /// Returns the IDs of all categories and all other channels by name.
+    async fn get_channel_ids(
+        &self,
+        guild_id: u64,
+    ) -> Result<
+        (HashMap<String, ChannelId>, HashMap<String, ChannelId>),
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        let (categories, channels) = self
+            .client
+            .http
+            .get_channels(guild_id.into())
+            .await?
+            .into_iter()
+            .partition::<Vec<_>, _>(|channel| channel.kind == ChannelType::Category);
+
+        let by_name = |channels: Vec<serenity::all::GuildChannel>| {
+            channels
+                .into_iter()
+                .map(|channel| (channel.name, channel.id))
+                .collect::<HashMap<_, _>>()
+        };
+
+        Ok((by_name(categories), by_name(channels)))
     }
 
     async fn get_user_roles_from_database(
@@ -691,15 +807,25 @@ impl DiscordClient {
                     .get_event_discord_id(affiliate.id, event_id)
                     .await
                 {
-                    // Add team role in the format "team-{index}"
-                    user_roles
-                        .entry(discord_id.clone())
-                        .or_default()
-                        .push(format!("team-{:02}", team.index));
+                    // Members get "team-{index}", mentors "team-{index}-mentors"
+                    user_roles.entry(discord_id.clone()).or_default().extend(
+                        affiliate.roles.iter().map(|role| {
+                            PermissionRole::from_team_role(team.index, *role).to_string()
+                        }),
+                    );
                 }
             }
         }
 
+        // Unlinked accounts lose all managed roles
+        for discord_id in api_state
+            .user_service
+            .get_unlinked_discord_ids(event_id)
+            .await?
+        {
+            user_roles.entry(discord_id).or_default();
+        }
+
         // Log for debugging
         for (discord_id, roles) in &user_roles {
             let role_names: Vec<String> =
@@ -709,442 +835,4 @@ impl DiscordClient {
 
         Ok(user_roles)
     }
-
-    async fn sync_single_user_roles(
-        &self,
-        role_mapping: &HashMap<String, RoleId>,
-        guild_id: u64,
-        discord_user_id: String,
-        expected_role_names: Vec<String>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let discord_user_id: u64 = discord_user_id.parse().map_err(|e| {
-            format!("Failed to parse discord_user_id '{discord_user_id}' to u64: {e}")
-        })?;
-
-        // Get user's current roles in the guild
-        let Ok(member) = self
-            .client
-            .http
-            .get_member(guild_id.into(), discord_user_id.into())
-            .await
-        else {
-            warn!("User {} not found in guild, skipping", discord_user_id);
-            return Ok(());
-        };
-
-        let current_role_ids: HashSet<RoleId> = member.roles.iter().copied().collect();
-
-        // Find the expected role IDs (multiple)
-        let expected_role_ids: Vec<RoleId> = expected_role_names
-            .iter()
-            .filter_map(|role_name| {
-                if let Some(role_id) = role_mapping.get(role_name) {
-                    Some(*role_id)
-                } else {
-                    warn!(
-                        "Role '{}' not found in server for user {}",
-                        role_name, discord_user_id
-                    );
-                    None
-                }
-            })
-            .collect();
-
-        // Determine which roles to add and remove
-        let config_roles: HashSet<String> = role_mapping.keys().cloned().collect();
-
-        let roles_to_add: Vec<RoleId> = expected_role_ids
-            .iter()
-            .filter(|expected_role_id| !current_role_ids.contains(expected_role_id))
-            .copied() // converts &RoleId to RoleId
-            .collect();
-
-        // Remove other config roles that user shouldn't have
-        let roles_to_remove: Vec<RoleId> = member
-            .roles
-            .iter()
-            .filter_map(|role_id| {
-                role_mapping
-                    .iter()
-                    .find_map(|(name, id)| if id == role_id { Some(name) } else { None })
-                    .filter(|role_name| {
-                        !expected_role_names.contains(role_name)
-                            && config_roles.contains(*role_name)
-                    })
-                    .map(|_| *role_id)
-            })
-            .collect();
-
-        // Apply changes
-        if !roles_to_remove.is_empty() {
-            info!(
-                "Removing {} roles from user {}",
-                roles_to_remove.len(),
-                discord_user_id
-            );
-            for role_id in roles_to_remove {
-                self.client
-                    .http
-                    .remove_member_role(
-                        guild_id.into(),
-                        discord_user_id.into(),
-                        role_id,
-                        Some("Sync, removing role"),
-                    )
-                    .await?;
-            }
-        }
-
-        if !roles_to_add.is_empty() {
-            info!(
-                "Adding {} roles to user {}",
-                roles_to_add.len(),
-                discord_user_id
-            );
-            for role_id in roles_to_add {
-                self.client
-                    .http
-                    .add_member_role(
-                        guild_id.into(),
-                        discord_user_id.into(),
-                        role_id,
-                        Some("Sync, adding role"),
-                    )
-                    .await?;
-            }
-        }
-
-        Ok(())
-    }
-
-    async fn sync_roles(
-        &self,
-        api_state: &ApiState,
-        event_id: Uuid,
-        config: &mut DiscordConfig,
-        guild_id: u64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        info!("Syncing roles for guild: {}", guild_id);
-
-        // Get current roles from the server
-        let current_roles = self.client.http.get_guild_roles(guild_id.into()).await?;
-
-        // Create sets for comparison
-        let mut config_role_names: HashSet<String> =
-            config.roles.iter().map(|r| r.name.to_string()).collect();
-
-        let current_role_names: HashSet<String> =
-            current_roles.iter().map(|r| r.name.to_string()).collect();
-
-        // Get team roles from database
-        let teams = api_state.team_service.get_teams(event_id).await?;
-
-        // Add all team indexes to config roles names, in the format team-{index}
-        for team in teams {
-            // Only do that if indexes have been assigned
-            if team.index > 0 {
-                config_role_names.insert(format!("team-{:02}", team.index));
-                config.roles.push(RoleConfig {
-                    name: format!("team-{:02}", team.index),
-                    slug: format!("team-{:02}", team.index),
-                    mentionable: true,
-                    show_in_roster: false,
-                    color: "#acacac".to_string(),
-                    special: None,
-                });
-            }
-        }
-
-        // Total roles in config
-        let total_roles = config.roles.len();
-
-        // Log all config roles debg
-        debug!("Config roles: {:?}", config_role_names);
-        debug!("Current server roles: {:?}", current_role_names);
-
-        // 1. Create roles that are in config but not on server
-        for (index, role_config) in config.roles.iter().enumerate() {
-            if !current_role_names.contains(&role_config.name) {
-                let position = (total_roles - index) as u16;
-                info!(
-                    "Creating new role: {} (position: {})",
-                    role_config.name, position
-                );
-                self.create_role(role_config, guild_id, position).await?;
-            }
-        }
-
-        // 2. Update roles that exist in both config and server
-        for (index, role_config) in config.roles.iter().enumerate() {
-            if let Some(existing_role) = current_roles.iter().find(|r| r.name == role_config.name) {
-                let position = (total_roles - index) as u16;
-                debug!("Role exists: {}", role_config.name);
-                if self.role_needs_update(existing_role, role_config, position) {
-                    info!(
-                        "Updating role: {} (position: {})",
-                        role_config.name, position
-                    );
-                    self.update_role(existing_role.id, role_config, guild_id, position)
-                        .await?;
-                }
-            }
-        }
-
-        // 3. Delete roles that are on server but not in config (except special roles)
-        for existing_role in &current_roles {
-            // Don't delete @everyone role (has same ID as guild) or other special roles
-            if existing_role.id == guild_id || existing_role.managed {
-                continue;
-            }
-
-            if !config_role_names.contains(&(existing_role.name.to_string())) {
-                info!("Deleting role: {} (not in config)", existing_role.name);
-                self.delete_role(existing_role.id, guild_id).await?;
-            }
-        }
-
-        info!("Role synchronization completed");
-        Ok(())
-    }
-
-    async fn create_role(
-        &self,
-        role_config: &RoleConfig,
-        guild_id: u64,
-        position: u16,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let mut create_role = EditRole::default()
-            .name(&role_config.name) // Use slug as the role name in Discord
-            .colour(self.parse_color(&role_config.color)?)
-            .mentionable(role_config.mentionable)
-            .hoist(role_config.show_in_roster) // hoist means show in separate section in member list
-            .position(position);
-
-        // Set permissions based on special role type
-        let permissions = self.get_role_permissions(role_config.special.as_ref());
-        create_role = create_role.permissions(permissions);
-
-        self.client
-            .http
-            .create_role(
-                guild_id.into(),
-                &create_role,
-                Some("Discord sync: Creating role"),
-            )
-            .await?;
-        Ok(())
-    }
-
-    async fn update_role(
-        &self,
-        role_id: RoleId,
-        role_config: &RoleConfig,
-        guild_id: u64,
-        position: u16,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let edit_role = EditRole::new()
-            .name(&role_config.name)
-            .colour(self.parse_color(&role_config.color)?)
-            .mentionable(role_config.mentionable)
-            .hoist(role_config.show_in_roster)
-            .permissions(self.get_role_permissions(role_config.special.as_ref()))
-            .position(position);
-
-        self.client
-            .http
-            .edit_role(
-                guild_id.into(),
-                role_id,
-                &edit_role,
-                Some("Discord sync: updating role"),
-            )
-            .await?;
-        Ok(())
-    }
-
-    async fn delete_role(
-        &self,
-        role_id: RoleId,
-        guild_id: u64,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        self.client
-            .http
-            .delete_role(
-                guild_id.into(),
-                role_id,
-                Some("Discoed sync: deleting role"),
-            )
-            .await?;
-        Ok(())
-    }
-
-    fn role_needs_update(
-        &self,
-        existing_role: &Role,
-        role_config: &RoleConfig,
-        _expected_position: u16,
-    ) -> bool {
-        // Check if color needs update
-        let config_color = self.parse_color(&role_config.color).unwrap_or(0);
-        if existing_role.colour.0 != config_color {
-            return true;
-        }
-
-        // Check if name needs update
-        if existing_role.name != role_config.name {
-            return true;
-        }
-
-        // Check if mentionable needs update
-        if existing_role.mentionable != role_config.mentionable {
-            return true;
-        }
-
-        // Check if hoist (show in roster) needs update
-        if existing_role.hoist != role_config.show_in_roster {
-            return true;
-        }
-
-        // Check if permissions need update
-        let expected_permissions = self.get_role_permissions(role_config.special.as_ref());
-        if existing_role.permissions != expected_permissions {
-            return true;
-        }
-
-        // Check if position needs update
-        /*if existing_role.position != expected_position {
-            return true;
-        }*/
-
-        false
-    }
-
-    async fn set_everyone_permissions(
-        &self,
-        guild_id: u64,
-        default_permissions: &HashMap<String, bool>,
-    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
-        info!("Setting @everyone permissions: {:?}", default_permissions);
-
-        // Convert the permission map to Serenity Permissions
-        let permissions = self.parse_permissions(default_permissions);
-
-        // Update the @everyone role permissions
-        let everyone_role_id = RoleId::new(guild_id); // @everyone role ID is same as guild ID
-
-        self.client
-            .http
-            .edit_role(
-                guild_id.into(),
-                everyone_role_id,
-                &EditRole::new().permissions(permissions),
-                Some("Discord sync: updating @everyone permissions"),
-            )
-            .await?;
-
-        info!("Successfully updated @everyone permissions");
-        Ok(())
-    }
-
-    fn parse_discord_config(
-        &self,
-        config_yaml: &str,
-    ) -> Result<DiscordConfig, Box<dyn std::error::Error + Send + Sync>> {
-        if config_yaml.trim().is_empty() {
-            return Err("Empty Discord configuration".into());
-        }
-
-        let config: DiscordConfig = serde_yaml::from_str(config_yaml).map_err(|e| {
-            error!("Failed to parse Discord YAML configuration: {}", e);
-            Box::new(e) as Box<dyn std::error::Error + Send + Sync>
-        })?;
-
-        Ok(config)
-    }
-
-    fn parse_permissions(&self, permission_map: &HashMap<String, bool>) -> Permissions {
-        let mut permissions = Permissions::empty();
-
-        for (permission_name, allowed) in permission_map {
-            if let Some(permission) = self.map_permission_name(permission_name) {
-                if *allowed {
-                    permissions.insert(permission);
-                } else {
-                    permissions.remove(permission);
-                }
-            } else {
-                warn!("Unknown permission name in config: {}", permission_name);
-            }
-        }
-
-        // Debug print the final permissions
-        debug!("Computed permissions: {:?}", permissions);
-
-        permissions
-    }
-
-    fn parse_color(
-        &self,
-        color_str: &str,
-    ) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
-        // Handle hex colors like "#FF0000" or "FF0000"
-        let color_str = color_str.trim_start_matches('#');
-        u32::from_str_radix(color_str, 16)
-            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
-    }
-
-    fn get_role_permissions(&self, special_role: Option<&SpecialRole>) -> Permissions {
-        match special_role {
-            Some(SpecialRole::Admin) => Permissions::all(),
-            Some(_) | None => Permissions::empty(), // Other roles
-        }
-    }
-
-    fn map_permission_name(&self, name: &str) -> Option<Permissions> {
-        match name.to_lowercase().as_str() {
-            "create_instant_invite" => Some(Permissions::CREATE_INSTANT_INVITE),
-            "kick_members" => Some(Permissions::KICK_MEMBERS),
-            "ban_members" => Some(Permissions::BAN_MEMBERS),
-            "administrator" => Some(Permissions::ADMINISTRATOR),
-            "manage_channels" => Some(Permissions::MANAGE_CHANNELS),
-            "manage_guild" => Some(Permissions::MANAGE_GUILD),
-            "add_reactions" => Some(Permissions::ADD_REACTIONS),
-            "view_audit_log" => Some(Permissions::VIEW_AUDIT_LOG),
-            "priority_speaker" => Some(Permissions::PRIORITY_SPEAKER),
-            "stream" => Some(Permissions::STREAM),
-            "view_channel" => Some(Permissions::VIEW_CHANNEL),
-            "send_messages" => Some(Permissions::SEND_MESSAGES),
-            "send_tts_messages" => Some(Permissions::SEND_TTS_MESSAGES),
-            "manage_messages" => Some(Permissions::MANAGE_MESSAGES),
-            "embed_links" => Some(Permissions::EMBED_LINKS),
-            "attach_files" => Some(Permissions::ATTACH_FILES),
-            "read_message_history" => Some(Permissions::READ_MESSAGE_HISTORY),
-            "mention_everyone" => Some(Permissions::MENTION_EVERYONE),
-            "use_external_emojis" => Some(Permissions::USE_EXTERNAL_EMOJIS),
-            "view_guild_insights" => Some(Permissions::VIEW_GUILD_INSIGHTS),
-            "connect" => Some(Permissions::CONNECT),
-            "speak" => Some(Permissions::SPEAK),
-            "mute_members" => Some(Permissions::MUTE_MEMBERS),
-            "deafen_members" => Some(Permissions::DEAFEN_MEMBERS),
-            "move_members" => Some(Permissions::MOVE_MEMBERS),
-            "use_vad" => Some(Permissions::USE_VAD),
-            "change_nickname" => Some(Permissions::CHANGE_NICKNAME),
-            "manage_nicknames" => Some(Permissions::MANAGE_NICKNAMES),
-            "manage_roles" => Some(Permissions::MANAGE_ROLES),
-            "manage_webhooks" => Some(Permissions::MANAGE_WEBHOOKS),
-            "create_guild_expressions" => Some(Permissions::CREATE_GUILD_EXPRESSIONS),
-            "use_application_commands" => Some(Permissions::USE_APPLICATION_COMMANDS),
-            "request_to_speak" => Some(Permissions::REQUEST_TO_SPEAK),
-            "manage_events" => Some(Permissions::MANAGE_EVENTS),
-            "manage_threads" => Some(Permissions::MANAGE_THREADS),
-            "create_public_threads" => Some(Permissions::CREATE_PUBLIC_THREADS),
-            "create_private_threads" => Some(Permissions::CREATE_PRIVATE_THREADS),
-            "use_external_stickers" => Some(Permissions::USE_EXTERNAL_STICKERS),
-            "send_messages_in_threads" => Some(Permissions::SEND_MESSAGES_IN_THREADS),
-            "use_embedded_activities" => Some(Permissions::USE_EMBEDDED_ACTIVITIES),
-            "moderate_members" => Some(Permissions::MODERATE_MEMBERS),
-            "send_polls" => Some(Permissions::SEND_POLLS),
-            _ => None,
-        }
-    }
 }
@@ -0,0 +1,457 @@
+use crate::api_state::ApiState;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Europe::Zurich;
+use hackathon_portal_repositories::db::TeamRole;
+use hackathon_portal_repositories::discord::DiscordCredentials;
+use hackathon_portal_services::appointment::models::Appointment;
+use hackathon_portal_services::authorization::groups::Groups;
+use hackathon_portal_services::authorization::models::UserRoles;
+use hackathon_portal_services::event::models::Event;
+use hackathon_portal_services::sidequest::models::{Cooldown, TeamLeaderboardEntry};
+use hackathon_portal_services::team::models::Team;
+use serenity::all::{
+    Command, CommandInteraction, Context, CreateCommand, CreateInteractionResponse,
+    CreateInteractionResponseMessage, EventHandler, GatewayIntents, Interaction, Ready,
+};
+use serenity::async_trait;
+use serenity::Client;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Bots of events added or changed in the meantime are connected on the next refresh
+const BOTS_REFRESH_INTERVAL: Duration = Duration::from_mins(5);
+
+const MAX_SCHEDULE_ENTRIES: usize = 10;
+
+const MAX_LEADERBOARD_ENTRIES: usize = 10;
+
+type CommandResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Answers slash commands of all bots in use until the server shuts down, one gateway connection
+/// per bot.
+pub async fn run(api_state: ApiState) {
+    let mut bots = HashMap::<DiscordCredentials, JoinHandle<()>>::new();
+
+    loop {
+        match get_credentials(&api_state).await {
+            Ok(credentials) => {
+                // Bots no longer in use are disconnected, failed connections are retried
+                bots.retain(|bot_credentials, bot| {
+                    if !credentials.contains(bot_credentials) {
+                        bot.abort();
+                        return false;
+                    }
+
+                    !bot.is_finished()
+                });
+
+                for credentials in credentials {
+                    bots.entry(credentials).or_insert_with_key(|credentials| {
+                        tokio::spawn(connect(api_state.clone(), credentials.bot_token.clone()))
+                    });
+                }
+            }
+            Err(e) => error!("Failed to fetch Discord bots for slash commands: {}", e),
+        }
+
+        tokio::time::sleep(BOTS_REFRESH_INTERVAL).await;
+    }
+}
+
+/// Credentials of all events with a Discord server.
+async fn get_credentials(api_state: &ApiState) -> CommandResult<HashSet<DiscordCredentials>> {
+    let mut credentials = HashSet::new();
+
+    for event in api_state.event_service.get_events().await? {
+        let guilds = api_state
+            .discord_service
+            .get_synced_guilds(event.id)
+            .await?;
+
+        if guilds.is_empty() {
+            continue;
+        }
+
+        match api_state.discord_service.get_credentials(event.id).await {
+            Ok(event_credentials) => {
+                credentials.insert(event_credentials);
+            }
+            Err(e) => warn!(
+                "Failed to fetch Discord bot of event {} (ID: {}): {}",
+                event.name, event.id, e
+            ),
+        }
+    }
+
+    Ok(credentials)
+}
+
+async fn connect(api_state: ApiState, token: String) {
+    let client = Client::builder(&token, GatewayIntents::GUILDS)
+        .event_handler(CommandHandler { api_state })
+        .await;
+
+    let result = match client {
+        Ok(mut client) => client.start().await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        error!(
+            "Discord gateway connection for slash commands failed: {}",
+            e
+        );
+    }
+}
+
+fn commands() -> Vec<CreateCommand> {
+    vec![
+        CreateCommand::new("schedule").description("Upcoming appointments of the event"),
+        CreateCommand::new("cooldown").description("When you can attempt the next sidequest"),
+        CreateCommand::new("myteam").description("Addresses of your team, only visible to you"),
+        CreateCommand::new("leaderboard").description("Sidequest leaderboard of the teams"),
+    ]
+}
+
+struct Reply {
+    content: String,
+    /// Only visible to the caller
+    ephemeral: bool,
+}
+
+impl Reply {
+    fn public(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ephemeral: false,
+        }
+    }
+
+    fn private(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ephemeral: true,
+        }
+    }
+
+    fn forbidden(action: &str) -> Self {
+        Self::private(format!("You are not allowed to {action}"))
+    }
+}
+
+struct CommandHandler {
+    api_state: ApiState,
+}
+
+#[async_trait]
+impl EventHandler for CommandHandler {
+    async fn ready(&self, ctx: Context, data_about_bot: Ready) {
+        match Command::set_global_commands(&ctx.http, commands()).await {
+            Ok(commands) => info!(
+                "Registered {} slash commands for bot {}",
+                commands.len(),
+                data_about_bot.user.name
+            ),
+            Err(e) => error!(
+                "Failed to register slash commands for bot {}: {}",
+                data_about_bot.user.name, e
+            ),
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let Interaction::Command(command) = interaction else {
+            return;
+        };
+
+        let reply = match self.respond(&command).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                warn!(
+                    "Failed to answer slash command /{} of Discord user {}: {}",
+                    command.data.name, command.user.id, e
+                );
+                Reply::private("Something went wrong, please try again later")
+            }
+        };
+
+        let message = CreateInteractionResponseMessage::new()
+            .content(reply.content)
+            .ephemeral(reply.ephemeral);
+
+        if let Err(e) = command
+            .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+            .await
+        {
+            warn!(
+                "Failed to reply to slash command /{}: {}",
+                command.data.name, e
+            );
+        }
+    }
+}
+
+impl CommandHandler {
+    async fn respond(&self, command: &CommandInteraction) -> CommandResult<Reply> {
+        let Some(guild_id) = command.guild_id else {
+            return Ok(Reply::private(
+                "Commands are only available on the Discord server of an event",
+            ));
+        };
+
+        let Some((event_id, user_id)) = self
+            .api_state
+            .discord_service
+            .get_event_user_by_discord_id(&guild_id.to_string(), &command.user.id.to_string())
+            .await?
+        else {
+            return Ok(Reply::private(
+                "Link your Discord account in the portal to use commands",
+            ));
+        };
+
+        let event = self.api_state.event_service.get_event(event_id).await?;
+        let roles = self
+            .api_state
+            .authorization_service
+            .get_roles(user_id)
+            .await?;
+
+        match command.data.name.as_str() {
+            "schedule" => self.schedule(&event, &roles).await,
+            "cooldown" => self.cooldown(&event, &roles, user_id).await,
+            "myteam" => self.my_team(&event, &roles).await,
+            "leaderboard" => self.leaderboard(&event, &roles).await,
+            name => Ok(Reply::private(format!("Unknown command /{name}"))),
+        }
+    }
+
+    async fn schedule(&self, event: &Event, roles: &UserRoles) -> CommandResult<Reply> {
+        let groups = Groups::from_event(roles, event.id);
+
+        if !groups.can_view_event_internal(event.visibility) {
+            return Ok(Reply::forbidden("view appointments for this event"));
+        }
+
+        let appointments = self
+            .api_state
+            .appointment_service
+            .get_appointments(event.id)
+            .await?;
+
+        Ok(Reply::public(format_schedule(
+            &appointments,
+            Utc::now().naive_utc(),
+        )))
+    }
+
+    async fn cooldown(
+        &self,
+        event: &Event,
+        roles: &UserRoles,
+        user_id: Uuid,
+    ) -> CommandResult<Reply> {
+        let groups = Groups::from_event(roles, event.id);
+
+        if !groups.can_view_event_internal(event.visibility) {
+            return Ok(Reply::forbidden("view sidequest attempts for this event"));
+        }
+
+        let cooldown = self
+            .api_state
+            .sidequest_service
+            .get_cooldown(event.id, user_id)
+            .await?;
+
+        Ok(Reply::public(format_cooldown(&cooldown)))
+    }
+
+    async fn my_team(&self, event: &Event, roles: &UserRoles) -> CommandResult<Reply> {
+        let mut team = None;
+
+        for (team_id, team_roles) in &roles.team {
+            if !team_roles.contains(&TeamRole::Member) {
+                continue;
+            }
+
+            let candidate = self.api_state.team_service.get_team(*team_id).await?;
+
+            if candidate.event_id == event.id {
+                team = Some(candidate);
+                break;
+            }
+        }
+
+        let Some(team) = team else {
+            return Ok(Reply::private(
+                "You are not a member of a team of this event",
+            ));
+        };
+
+        let groups = Groups::from_event_and_team(roles, event.id, team.id);
+
+        if !groups.can_view_team_confidential(event.visibility) {
+            return Ok(Reply::forbidden("view the details of this team"));
+        }
+
+        Ok(Reply::private(format_team(&team)))
+    }
+
+    async fn leaderboard(&self, event: &Event, roles: &UserRoles) -> CommandResult<Reply> {
+        let groups = Groups::from_event(roles, event.id);
+
+        if !groups.can_view_event_internal(event.visibility) {
+            return Ok(Reply::forbidden(
+                "view sidequest leaderboard for this event",
+            ));
+        }
+
+        let leaderboard = self
+            .api_state
+            .sidequest_service
+            .get_leaderboard(event.id)
+            .await?;
+
+        Ok(Reply::public(format_leaderboard(&leaderboard)))
+    }
+}
+
+fn format_time(time: &NaiveDateTime) -> String {
+    Zurich
+        .from_utc_datetime(time)
+        .format("%a %H:%M")
+        .to_string()
+}
+
+/// Appointments that did not end yet, ordered by start.
+fn format_schedule(appointments: &[Appointment], now: NaiveDateTime) -> String {
+    let mut upcoming = appointments
+        .iter()
+        .filter(|appointment| appointment.end.unwrap_or(appointment.start) >= now)
+        .collect::<Vec<_>>();
+
+    if upcoming.is_empty() {
+        return "No upcoming appointments".to_string();
+    }
+
+    upcoming.sort_by_key(|appointment| appointment.start);
+
+    let lines = upcoming
+        .into_iter()
+        .take(MAX_SCHEDULE_ENTRIES)
+        .map(|appointment| {
+            let time = match appointment.end {
+                Some(end) => format!(
+                    "{} – {}",
+                    format_time(&appointment.start),
+                    Zurich.from_utc_datetime(&end).format("%H:%M")
+                ),
+                None => format_time(&appointment.start),
+            };
+
+            format!("• {time} **{}**", appointment.title)
+        })
+        .collect::<Vec<_>>();
+
+    format!("📅 **Schedule**\n{}", lines.join("\n"))
+}
+
+fn format_cooldown(cooldown: &Cooldown) -> String {
+    match cooldown.next_attempt {
+        Some(next_attempt) => format!(
+            "⏳ Your next sidequest attempt is possible at {}",
+            format_time(&next_attempt)
+        ),
+        None => "✅ You can attempt a sidequest now".to_string(),
+    }
+}
+
+fn format_team(team: &Team) -> String {
+    let mut lines = vec![format!("👥 **{}** (team {:02})", team.name, team.index)];
+
+    let addresses = [
+        ("Managed address", &team.managed_address),
+        ("Direct address", &team.direct_address),
+        ("Private address", &team.private_address),
+        ("Ingress URL", &team.ingress_url),
+    ];
+
+    lines.extend(
+        addresses
+            .into_iter()
+            .filter_map(|(label, value)| Some(format!("{label}: `{}`", value.as_ref()?))),
+    );
+
+    if lines.len() == 1 {
+        lines.push("No addresses assigned yet".to_string());
+    }
+
+    lines.join("\n")
+}
+
+fn format_leaderboard(leaderboard: &[TeamLeaderboardEntry]) -> String {
+    if leaderboard.is_empty() {
+        return "No teams yet".to_string();
+    }
+
+    let lines = leaderboard
+        .iter()
+        .take(MAX_LEADERBOARD_ENTRIES)
+        .enumerate()
+        .map(|(rank, entry)| format!("{}. **{}** {:.1}", rank + 1, entry.team_name, entry.score))
+        .collect::<Vec<_>>();
+
+    format!("🏆 **Sidequest leaderboard**\n{}", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn schedule_lists_appointments_not_ended() -> Result<(), Box<dyn std::error::Error>> {
+        let now = NaiveDateTime::parse_from_str("2025-10-17 10:00:00", "%Y-%m-%d %H:%M:%S")?;
+
+        let appointment = |title: &str, start: i64, end: Option<i64>| Appointment {
+            id: Uuid::new_v4(),
+            event_id: Uuid::nil(),
+            title: title.to_string(),
+            description: None,
+            content: None,
+            start: now + TimeDelta::minutes(start),
+            end: end.map(|end| now + TimeDelta::minutes(end)),
+        };
+
+        let appointments = [
+            appointment("Dinner", 480, None),
+            appointment("Breakfast", -120, Some(-60)),
+            appointment("Workshop", -30, Some(30)),
+        ];
+
+        assert_eq!(
+            format_schedule(&appointments, now),
+            "📅 **Schedule**\n• Fri 11:30 – 12:30 **Workshop**\n• Fri 20:00 **Dinner**"
+        );
+        assert_eq!(format_schedule(&[], now), "No upcoming appointments");
+
+        Ok(())
+    }
+
+    #[test]
+    fn leaderboard_ranks_teams() {
+        let entry = |team_name: &str, score: f64| TeamLeaderboardEntry {
+            team_id: Uuid::new_v4(),
+            team_name: team_name.to_string(),
+            score,
+        };
+
+        assert_eq!(
+            format_leaderboard(&[entry("Rustaceans", 12.5), entry("Gophers", 3.0)]),
+            "🏆 **Sidequest leaderboard**\n1. **Rustaceans** 12.5\n2. **Gophers** 3.0"
+        );
+    }
+}
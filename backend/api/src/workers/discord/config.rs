@@ -1,17 +1,28 @@
+use hackathon_portal_repositories::db::TeamRole;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serenity::model::permissions::Permissions;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use strum::Display;
+use tracing::warn;
+use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct DiscordConfig {
     pub default_permissions: HashMap<String, bool>,
     pub roles: Vec<RoleConfig>,
     pub categories: Vec<CategoryConfig>,
     pub channels: Vec<ChannelConfig>,
+    /// Channels created for every indexed team, defaults to a single text channel
+    #[serde(default)]
+    pub team_channels: Option<Vec<TeamChannelTemplate>>,
+    #[serde(default)]
+    pub announcements: Option<AnnouncementConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct RoleConfig {
     pub name: String,
     pub slug: String,
@@ -34,6 +45,7 @@ pub enum SpecialRole {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CategoryConfig {
     pub slug: String,
     pub name: String,
@@ -46,6 +58,7 @@ pub struct CategoryConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ChannelConfig {
     pub name: String,
     pub category: String,
@@ -59,6 +72,37 @@ pub struct ChannelConfig {
     pub voice: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TeamChannelTemplate {
+    /// `{index}` is replaced by the two digit team index and `{name}` by the team name
+    pub name: String,
+    /// Category slug
+    pub category: String,
+    #[serde(default)]
+    pub voice: bool,
+    #[serde(default = "default_team_channel_roles")]
+    pub visible_to: Vec<TeamChannelRole>,
+    #[serde(default = "default_team_channel_roles")]
+    pub writable_by: Vec<TeamChannelRole>,
+    #[serde(default = "default_team_channel_notification")]
+    pub default_notification: NotificationSetting,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TeamChannelRole {
+    Admin,
+    Mentor,
+    Stakeholder,
+    SidequestMaster,
+    All,
+    /// Members of the channel's team
+    Team,
+    /// Mentors assigned to the channel's team
+    TeamMentors,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum PermissionTarget {
@@ -75,6 +119,7 @@ pub enum PermissionRole {
     SidequestMaster,
     All,
     Team(i32),
+    TeamMentors(i32),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +130,321 @@ pub enum NotificationSetting {
     None,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnnouncementConfig {
+    /// Name of a channel in `channels`
+    pub channel: String,
+    #[serde(default = "default_appointment_reminder_minutes")]
+    pub appointment_reminder_minutes: i64,
+    #[serde(default)]
+    pub messages: AnnouncementMessages,
+}
+
+/// Message templates, an empty template disables the announcement. Supported placeholders are
+/// `{event}`, `{title}`, `{description}`, `{start}`, `{minutes}` and `{phase}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct AnnouncementMessages {
+    pub appointment: String,
+    pub phase: String,
+    pub voting_open: String,
+    pub finalists: String,
+}
+
+impl Default for AnnouncementMessages {
+    fn default() -> Self {
+        Self {
+            appointment: "⏰ **{title}** starts in {minutes} minutes ({start})\n{description}"
+                .to_string(),
+            phase: "📢 {event} is now in the **{phase}** phase".to_string(),
+            voting_open: "🗳️ Voting for {event} is now open!".to_string(),
+            finalists: "🏆 The finalists of {event} have been announced!".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct DiscordConfigValidationError {
+    /// 1-based line in the submitted YAML, `None` if it cannot be determined
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Path of the invalid value, e.g. `roles[2].color`
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl DiscordConfig {
+    pub fn parse(config_yaml: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if config_yaml.trim().is_empty() {
+            return Err("Empty Discord configuration".into());
+        }
+
+        Ok(serde_yaml::from_str(config_yaml)?)
+    }
+
+    /// Validates a submitted configuration, returns all errors instead of only the first one.
+    pub fn validate(config_yaml: &str) -> Result<Self, Vec<DiscordConfigValidationError>> {
+        let config: Self = match serde_yaml::from_str(config_yaml) {
+            Ok(config) => config,
+            Err(e) => {
+                let location = e.location();
+
+                return Err(vec![DiscordConfigValidationError {
+                    line: location.as_ref().map(serde_yaml::Location::line),
+                    column: location.as_ref().map(serde_yaml::Location::column),
+                    path: None,
+                    message: e.to_string(),
+                }]);
+            }
+        };
+
+        let mut errors = Vec::new();
+
+        for permission_name in config.default_permissions.keys() {
+            if map_permission_name(permission_name).is_none() {
+                errors.push(DiscordConfigValidationError {
+                    line: find_key_line(config_yaml, "default_permissions", permission_name),
+                    column: None,
+                    path: Some(format!("default_permissions.{permission_name}")),
+                    message: format!("Unknown permission '{permission_name}'"),
+                });
+            }
+        }
+
+        let mut error = |section: &str, index: usize, key: &str, value: &str, message: String| {
+            errors.push(DiscordConfigValidationError {
+                line: find_value_line(config_yaml, section, key, value, index),
+                column: None,
+                path: Some(format!("{section}[{index}].{key}")),
+                message,
+            });
+        };
+
+        let mut role_slugs = HashSet::new();
+
+        for (index, role) in config.roles.iter().enumerate() {
+            if !role_slugs.insert(role.slug.as_str()) {
+                error(
+                    "roles",
+                    index,
+                    "slug",
+                    &role.slug,
+                    format!("Duplicate role slug '{}'", role.slug),
+                );
+            }
+
+            if !is_valid_color(&role.color) {
+                error(
+                    "roles",
+                    index,
+                    "color",
+                    &role.color,
+                    format!(
+                        "Invalid color '{}', expected a hex color like '#FF0000'",
+                        role.color
+                    ),
+                );
+            }
+        }
+
+        let mut category_slugs = HashSet::new();
+
+        for (index, category) in config.categories.iter().enumerate() {
+            if !category_slugs.insert(category.slug.as_str()) {
+                error(
+                    "categories",
+                    index,
+                    "slug",
+                    &category.slug,
+                    format!("Duplicate category slug '{}'", category.slug),
+                );
+            }
+        }
+
+        for (index, channel) in config.channels.iter().enumerate() {
+            if !category_slugs.contains(channel.category.as_str()) {
+                error(
+                    "channels",
+                    index,
+                    "category",
+                    &channel.category,
+                    format!(
+                        "Channel '{}' references unknown category '{}'",
+                        channel.name, channel.category
+                    ),
+                );
+            }
+        }
+
+        for (index, template) in config.team_channels.iter().flatten().enumerate() {
+            errors.extend(template.validate(config_yaml, index, &category_slugs));
+        }
+
+        if let Some(announcements) = &config.announcements {
+            errors.extend(announcements.validate(config_yaml, &config.channels));
+        }
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn team_channel_templates(&self) -> Vec<TeamChannelTemplate> {
+        self.team_channels.clone().unwrap_or_else(|| {
+            vec![TeamChannelTemplate {
+                name: "{index}-{name}".to_string(),
+                category: "teams".to_string(),
+                voice: false,
+                visible_to: default_team_channel_roles(),
+                writable_by: default_team_channel_roles(),
+                default_notification: default_team_channel_notification(),
+            }]
+        })
+    }
+
+    /// Permissions of the `@everyone` role, unknown permission names are ignored.
+    pub fn everyone_permissions(&self) -> Permissions {
+        let mut permissions = Permissions::empty();
+
+        for (permission_name, allowed) in &self.default_permissions {
+            if let Some(permission) = map_permission_name(permission_name) {
+                if *allowed {
+                    permissions.insert(permission);
+                } else {
+                    permissions.remove(permission);
+                }
+            } else {
+                warn!("Unknown permission name in config: {}", permission_name);
+            }
+        }
+
+        permissions
+    }
+}
+
+impl TeamChannelTemplate {
+    fn validate(
+        &self,
+        config_yaml: &str,
+        index: usize,
+        category_slugs: &HashSet<&str>,
+    ) -> Vec<DiscordConfigValidationError> {
+        let mut errors = Vec::new();
+
+        if !self.name.contains("{index}") {
+            errors.push(DiscordConfigValidationError {
+                line: find_value_line(config_yaml, "team_channels", "name", &self.name, index),
+                column: None,
+                path: Some(format!("team_channels[{index}].name")),
+                message: format!(
+                    "Team channel name '{}' must contain {{index}} to be unique per team",
+                    self.name
+                ),
+            });
+        }
+
+        if !category_slugs.contains(self.category.as_str()) {
+            errors.push(DiscordConfigValidationError {
+                line: find_value_line(
+                    config_yaml,
+                    "team_channels",
+                    "category",
+                    &self.category,
+                    index,
+                ),
+                column: None,
+                path: Some(format!("team_channels[{index}].category")),
+                message: format!(
+                    "Team channel '{}' references unknown category '{}'",
+                    self.name, self.category
+                ),
+            });
+        }
+
+        errors
+    }
+}
+
+impl TeamChannelRole {
+    pub fn for_team(&self, index: i32) -> PermissionRole {
+        match self {
+            TeamChannelRole::Admin => PermissionRole::Admin,
+            TeamChannelRole::Mentor => PermissionRole::Mentor,
+            TeamChannelRole::Stakeholder => PermissionRole::Stakeholder,
+            TeamChannelRole::SidequestMaster => PermissionRole::SidequestMaster,
+            TeamChannelRole::All => PermissionRole::All,
+            TeamChannelRole::Team => PermissionRole::Team(index),
+            TeamChannelRole::TeamMentors => PermissionRole::TeamMentors(index),
+        }
+    }
+}
+
+impl PermissionRole {
+    /// Permission role granted by a role in an indexed team.
+    pub fn from_team_role(index: i32, role: TeamRole) -> Self {
+        match role {
+            TeamRole::Member => PermissionRole::Team(index),
+            TeamRole::Mentor => PermissionRole::TeamMentors(index),
+        }
+    }
+}
+
+impl AnnouncementConfig {
+    /// The announcement channel must be one of the configured channels.
+    fn validate(
+        &self,
+        config_yaml: &str,
+        channels: &[ChannelConfig],
+    ) -> Vec<DiscordConfigValidationError> {
+        let mut errors = Vec::new();
+        let channel = slugify_channel_name(&self.channel);
+
+        if !channels
+            .iter()
+            .any(|c| slugify_channel_name(&c.name) == channel)
+        {
+            errors.push(DiscordConfigValidationError {
+                line: find_key_line(config_yaml, "announcements", "channel"),
+                column: None,
+                path: Some("announcements.channel".to_string()),
+                message: format!(
+                    "Announcement channel '{}' is not defined in channels",
+                    self.channel
+                ),
+            });
+        }
+
+        if self.appointment_reminder_minutes <= 0 {
+            errors.push(DiscordConfigValidationError {
+                line: find_key_line(config_yaml, "announcements", "appointment_reminder_minutes"),
+                column: None,
+                path: Some("announcements.appointment_reminder_minutes".to_string()),
+                message: "Appointment reminder minutes must be positive".to_string(),
+            });
+        }
+
+        errors
+    }
+}
+
+impl RoleConfig {
+    pub fn parse_color(&self) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+        // Handle hex colors like "#FF0000" or "FF0000"
+        let color_str = self.color.trim_start_matches('#');
+        Ok(u32::from_str_radix(color_str, 16)?)
+    }
+
+    pub fn permissions(&self) -> Permissions {
+        match self.special {
+            Some(SpecialRole::Admin) => Permissions::all(),
+            Some(_) | None => Permissions::empty(), // Other roles
+        }
+    }
+}
+
 // Default value helpers
 fn default_true() -> bool {
     true
@@ -102,6 +462,22 @@ fn default_notification() -> NotificationSetting {
     NotificationSetting::None
 }
 
+fn default_appointment_reminder_minutes() -> i64 {
+    15
+}
+
+fn default_team_channel_roles() -> Vec<TeamChannelRole> {
+    vec![
+        TeamChannelRole::Admin,
+        TeamChannelRole::Team,
+        TeamChannelRole::TeamMentors,
+    ]
+}
+
+fn default_team_channel_notification() -> NotificationSetting {
+    NotificationSetting::All
+}
+
 // Implement default trait for PermissionTarget to handle null values
 impl Default for PermissionTarget {
     fn default() -> Self {
@@ -163,6 +539,7 @@ impl Display for PermissionRole {
             PermissionRole::SidequestMaster => write!(f, "SidequestMaster"),
             PermissionRole::All => write!(f, "All"),
             PermissionRole::Team(idx) => write!(f, "team-{idx:02}"),
+            PermissionRole::TeamMentors(idx) => write!(f, "team-{idx:02}-mentors"),
         }
     }
 }
@@ -185,12 +562,193 @@ impl ChannelConfig {
     pub fn get_writable_by_roles(&self) -> Vec<&PermissionRole> {
         self.writable_by.get_roles()
     }
+}
 
-    pub fn get_channel_type(&self) -> serenity::all::ChannelType {
-        if self.voice {
-            serenity::all::ChannelType::Voice
-        } else {
-            serenity::all::ChannelType::Text
+/// Discord channel names are lowercase without spaces, emojis are kept.
+pub fn slugify_channel_name(name: &str) -> String {
+    name.to_lowercase().replace(' ', "-")
+}
+
+fn is_valid_color(color: &str) -> bool {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Lines of a top-level section with their 1-based line numbers.
+fn section_lines<'a>(
+    config_yaml: &'a str,
+    section: &str,
+) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    let header = format!("{section}:");
+
+    config_yaml
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .skip_while(move |(_, line)| !line.starts_with(&header))
+        .skip(1)
+        .take_while(|(_, line)| line.is_empty() || line.starts_with([' ', '\t', '-', '#']))
+}
+
+/// Splits a line like `  - slug: admin # comment` into key and unquoted value.
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start().trim_start_matches("- ");
+    let (key, value) = line.split_once(':')?;
+    let value = value.split(" #").next().unwrap_or_default().trim();
+
+    Some((key.trim(), value.trim_matches(['"', '\''])))
+}
+
+fn find_key_line(config_yaml: &str, section: &str, key: &str) -> Option<usize> {
+    section_lines(config_yaml, section)
+        .find(|(_, line)| key_value(line).is_some_and(|(k, _)| k == key))
+        .map(|(number, _)| number)
+}
+
+/// Finds the line of `key: value` in the `index`-th item of a top-level list. Serde does not keep
+/// spans, so the YAML is scanned line by line.
+fn find_value_line(
+    config_yaml: &str,
+    section: &str,
+    key: &str,
+    value: &str,
+    index: usize,
+) -> Option<usize> {
+    let item_indent = section_lines(config_yaml, section)
+        .find(|(_, line)| line.trim_start().starts_with("- "))
+        .map(|(_, line)| line.len() - line.trim_start().len())?;
+
+    let mut item = None;
+
+    for (number, line) in section_lines(config_yaml, section) {
+        let indent = line.len() - line.trim_start().len();
+
+        if indent == item_indent && line.trim_start().starts_with("- ") {
+            item = Some(item.map_or(0, |item| item + 1));
         }
+
+        if item == Some(index) && key_value(line) == Some((key, value)) {
+            return Some(number);
+        }
+    }
+
+    None
+}
+
+fn map_permission_name(name: &str) -> Option<Permissions> {
+    match name.to_lowercase().as_str() {
+        "create_instant_invite" => Some(Permissions::CREATE_INSTANT_INVITE),
+        "kick_members" => Some(Permissions::KICK_MEMBERS),
+        "ban_members" => Some(Permissions::BAN_MEMBERS),
+        "administrator" => Some(Permissions::ADMINISTRATOR),
+        "manage_channels" => Some(Permissions::MANAGE_CHANNELS),
+        "manage_guild" => Some(Permissions::MANAGE_GUILD),
+        "add_reactions" => Some(Permissions::ADD_REACTIONS),
+        "view_audit_log" => Some(Permissions::VIEW_AUDIT_LOG),
+        "priority_speaker" => Some(Permissions::PRIORITY_SPEAKER),
+        "stream" => Some(Permissions::STREAM),
+        "view_channel" => Some(Permissions::VIEW_CHANNEL),
+        "send_messages" => Some(Permissions::SEND_MESSAGES),
+        "send_tts_messages" => Some(Permissions::SEND_TTS_MESSAGES),
+        "manage_messages" => Some(Permissions::MANAGE_MESSAGES),
+        "embed_links" => Some(Permissions::EMBED_LINKS),
+        "attach_files" => Some(Permissions::ATTACH_FILES),
+        "read_message_history" => Some(Permissions::READ_MESSAGE_HISTORY),
+        "mention_everyone" => Some(Permissions::MENTION_EVERYONE),
+        "use_external_emojis" => Some(Permissions::USE_EXTERNAL_EMOJIS),
+        "view_guild_insights" => Some(Permissions::VIEW_GUILD_INSIGHTS),
+        "connect" => Some(Permissions::CONNECT),
+        "speak" => Some(Permissions::SPEAK),
+        "mute_members" => Some(Permissions::MUTE_MEMBERS),
+        "deafen_members" => Some(Permissions::DEAFEN_MEMBERS),
+        "move_members" => Some(Permissions::MOVE_MEMBERS),
+        "use_vad" => Some(Permissions::USE_VAD),
+        "change_nickname" => Some(Permissions::CHANGE_NICKNAME),
+        "manage_nicknames" => Some(Permissions::MANAGE_NICKNAMES),
+        "manage_roles" => Some(Permissions::MANAGE_ROLES),
+        "manage_webhooks" => Some(Permissions::MANAGE_WEBHOOKS),
+        "create_guild_expressions" => Some(Permissions::CREATE_GUILD_EXPRESSIONS),
+        "use_application_commands" => Some(Permissions::USE_APPLICATION_COMMANDS),
+        "request_to_speak" => Some(Permissions::REQUEST_TO_SPEAK),
+        "manage_events" => Some(Permissions::MANAGE_EVENTS),
+        "manage_threads" => Some(Permissions::MANAGE_THREADS),
+        "create_public_threads" => Some(Permissions::CREATE_PUBLIC_THREADS),
+        "create_private_threads" => Some(Permissions::CREATE_PRIVATE_THREADS),
+        "use_external_stickers" => Some(Permissions::USE_EXTERNAL_STICKERS),
+        "send_messages_in_threads" => Some(Permissions::SEND_MESSAGES_IN_THREADS),
+        "use_embedded_activities" => Some(Permissions::USE_EMBEDDED_ACTIVITIES),
+        "moderate_members" => Some(Permissions::MODERATE_MEMBERS),
+        "send_polls" => Some(Permissions::SEND_POLLS),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r##"default_permissions:
+  view_channel: true
+roles:
+  - name: Tech
+    slug: admin
+    special: admin
+    color: "#FF0000"
+  - name: Mentor
+    slug: admin
+    color: "#8A2BE"
+categories:
+  - slug: orga
+    name: Organizers
+channels:
+  - name: general
+    category: orga
+  - name: help
+    category: "support" # typo
+"##;
+
+    #[test]
+    fn validate_shipped_config() {
+        let config = include_str!("../../../../../discord-viscon-2025.yaml");
+
+        assert!(DiscordConfig::validate(config).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_lines() {
+        let Err(errors) = DiscordConfig::validate(CONFIG) else {
+            panic!("config should be invalid");
+        };
+
+        let located = errors
+            .iter()
+            .map(|e| (e.path.as_deref(), e.line))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            located,
+            vec![
+                (Some("roles[1].slug"), Some(9)),
+                (Some("roles[1].color"), Some(10)),
+                (Some("channels[1].category"), Some(18)),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_syntax_and_model_errors() {
+        let Err(errors) = DiscordConfig::validate("roles:\n  - name: Tech\n    colour: red\n")
+        else {
+            panic!("config should be invalid");
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, Some(3));
+
+        let Err(errors) = DiscordConfig::validate("roles: [") else {
+            panic!("config should be invalid");
+        };
+
+        assert!(errors[0].line.is_some());
     }
 }
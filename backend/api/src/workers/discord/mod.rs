@@ -1,19 +1,38 @@
 use crate::api_state::ApiState;
-use crate::workers::discord::client::DiscordClient;
+use crate::error::ApiError;
+use crate::workers::discord::client::{DiscordClient, DiscordClients, DiscordSyncReport};
+use crate::workers::discord::plan::DiscordSyncPlan;
 use crate::ApiResult;
+use chrono::{NaiveDateTime, Utc};
+use hackathon_portal_repositories::db::DiscordSyncOutcome;
+use hackathon_portal_services::discord::models::{DiscordGuild, DiscordSyncStatus};
+use hackathon_portal_services::event::models::Event;
 use hackathon_portal_services::ServiceResult;
 use tokio_cron_scheduler::Job;
 use tracing::{debug, error, info, info_span, warn, Instrument};
+pub mod announcements;
 mod client;
-mod config;
+pub mod commands;
+pub mod config;
+pub mod plan;
+pub mod queue;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use uuid::Uuid;
 
 static DISCORD_SYNC_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
 
+/// Collects role changes made in quick succession into a single batch
+const QUEUE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+const QUEUE_RETRY_DELAY: Duration = Duration::from_secs(10);
+
 pub fn create_job(api_state: ApiState) -> ApiResult<Job> {
-    let job = Job::new_async("0 */2 * * * *", move |job_id, _| {
-        // every 2 minutes
+    // Role and team changes are synced by the queue worker, the full sync catches everything else
+    let job = Job::new_async("0 */30 * * * *", move |job_id, _| {
+        // every 30 minutes
         Box::pin({
             let api_state = api_state.clone();
             let span = info_span!("discord_sync", job_id = %job_id);
@@ -39,60 +58,577 @@ pub async fn run(api_state: ApiState) -> ServiceResult<()> {
         return Ok(());
     };
 
-    info!("Discord sync task running...");
-
-    // Launch Client here
-    match DiscordClient::new(&api_state).await {
-        Ok(client) => {
-            info!("Discord client created successfully");
-
-            // Get all events
-            let events = api_state.event_service.get_events().await?;
-            info!("Fetched {} events from the database", events.len());
-
-            // Loop through events to find Discord configurations
-            for event in events {
-                info!("Processing event: {} (ID: {})", event.name, event.id);
-
-                // Check if event has a discord server id
-                if let Some(discord_server_id) = &event.discord_server_id {
-                    debug!("Event has Discord server ID: {}", discord_server_id);
-
-                    // Check guild membership
-                    match client
-                        .check_guild_membership(discord_server_id.clone())
-                        .await
-                    {
-                        Ok(true) => {
-                            debug!("✅ Bot is part of target server: {}", discord_server_id);
-                            match client.sync_configuration(&api_state, &event).await {
-                                Ok(()) => info!(
-                                    "Successfully synced configuration for event: {} (ID: {})",
-                                    event.name, event.id
-                                ),
-                                Err(e) => error!(
-                                    "Failed to sync configuration for event: {} (ID: {}): {}",
-                                    event.name, event.id, e
-                                ),
-                            }
-                        }
-                        Ok(false) => {
-                            warn!("❌ Bot is NOT part of target server: {}", discord_server_id);
-                        }
-                        Err(e) => {
-                            error!("Failed to check guild membership: {}", e);
-                        }
+    let events = api_state.event_service.get_events().await?;
+    let mut clients = DiscordClients::default();
+
+    for event in events {
+        let guilds = match api_state.discord_service.get_synced_guilds(event.id).await {
+            Ok(guilds) if guilds.is_empty() => continue,
+            Ok(guilds) => guilds,
+            Err(e) => {
+                error!(
+                    "Failed to fetch Discord servers of event {} (ID: {}): {}",
+                    event.name, event.id, e
+                );
+                continue;
+            }
+        };
+
+        info!(
+            "Processing event: {} (ID: {}) with {} Discord servers",
+            event.name,
+            event.id,
+            guilds.len()
+        );
+
+        let client = match clients.get(&api_state, event.id).await {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to create Discord client: {}", e);
+
+                for guild in &guilds {
+                    let status = unsynced_status(
+                        event.id,
+                        &guild.guild_id,
+                        Utc::now().naive_utc(),
+                        DiscordSyncOutcome::Failed,
+                        None,
+                        e.to_string(),
+                    );
+                    record_status(&api_state, &status).await;
+                }
+
+                continue;
+            }
+        };
+
+        for (status, _) in sync_event(&api_state, client, &event, &guilds, &HashMap::new()).await {
+            match status.outcome {
+                DiscordSyncOutcome::Succeeded => info!(
+                    "Successfully synced Discord server {} for event: {} (ID: {})",
+                    status.guild_id, event.name, event.id
+                ),
+                outcome => warn!(
+                    %outcome,
+                    error = status.error,
+                    member_failures = status.member_failures.len(),
+                    "Failed to sync Discord server {} for event: {} (ID: {})",
+                    status.guild_id,
+                    event.name,
+                    event.id
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Syncs queued events and the roles of queued users until the server shuts down.
+pub async fn process_queue(api_state: ApiState) {
+    let queue = api_state.discord_sync_queue.clone();
+    let mut clients = DiscordClients::default();
+
+    loop {
+        queue.wait().await;
+        tokio::time::sleep(QUEUE_DEBOUNCE).await;
+
+        if let Some(backoff) = queue.backoff() {
+            info!(
+                backoff_ms = backoff.as_millis(),
+                "Backing off Discord queue"
+            );
+            tokio::time::sleep(backoff).await;
+        }
+
+        let events = queue.drain_events();
+        let mut batch = queue.drain();
+
+        if events.is_empty() && batch.is_empty() {
+            continue;
+        }
+
+        let guard = DISCORD_SYNC_LOCK.lock().await;
+        let span = info_span!("discord_queue", events = events.len(), users = batch.len());
+
+        // A full sync already covers the users of the event
+        batch.retain(|((event_id, _), _)| !events.contains(event_id));
+
+        let failed = async {
+            process_events(&api_state, &mut clients, events).await;
+            process_batch(&api_state, &mut clients, batch).await
+        }
+        .instrument(span)
+        .await;
+
+        drop(guard);
+
+        if failed {
+            tokio::time::sleep(QUEUE_RETRY_DELAY).await;
+        }
+    }
+}
+
+async fn process_events(api_state: &ApiState, clients: &mut DiscordClients, events: Vec<Uuid>) {
+    for event_id in events {
+        let (event, guilds) = match fetch_event_guilds(api_state, event_id).await {
+            Ok(event_guilds) => event_guilds,
+            Err(e) => {
+                warn!("Failed to fetch event {} for Discord sync: {}", event_id, e);
+                continue;
+            }
+        };
+
+        if guilds.is_empty() {
+            continue;
+        }
+
+        let client = match clients.get(api_state, event.id).await {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to create Discord client: {}", e);
+                continue;
+            }
+        };
+
+        for (status, _) in sync_event(api_state, client, &event, &guilds, &HashMap::new()).await {
+            if status.outcome != DiscordSyncOutcome::Succeeded {
+                warn!(
+                    outcome = %status.outcome,
+                    error = status.error,
+                    "Failed to sync Discord server {} for event {}",
+                    status.guild_id,
+                    event.id
+                );
+            }
+        }
+    }
+}
+
+/// Returns `true` if a sync failed and was requeued.
+async fn process_batch(
+    api_state: &ApiState,
+    clients: &mut DiscordClients,
+    batch: Vec<((Uuid, Uuid), u32)>,
+) -> bool {
+    let mut by_event = HashMap::<Uuid, Vec<(Uuid, u32)>>::new();
+
+    for ((event_id, user_id), attempts) in batch {
+        by_event
+            .entry(event_id)
+            .or_default()
+            .push((user_id, attempts));
+    }
+
+    let queue = &api_state.discord_sync_queue;
+    let mut failed = false;
+
+    for (event_id, users) in by_event {
+        let (event, guilds) = match fetch_event_guilds(api_state, event_id).await {
+            Ok(event_guilds) => event_guilds,
+            Err(e) => {
+                warn!("Failed to fetch event {} for Discord sync: {}", event_id, e);
+                continue;
+            }
+        };
+
+        let client = match clients.get(api_state, event.id).await {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to create Discord client: {}", e);
+                failed = true;
+
+                for (user_id, attempts) in users {
+                    queue.retry(event.id, user_id, attempts);
+                }
+
+                continue;
+            }
+        };
+
+        failed |= sync_members(api_state, client, &event, &guilds, users).await;
+    }
+
+    failed
+}
+
+async fn fetch_event_guilds(
+    api_state: &ApiState,
+    event_id: Uuid,
+) -> ServiceResult<(Event, Vec<DiscordGuild>)> {
+    let event = api_state.event_service.get_event(event_id).await?;
+    let guilds = api_state
+        .discord_service
+        .get_synced_guilds(event_id)
+        .await?;
+
+    Ok((event, guilds))
+}
+
+/// Syncs the roles of the users in all Discord servers of the event, returns `true` if a sync
+/// failed and was requeued.
+async fn sync_members(
+    api_state: &ApiState,
+    client: &DiscordClient,
+    event: &Event,
+    guilds: &[DiscordGuild],
+    users: Vec<(Uuid, u32)>,
+) -> bool {
+    let queue = &api_state.discord_sync_queue;
+    let mut failed = false;
+
+    for (user_id, attempts) in users {
+        let mut result = Ok(true);
+
+        for guild in guilds {
+            result = client.sync_member(api_state, event, guild, user_id).await;
+
+            if !matches!(result, Ok(true)) {
+                break;
+            }
+        }
+
+        match result {
+            Ok(true) => debug!("Synced Discord roles of user {}", user_id),
+            Ok(false) => {
+                info!(
+                    "Discord roles missing for event {}, running full sync",
+                    event.id
+                );
+
+                for (status, _) in
+                    sync_event(api_state, client, event, guilds, &HashMap::new()).await
+                {
+                    if status.outcome != DiscordSyncOutcome::Succeeded {
+                        warn!(
+                            outcome = %status.outcome,
+                            error = status.error,
+                            "Failed to sync Discord server {} for event {}",
+                            status.guild_id,
+                            event.id
+                        );
                     }
+                }
+
+                // The full sync already covers the remaining users of the event
+                break;
+            }
+            Err(e) => {
+                failed = true;
+
+                if queue.retry(event.id, user_id, attempts) {
+                    warn!(
+                        "Failed to sync Discord roles of user {}, retrying: {}",
+                        user_id, e
+                    );
                 } else {
-                    info!("Event does not have a Discord server ID configured.");
+                    error!("Failed to sync Discord roles of user {}: {}", user_id, e);
                 }
             }
         }
-        Err(e) => {
-            error!("Failed to create Discord client: {}", e);
+    }
+
+    failed
+}
+
+/// Syncs all Discord servers of an event and records their outcome. Unlinked Discord accounts are
+/// forgotten once their roles were removed from every server.
+async fn sync_event(
+    api_state: &ApiState,
+    client: &DiscordClient,
+    event: &Event,
+    guilds: &[DiscordGuild],
+    confirmed_deletions: &HashMap<String, String>,
+) -> Vec<(DiscordSyncStatus, Option<usize>)> {
+    let started_at = Utc::now().naive_utc();
+    let mut results = Vec::with_capacity(guilds.len());
+
+    for guild in guilds {
+        let confirmed = confirmed_deletions.get(&guild.guild_id).map(String::as_str);
+        results.push(sync_guild(api_state, client, event, guild, confirmed).await);
+    }
+
+    if results
+        .iter()
+        .all(|(status, _)| status.outcome == DiscordSyncOutcome::Succeeded)
+    {
+        if let Err(e) = api_state
+            .user_service
+            .clear_unlinked_discord_ids(event.id, started_at)
+            .await
+        {
+            error!(
+                "Failed to clear unlinked Discord accounts of event {}: {}",
+                event.id, e
+            );
         }
     }
 
-    info!("Discord server setup completed successfully");
+    results
+}
+
+/// Syncs a single Discord server of an event and records the outcome. Deletions of plans with
+/// too many of them are held back unless `confirmed` matches their digest, their number is then
+/// returned alongside the status.
+async fn sync_guild(
+    api_state: &ApiState,
+    client: &DiscordClient,
+    event: &Event,
+    guild: &DiscordGuild,
+    confirmed: Option<&str>,
+) -> (DiscordSyncStatus, Option<usize>) {
+    let started_at = Utc::now().naive_utc();
+    let start = Instant::now();
+    let guild_id = &guild.guild_id;
+
+    let status = match client.check_guild_membership(guild_id.clone()).await {
+        Ok(true) => None,
+        Ok(false) => Some(unsynced_status(
+            event.id,
+            guild_id,
+            started_at,
+            DiscordSyncOutcome::NotInGuild,
+            Some(false),
+            format!("Bot is not part of Discord server {guild_id}"),
+        )),
+        Err(e) => Some(unsynced_status(
+            event.id,
+            guild_id,
+            started_at,
+            DiscordSyncOutcome::Failed,
+            None,
+            e.to_string(),
+        )),
+    };
+
+    if let Some(status) = status {
+        record_status(api_state, &status).await;
+        return (status, None);
+    }
+
+    let mut report = DiscordSyncReport::default();
+    let result = plan_and_apply(api_state, client, event, guild, confirmed, &mut report).await;
+
+    let (outcome, error, skipped) = match result {
+        Ok(None) if report.member_failures.is_empty() => (DiscordSyncOutcome::Succeeded, None, None),
+        Ok(None) => (DiscordSyncOutcome::PartiallyFailed, None, None),
+        Ok(Some(destructive_changes)) => (
+            DiscordSyncOutcome::Skipped,
+            Some(format!(
+                "Held back {destructive_changes} deletions of roles, categories or channels until confirmed"
+            )),
+            Some(destructive_changes),
+        ),
+        Err(e) => (DiscordSyncOutcome::Failed, Some(e.to_string()), None),
+    };
+
+    let status = DiscordSyncStatus {
+        event_id: event.id,
+        guild_id: guild_id.clone(),
+        outcome,
+        bot_in_guild: Some(true),
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u32,
+        created: report.created,
+        updated: report.updated,
+        deleted: report.deleted,
+        member_failures: report.member_failures,
+        error,
+    };
+
+    record_status(api_state, &status).await;
+
+    (status, skipped)
+}
+
+/// Returns the number of deletions if they were held back, the rest of the plan is applied.
+async fn plan_and_apply(
+    api_state: &ApiState,
+    client: &DiscordClient,
+    event: &Event,
+    guild: &DiscordGuild,
+    confirmed: Option<&str>,
+    report: &mut DiscordSyncReport,
+) -> Result<Option<usize>, Box<dyn std::error::Error + Send + Sync>> {
+    let Some(plan) = client.plan(api_state, event, guild).await? else {
+        return Ok(None);
+    };
+
+    if plan.is_empty() {
+        debug!("Discord server already in sync");
+        return Ok(None);
+    }
+
+    let max_destructive_changes = api_state.discord_config.max_destructive_changes;
+
+    // A confirmation only covers the deletions that were previewed
+    if plan.destructive_changes > max_destructive_changes
+        && confirmed != Some(plan.deletions_digest.as_str())
+    {
+        warn!(
+            destructive_changes = plan.destructive_changes,
+            max_destructive_changes = max_destructive_changes,
+            "Discord sync would delete too many roles, categories or channels, holding them back until confirmed"
+        );
+
+        client.apply(&plan.without_deletions(), report).await?;
+
+        return Ok(Some(plan.destructive_changes));
+    }
+
+    client.apply(&plan, report).await?;
+
+    Ok(None)
+}
+
+/// Status of a sync that stopped before planning.
+fn unsynced_status(
+    event_id: Uuid,
+    guild_id: &str,
+    started_at: NaiveDateTime,
+    outcome: DiscordSyncOutcome,
+    bot_in_guild: Option<bool>,
+    error: String,
+) -> DiscordSyncStatus {
+    DiscordSyncStatus {
+        event_id,
+        guild_id: guild_id.to_string(),
+        outcome,
+        bot_in_guild,
+        started_at,
+        duration_ms: 0,
+        created: 0,
+        updated: 0,
+        deleted: 0,
+        member_failures: Vec::new(),
+        error: Some(error),
+    }
+}
+
+async fn record_status(api_state: &ApiState, status: &DiscordSyncStatus) {
+    if let Err(e) = api_state.discord_service.record_sync_status(status).await {
+        error!(
+            "Failed to record Discord sync status of event {} and server {}: {}",
+            status.event_id, status.guild_id, e
+        );
+    }
+}
+
+/// Computes the sync plans of all Discord servers of an event without touching them.
+pub async fn plan_event_sync(
+    api_state: &ApiState,
+    event: &Event,
+) -> ApiResult<Vec<DiscordSyncPlan>> {
+    let guilds = get_synced_guilds(api_state, event).await?;
+    let client = connect(api_state, event).await?;
+    let mut plans = Vec::with_capacity(guilds.len());
+
+    for guild in &guilds {
+        ensure_guild_membership(&client, &guild.guild_id).await?;
+
+        let plan = client
+            .plan(api_state, event, guild)
+            .await
+            .map_err(to_api_error)?
+            .ok_or_else(|| ApiError::BadRequest {
+                reason: format!("Discord server {} has no configuration", guild.guild_id),
+            })?;
+
+        plans.push(plan);
+    }
+
+    Ok(plans)
+}
+
+/// Syncs all Discord servers of an event right away. The limit on deletions is lifted for servers
+/// whose current plan has exactly the confirmed deletions digest. Fails if another sync is
+/// running or if deletions were held back, everything else is synced regardless.
+pub async fn apply_event_sync(
+    api_state: &ApiState,
+    event: &Event,
+    confirmed_deletions: &HashMap<String, String>,
+) -> ApiResult<Vec<DiscordSyncStatus>> {
+    let Ok(_guard) = DISCORD_SYNC_LOCK.try_lock() else {
+        return Err(ApiError::DiscordSyncRunning);
+    };
+
+    let guilds = get_synced_guilds(api_state, event).await?;
+    let client = connect(api_state, event).await?;
+
+    let results = sync_event(api_state, &client, event, &guilds, confirmed_deletions).await;
+
+    if let Some(destructive_changes) = results.iter().filter_map(|(_, skipped)| *skipped).max() {
+        return Err(ApiError::DiscordSyncNotConfirmed {
+            destructive_changes,
+            max_destructive_changes: api_state.discord_config.max_destructive_changes,
+        });
+    }
+
+    Ok(results.into_iter().map(|(status, _)| status).collect())
+}
+
+/// Returns the given Discord users that are members of the event's own Discord server.
+pub async fn get_guild_member_ids<'a>(
+    api_state: &ApiState,
+    event: &Event,
+    discord_user_ids: impl IntoIterator<Item = &'a String>,
+) -> ApiResult<HashSet<String>> {
+    let Some(discord_server_id) = &event.discord_server_id else {
+        return Err(ApiError::BadRequest {
+            reason: "Event has no Discord server".to_string(),
+        });
+    };
+
+    let client = connect(api_state, event).await?;
+    ensure_guild_membership(&client, discord_server_id).await?;
+
+    client
+        .get_guild_member_ids(discord_server_id, discord_user_ids)
+        .await
+        .map_err(to_api_error)
+}
+
+async fn get_synced_guilds(api_state: &ApiState, event: &Event) -> ApiResult<Vec<DiscordGuild>> {
+    let guilds = api_state
+        .discord_service
+        .get_synced_guilds(event.id)
+        .await?;
+
+    if guilds.is_empty() {
+        return Err(ApiError::BadRequest {
+            reason: "Event has no Discord server".to_string(),
+        });
+    }
+
+    Ok(guilds)
+}
+
+/// Creates a client for the bot of the event.
+async fn connect(api_state: &ApiState, event: &Event) -> ApiResult<DiscordClient> {
+    let credentials = api_state.discord_service.get_credentials(event.id).await?;
+
+    DiscordClient::new(api_state, &credentials.bot_token)
+        .await
+        .map_err(to_api_error)
+}
+
+async fn ensure_guild_membership(client: &DiscordClient, guild_id: &str) -> ApiResult<()> {
+    if !client
+        .check_guild_membership(guild_id.to_string())
+        .await
+        .map_err(to_api_error)?
+    {
+        return Err(ApiError::Discord {
+            message: format!("Bot is not part of Discord server {guild_id}"),
+        });
+    }
+
     Ok(())
 }
+
+#[allow(clippy::needless_pass_by_value)]
+fn to_api_error(e: Box<dyn std::error::Error + Send + Sync>) -> ApiError {
+    ApiError::Discord {
+        message: e.to_string(),
+    }
+}
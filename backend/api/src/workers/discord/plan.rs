@@ -0,0 +1,1031 @@
+use crate::workers::discord::config::{
+    slugify_channel_name, CategoryConfig, ChannelConfig, DiscordConfig, PermissionRole,
+    PermissionTarget, RoleConfig, TeamChannelRole,
+};
+use serde::{Deserialize, Serialize};
+use serenity::model::permissions::Permissions;
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Name of the role every member has, its ID equals the guild ID
+pub const EVERYONE_ROLE: &str = "@everyone";
+
+// region: current state of the guild
+
+pub struct GuildRole {
+    pub id: u64,
+    pub name: String,
+    pub color: u32,
+    pub mentionable: bool,
+    pub hoist: bool,
+    pub permissions: u64,
+    pub managed: bool,
+}
+
+pub struct GuildChannel {
+    pub id: u64,
+    pub name: String,
+    pub category: bool,
+    pub parent_id: Option<u64>,
+    /// Role overwrites only, member overwrites are left untouched
+    pub overwrites: Vec<GuildOverwrite>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GuildOverwrite {
+    pub role_id: u64,
+    pub allow: u64,
+    pub deny: u64,
+}
+
+pub struct GuildSnapshot {
+    pub guild_id: u64,
+    pub roles: Vec<GuildRole>,
+    pub channels: Vec<GuildChannel>,
+    /// Current role IDs of the event's linked members, members not in the guild are missing
+    pub members: HashMap<String, Vec<u64>>,
+}
+
+impl GuildSnapshot {
+    fn role_by_name(&self, name: &str) -> Option<&GuildRole> {
+        self.roles.iter().find(|role| role.name == name)
+    }
+
+    fn role_by_id(&self, id: u64) -> Option<&GuildRole> {
+        self.roles.iter().find(|role| role.id == id)
+    }
+
+    fn categories(&self) -> impl Iterator<Item = &GuildChannel> {
+        self.channels.iter().filter(|channel| channel.category)
+    }
+
+    fn category_by_name(&self, name: &str) -> Option<&GuildChannel> {
+        self.categories().find(|category| category.name == name)
+    }
+}
+
+// endregion
+
+// region: desired state of the guild
+
+pub struct DesiredGuild {
+    pub everyone_permissions: u64,
+    pub roles: Vec<DiscordPlannedRole>,
+    /// Maps permission roles (e.g. `Admin`, `team-01`) to Discord role names
+    pub role_names: HashMap<String, String>,
+    pub categories: Vec<CategoryConfig>,
+    pub channels: Vec<ChannelConfig>,
+    pub team_channels: Vec<TeamChannel>,
+    /// Expected permission roles of the event's linked members by Discord user ID
+    pub member_roles: HashMap<String, Vec<String>>,
+}
+
+/// Channel created from a team channel template
+pub struct TeamChannel {
+    pub name: String,
+    /// Parts of the name around the team name, used to find the channel after a team was renamed
+    pub rename_pattern: Option<(String, String)>,
+}
+
+impl DesiredGuild {
+    /// Extends the configuration with roles and channels per team, `teams` contains the index
+    /// and name of all indexed teams.
+    pub fn new(
+        mut config: DiscordConfig,
+        teams: &[(i32, String)],
+        mut member_roles: HashMap<String, Vec<String>>,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let templates = config.team_channel_templates();
+        let mut team_channels = Vec::new();
+
+        // Mentor roles are only created if a template grants them access
+        let uses_team_mentors = templates.iter().any(|template| {
+            template.visible_to.contains(&TeamChannelRole::TeamMentors)
+                || template.writable_by.contains(&TeamChannelRole::TeamMentors)
+        });
+
+        for (index, name) in teams {
+            let mut team_roles = vec![PermissionRole::Team(*index)];
+
+            if uses_team_mentors {
+                team_roles.push(PermissionRole::TeamMentors(*index));
+            }
+
+            for role in team_roles {
+                config.roles.push(RoleConfig {
+                    name: role.to_string(),
+                    slug: role.to_string(),
+                    mentionable: true,
+                    show_in_roster: false,
+                    color: "#acacac".to_string(),
+                    special: None,
+                });
+            }
+
+            for template in &templates {
+                let pattern =
+                    slugify_channel_name(&template.name.replace("{index}", &format!("{index:02}")));
+                let channel_name = pattern.replace("{name}", &slugify_channel_name(name));
+
+                team_channels.push(TeamChannel {
+                    name: channel_name.clone(),
+                    rename_pattern: pattern
+                        .split_once("{name}")
+                        .map(|(prefix, suffix)| (prefix.to_string(), suffix.to_string())),
+                });
+
+                let roles = |roles: &[TeamChannelRole]| {
+                    PermissionTarget::Multiple(
+                        roles.iter().map(|role| role.for_team(*index)).collect(),
+                    )
+                };
+
+                config.channels.push(ChannelConfig {
+                    name: channel_name,
+                    category: template.category.clone(),
+                    voice: template.voice,
+                    visible_to: roles(&template.visible_to),
+                    writable_by: roles(&template.writable_by),
+                    default_notification: template.default_notification.clone(),
+                });
+            }
+        }
+
+        for channel in &mut config.channels {
+            channel.name = slugify_channel_name(&channel.name);
+        }
+
+        let mut role_names = HashMap::new();
+        let mut roles = Vec::with_capacity(config.roles.len());
+        let total_roles = config.roles.len();
+
+        for (index, role_config) in config.roles.iter().enumerate() {
+            if let Some(special) = &role_config.special {
+                role_names.insert(special.to_string(), role_config.name.clone());
+            } else if role_config.name.starts_with("team-") {
+                role_names.insert(role_config.name.clone(), role_config.name.clone());
+            }
+
+            roles.push(DiscordPlannedRole {
+                name: role_config.name.clone(),
+                color: role_config.parse_color()?,
+                mentionable: role_config.mentionable,
+                hoist: role_config.show_in_roster,
+                permissions: role_config.permissions().bits(),
+                position: (total_roles - index) as u16,
+            });
+        }
+
+        // Team mentors are linked regardless of whether a template uses their role
+        for roles in member_roles.values_mut() {
+            roles.retain(|role| !role.starts_with("team-") || role_names.contains_key(role));
+        }
+
+        Ok(Self {
+            everyone_permissions: config.everyone_permissions().bits(),
+            roles,
+            role_names,
+            categories: config.categories,
+            channels: config.channels,
+            team_channels,
+            member_roles,
+        })
+    }
+
+    fn overwrites(
+        &self,
+        name: &str,
+        visible_to: &[&PermissionRole],
+        writable_by: &[&PermissionRole],
+    ) -> Vec<DiscordPlannedOverwrite> {
+        // Default: deny view channel for everyone
+        let mut everyone_allow = Permissions::empty();
+        let mut everyone_deny = Permissions::VIEW_CHANNEL;
+
+        if visible_to.contains(&&PermissionRole::All) {
+            everyone_allow |= Permissions::VIEW_CHANNEL;
+            everyone_deny &= !Permissions::VIEW_CHANNEL;
+        }
+
+        if writable_by.contains(&&PermissionRole::All) {
+            everyone_allow |= Permissions::SEND_MESSAGES;
+        }
+
+        let mut overwrites = vec![DiscordPlannedOverwrite {
+            role: EVERYONE_ROLE.to_string(),
+            allow: everyone_allow.bits(),
+            deny: everyone_deny.bits(),
+        }];
+
+        let role_name = |role: &PermissionRole| {
+            let role_name = self.role_names.get(&role.to_string());
+
+            if role_name.is_none() {
+                warn!(
+                    "Role {} not found in role mapping for cat/chan {}",
+                    role, name
+                );
+            }
+
+            role_name.cloned()
+        };
+
+        for role in visible_to
+            .iter()
+            .filter(|role| ***role != PermissionRole::All)
+        {
+            if let Some(role) = role_name(role) {
+                overwrites.push(DiscordPlannedOverwrite {
+                    role,
+                    allow: Permissions::VIEW_CHANNEL.bits(),
+                    deny: 0,
+                });
+            }
+        }
+
+        for role in writable_by
+            .iter()
+            .filter(|role| ***role != PermissionRole::All)
+        {
+            let Some(role) = role_name(role) else {
+                continue;
+            };
+
+            if let Some(overwrite) = overwrites.iter_mut().find(|ow| ow.role == role) {
+                overwrite.allow |= Permissions::SEND_MESSAGES.bits();
+            } else {
+                overwrites.push(DiscordPlannedOverwrite {
+                    role,
+                    allow: (Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL).bits(),
+                    deny: 0,
+                });
+            }
+        }
+
+        overwrites
+    }
+}
+
+// endregion
+
+// region: plan
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct DiscordPlannedRole {
+    pub name: String,
+    pub color: u32,
+    pub mentionable: bool,
+    /// Show members separately in the member list
+    pub hoist: bool,
+    pub permissions: u64,
+    pub position: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DiscordRoleChange {
+    Create {
+        role: DiscordPlannedRole,
+    },
+    Update {
+        id: String,
+        role: DiscordPlannedRole,
+    },
+    Delete {
+        id: String,
+        name: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DiscordCategoryChange {
+    Create { name: String },
+    Delete { id: String, name: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DiscordChannelChange {
+    Create {
+        name: String,
+        category: Option<String>,
+        voice: bool,
+    },
+    Rename {
+        id: String,
+        from: String,
+        to: String,
+    },
+    Move {
+        id: String,
+        name: String,
+        category: String,
+    },
+    Delete {
+        id: String,
+        name: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct DiscordPlannedOverwrite {
+    /// Role name, `@everyone` for the default overwrite
+    pub role: String,
+    pub allow: u64,
+    pub deny: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordOverwriteChange {
+    /// Category or channel name
+    pub target: String,
+    pub category: bool,
+    /// Replaces all role overwrites of the target
+    pub overwrites: Vec<DiscordPlannedOverwrite>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordMemberRoleChange {
+    pub discord_user_id: String,
+    /// Role names
+    pub add: Vec<String>,
+    /// Role names
+    pub remove: Vec<String>,
+}
+
+/// Changes required to bring a guild in line with the event's Discord configuration, applied
+/// in the order of the fields with deletions last.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordSyncPlan {
+    pub event_id: Uuid,
+    pub guild_id: String,
+    /// New permissions of `@everyone`, `None` if unchanged
+    pub everyone_permissions: Option<u64>,
+    pub roles: Vec<DiscordRoleChange>,
+    pub member_roles: Vec<DiscordMemberRoleChange>,
+    pub categories: Vec<DiscordCategoryChange>,
+    pub channels: Vec<DiscordChannelChange>,
+    pub overwrites: Vec<DiscordOverwriteChange>,
+    /// Number of roles, categories and channels the plan deletes
+    pub destructive_changes: usize,
+    /// SHA-256 of the sorted IDs of the deleted roles, categories and channels, confirms exactly
+    /// these deletions when applying the sync
+    pub deletions_digest: String,
+}
+
+impl DiscordSyncPlan {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.everyone_permissions.is_none()
+            && self.roles.is_empty()
+            && self.member_roles.is_empty()
+            && self.categories.is_empty()
+            && self.channels.is_empty()
+            && self.overwrites.is_empty()
+    }
+
+    /// The plan without any deletions, e.g. to apply it while deletions await confirmation.
+    #[must_use]
+    pub fn without_deletions(&self) -> Self {
+        let mut plan = Self {
+            roles: self
+                .roles
+                .iter()
+                .filter(|change| !matches!(change, DiscordRoleChange::Delete { .. }))
+                .cloned()
+                .collect(),
+            categories: self
+                .categories
+                .iter()
+                .filter(|change| !matches!(change, DiscordCategoryChange::Delete { .. }))
+                .cloned()
+                .collect(),
+            channels: self
+                .channels
+                .iter()
+                .filter(|change| !matches!(change, DiscordChannelChange::Delete { .. }))
+                .cloned()
+                .collect(),
+            ..self.clone()
+        };
+
+        plan.summarize_deletions();
+
+        plan
+    }
+
+    fn summarize_deletions(&mut self) {
+        let roles = self.roles.iter().filter_map(|change| match change {
+            DiscordRoleChange::Delete { id, .. } => Some(id.as_str()),
+            _ => None,
+        });
+
+        let categories = self.categories.iter().filter_map(|change| match change {
+            DiscordCategoryChange::Delete { id, .. } => Some(id.as_str()),
+            DiscordCategoryChange::Create { .. } => None,
+        });
+
+        let channels = self.channels.iter().filter_map(|change| match change {
+            DiscordChannelChange::Delete { id, .. } => Some(id.as_str()),
+            _ => None,
+        });
+
+        let mut ids = roles.chain(categories).chain(channels).collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        self.destructive_changes = ids.len();
+        self.deletions_digest = hex::encode(Sha256::digest(ids.join(",")));
+    }
+}
+
+pub fn plan(event_id: Uuid, guild: &GuildSnapshot, desired: &DesiredGuild) -> DiscordSyncPlan {
+    let everyone_permissions = guild
+        .role_by_id(guild.guild_id)
+        .filter(|everyone| everyone.permissions == desired.everyone_permissions)
+        .map_or(Some(desired.everyone_permissions), |_| None);
+
+    let channels = plan_channels(guild, desired);
+    let overwrites = plan_overwrites(guild, desired, &channels);
+
+    let mut plan = DiscordSyncPlan {
+        event_id,
+        guild_id: guild.guild_id.to_string(),
+        everyone_permissions,
+        roles: plan_roles(guild, desired),
+        member_roles: plan_member_roles(guild, desired),
+        categories: plan_categories(guild, desired),
+        channels,
+        overwrites,
+        destructive_changes: 0,
+        deletions_digest: String::new(),
+    };
+
+    plan.summarize_deletions();
+
+    plan
+}
+
+/// Plans the role changes of the members in the snapshot only, the guild's roles and channels
+/// are left as they are.
+pub fn plan_members(
+    event_id: Uuid,
+    guild: &GuildSnapshot,
+    desired: &DesiredGuild,
+) -> DiscordSyncPlan {
+    let mut plan = DiscordSyncPlan {
+        event_id,
+        guild_id: guild.guild_id.to_string(),
+        everyone_permissions: None,
+        roles: Vec::new(),
+        member_roles: plan_member_roles(guild, desired),
+        categories: Vec::new(),
+        channels: Vec::new(),
+        overwrites: Vec::new(),
+        destructive_changes: 0,
+        deletions_digest: String::new(),
+    };
+
+    plan.summarize_deletions();
+
+    plan
+}
+
+fn plan_roles(guild: &GuildSnapshot, desired: &DesiredGuild) -> Vec<DiscordRoleChange> {
+    let mut changes = Vec::new();
+
+    for role in &desired.roles {
+        match guild.role_by_name(&role.name) {
+            None => changes.push(DiscordRoleChange::Create { role: role.clone() }),
+            // Positions are only set on creation, Discord shifts them around on its own
+            Some(existing)
+                if existing.color != role.color
+                    || existing.mentionable != role.mentionable
+                    || existing.hoist != role.hoist
+                    || existing.permissions != role.permissions =>
+            {
+                changes.push(DiscordRoleChange::Update {
+                    id: existing.id.to_string(),
+                    role: role.clone(),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    let desired_names = desired
+        .roles
+        .iter()
+        .map(|role| role.name.as_str())
+        .collect::<HashSet<_>>();
+
+    for existing in &guild.roles {
+        // Don't delete @everyone (has same ID as guild) or roles managed by integrations
+        if existing.id == guild.guild_id || existing.managed {
+            continue;
+        }
+
+        if !desired_names.contains(existing.name.as_str()) {
+            changes.push(DiscordRoleChange::Delete {
+                id: existing.id.to_string(),
+                name: existing.name.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+fn plan_member_roles(
+    guild: &GuildSnapshot,
+    desired: &DesiredGuild,
+) -> Vec<DiscordMemberRoleChange> {
+    let managed_roles = desired
+        .role_names
+        .values()
+        .map(String::as_str)
+        .collect::<HashSet<_>>();
+
+    let mut member_roles = desired.member_roles.iter().collect::<Vec<_>>();
+    member_roles.sort_by_key(|(discord_user_id, _)| *discord_user_id);
+
+    let mut changes = Vec::new();
+
+    for (discord_user_id, expected_roles) in member_roles {
+        let Some(current_role_ids) = guild.members.get(discord_user_id) else {
+            continue;
+        };
+
+        let current_roles = current_role_ids
+            .iter()
+            .filter_map(|id| guild.role_by_id(*id))
+            .map(|role| role.name.as_str())
+            .collect::<HashSet<_>>();
+
+        let expected_roles = expected_roles
+            .iter()
+            .filter_map(|role| {
+                let role_name = desired.role_names.get(role);
+
+                if role_name.is_none() {
+                    warn!(
+                        "Role '{}' not found in config for user {}",
+                        role, discord_user_id
+                    );
+                }
+
+                role_name.map(String::as_str)
+            })
+            .collect::<HashSet<_>>();
+
+        let mut add = expected_roles
+            .difference(&current_roles)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        // Only remove roles managed by the sync that the member shouldn't have
+        let mut remove = current_roles
+            .iter()
+            .filter(|role| managed_roles.contains(*role) && !expected_roles.contains(*role))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        if add.is_empty() && remove.is_empty() {
+            continue;
+        }
+
+        add.sort();
+        remove.sort();
+
+        changes.push(DiscordMemberRoleChange {
+            discord_user_id: discord_user_id.clone(),
+            add,
+            remove,
+        });
+    }
+
+    changes
+}
+
+fn plan_categories(guild: &GuildSnapshot, desired: &DesiredGuild) -> Vec<DiscordCategoryChange> {
+    let mut changes = Vec::new();
+
+    for category in &desired.categories {
+        if guild.category_by_name(&category.name).is_none() {
+            changes.push(DiscordCategoryChange::Create {
+                name: category.name.clone(),
+            });
+        }
+    }
+
+    for existing in guild.categories() {
+        if !desired.categories.iter().any(|c| c.name == existing.name) {
+            changes.push(DiscordCategoryChange::Delete {
+                id: existing.id.to_string(),
+                name: existing.name.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+fn plan_channels(guild: &GuildSnapshot, desired: &DesiredGuild) -> Vec<DiscordChannelChange> {
+    let mut existing_channels = guild
+        .channels
+        .iter()
+        .filter(|channel| !channel.category)
+        .map(|channel| (channel.name.clone(), channel))
+        .collect::<HashMap<_, _>>();
+
+    let mut changes = Vec::new();
+    let mut renamed_channels = HashSet::new();
+
+    let desired_names = desired
+        .channels
+        .iter()
+        .map(|channel| channel.name.as_str())
+        .collect::<HashSet<_>>();
+
+    let mut team_channels = desired
+        .team_channels
+        .iter()
+        .filter(|channel| !existing_channels.contains_key(&channel.name))
+        .filter_map(|channel| Some((&channel.name, channel.rename_pattern.as_ref()?)))
+        .collect::<Vec<_>>();
+
+    // More specific patterns first, so `01-{name}` doesn't match the channel of `01-{name}-voice`
+    team_channels.sort_by_key(|(_, (prefix, suffix))| Reverse(prefix.len() + suffix.len()));
+
+    // Team names may change, their channels are matched by the parts around the name
+    for (expected_name, (prefix, suffix)) in team_channels {
+        let existing_team_channel = existing_channels.iter().find(|(existing_name, _)| {
+            existing_name.len() > prefix.len() + suffix.len()
+                && existing_name.starts_with(prefix.as_str())
+                && existing_name.ends_with(suffix.as_str())
+                && !desired_names.contains(existing_name.as_str())
+                && !renamed_channels.contains(*existing_name)
+        });
+
+        if let Some((old_name, channel)) = existing_team_channel {
+            let (old_name, channel) = (old_name.clone(), *channel);
+
+            changes.push(DiscordChannelChange::Rename {
+                id: channel.id.to_string(),
+                from: old_name.clone(),
+                to: expected_name.clone(),
+            });
+
+            existing_channels.insert(expected_name.clone(), channel);
+            renamed_channels.insert(old_name);
+        }
+    }
+
+    let mut channels_to_keep = HashSet::new();
+
+    for channel_config in &desired.channels {
+        channels_to_keep.insert(channel_config.name.as_str());
+
+        let category = desired
+            .categories
+            .iter()
+            .find(|category| category.slug == channel_config.category)
+            .map(|category| category.name.clone());
+
+        if category.is_none() {
+            warn!(
+                "Category slug {} not found for channel {}",
+                channel_config.category, channel_config.name
+            );
+        }
+
+        if let Some(existing) = existing_channels.get(&channel_config.name) {
+            let Some(category) = category else {
+                continue;
+            };
+
+            let current_category = existing
+                .parent_id
+                .and_then(|parent_id| guild.categories().find(|c| c.id == parent_id));
+
+            if current_category.is_none_or(|current| current.name != category) {
+                changes.push(DiscordChannelChange::Move {
+                    id: existing.id.to_string(),
+                    name: channel_config.name.clone(),
+                    category,
+                });
+            }
+        } else {
+            changes.push(DiscordChannelChange::Create {
+                name: channel_config.name.clone(),
+                category,
+                voice: channel_config.voice,
+            });
+        }
+    }
+
+    let mut deleted_channels = existing_channels
+        .iter()
+        .filter(|(name, _)| {
+            !channels_to_keep.contains(name.as_str()) && !renamed_channels.contains(*name)
+        })
+        .map(|(name, channel)| DiscordChannelChange::Delete {
+            id: channel.id.to_string(),
+            name: name.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    deleted_channels.sort_by_key(|change| match change {
+        DiscordChannelChange::Delete { name, .. } => name.clone(),
+        _ => String::new(),
+    });
+
+    changes.extend(deleted_channels);
+
+    changes
+}
+
+fn plan_overwrites(
+    guild: &GuildSnapshot,
+    desired: &DesiredGuild,
+    channel_changes: &[DiscordChannelChange],
+) -> Vec<DiscordOverwriteChange> {
+    let mut changes = Vec::new();
+
+    let renamed_channels = channel_changes
+        .iter()
+        .filter_map(|change| match change {
+            DiscordChannelChange::Rename { from, to, .. } => Some((to.as_str(), from.as_str())),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let targets = desired
+        .categories
+        .iter()
+        .map(|c| {
+            (
+                c.name.as_str(),
+                true,
+                c.get_visible_to_roles(),
+                c.get_writable_by_roles(),
+            )
+        })
+        .chain(desired.channels.iter().map(|c| {
+            (
+                c.name.as_str(),
+                false,
+                c.get_visible_to_roles(),
+                c.get_writable_by_roles(),
+            )
+        }));
+
+    for (name, category, visible_to, writable_by) in targets {
+        let overwrites = desired.overwrites(name, &visible_to, &writable_by);
+
+        let current_name = renamed_channels.get(name).copied().unwrap_or(name);
+        let existing = guild
+            .channels
+            .iter()
+            .find(|channel| channel.category == category && channel.name == current_name);
+
+        let unchanged = existing.is_some_and(|existing| {
+            let resolved = overwrites
+                .iter()
+                .map(|overwrite| {
+                    guild
+                        .role_by_name(&overwrite.role)
+                        .map(|role| GuildOverwrite {
+                            role_id: role.id,
+                            allow: overwrite.allow,
+                            deny: overwrite.deny,
+                        })
+                })
+                .collect::<Option<HashSet<_>>>();
+
+            // Roles which don't exist yet can't be part of the current overwrites
+            resolved.is_some_and(|resolved| {
+                resolved == existing.overwrites.iter().copied().collect::<HashSet<_>>()
+            })
+        });
+
+        if !unchanged {
+            changes.push(DiscordOverwriteChange {
+                target: name.to_string(),
+                category,
+                overwrites,
+            });
+        }
+    }
+
+    changes
+}
+
+// endregion
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD_ID: u64 = 1;
+
+    const CONFIG: &str = r##"
+default_permissions:
+  view_channel: true
+roles:
+  - name: Organizers
+    slug: admin
+    special: admin
+    color: "#ff0000"
+categories:
+  - name: General
+    slug: general
+  - name: Teams
+    slug: teams
+    visible_to: admin
+channels:
+  - name: Announcements
+    category: general
+"##;
+
+    fn config() -> Result<DiscordConfig, Box<dyn std::error::Error + Send + Sync>> {
+        DiscordConfig::parse(CONFIG)
+    }
+
+    fn role(id: u64, name: &str, color: u32, permissions: Permissions) -> GuildRole {
+        GuildRole {
+            id,
+            name: name.to_string(),
+            color,
+            mentionable: true,
+            hoist: !name.starts_with("team-") && id != GUILD_ID,
+            permissions: permissions.bits(),
+            managed: false,
+        }
+    }
+
+    fn channel(id: u64, name: &str, category: bool, parent_id: Option<u64>) -> GuildChannel {
+        GuildChannel {
+            id,
+            name: name.to_string(),
+            category,
+            parent_id,
+            overwrites: vec![],
+        }
+    }
+
+    #[test]
+    fn plan_empty_guild() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let member_roles = HashMap::from([("42".to_string(), vec!["team-01".to_string()])]);
+        let desired =
+            DesiredGuild::new(config()?, &[(1, "Rusty Crabs".to_string())], member_roles)?;
+
+        let guild = GuildSnapshot {
+            guild_id: GUILD_ID,
+            roles: vec![role(GUILD_ID, EVERYONE_ROLE, 0, Permissions::empty())],
+            channels: vec![channel(10, "general", false, None)],
+            members: HashMap::from([("42".to_string(), vec![])]),
+        };
+
+        let plan = plan(Uuid::nil(), &guild, &desired);
+
+        assert_eq!(
+            plan.everyone_permissions,
+            Some(Permissions::VIEW_CHANNEL.bits())
+        );
+        assert_eq!(plan.roles.len(), 3);
+        assert_eq!(plan.member_roles[0].add, vec!["team-01".to_string()]);
+        assert_eq!(plan.categories.len(), 2);
+        assert!(matches!(
+            &plan.channels[..],
+            [
+                DiscordChannelChange::Create { name, .. },
+                DiscordChannelChange::Create { category: Some(category), .. },
+                DiscordChannelChange::Delete { name: deleted, .. },
+            ] if name == "announcements" && category == "Teams" && deleted == "general"
+        ));
+        assert_eq!(plan.overwrites.len(), 4);
+        assert_eq!(plan.destructive_changes, 1);
+
+        let held_back = plan.without_deletions();
+
+        assert_eq!(held_back.destructive_changes, 0);
+        assert_ne!(held_back.deletions_digest, plan.deletions_digest);
+        assert_eq!(held_back.channels.len(), plan.channels.len() - 1);
+
+        // Deleting a different channel is not covered by the same confirmation
+        let other_guild = GuildSnapshot {
+            channels: vec![channel(11, "general", false, None)],
+            ..guild
+        };
+        let other_plan = super::plan(Uuid::nil(), &other_guild, &desired);
+
+        assert_eq!(other_plan.destructive_changes, plan.destructive_changes);
+        assert_ne!(other_plan.deletions_digest, plan.deletions_digest);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_renames_team_channels() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let desired =
+            DesiredGuild::new(config()?, &[(1, "Rusty Crabs".to_string())], HashMap::new())?;
+
+        let guild = GuildSnapshot {
+            guild_id: GUILD_ID,
+            roles: vec![
+                role(GUILD_ID, EVERYONE_ROLE, 0, Permissions::VIEW_CHANNEL),
+                role(2, "Organizers", 0xff_00_00, Permissions::all()),
+                role(3, "team-01", 0xac_ac_ac, Permissions::empty()),
+                role(4, "team-01-mentors", 0xac_ac_ac, Permissions::empty()),
+            ],
+            channels: vec![
+                channel(10, "General", true, None),
+                channel(11, "Teams", true, None),
+                channel(12, "announcements", false, Some(10)),
+                // Renamed team
+                channel(13, "01-old-name", false, Some(11)),
+            ],
+            members: HashMap::new(),
+        };
+
+        let plan = plan(Uuid::nil(), &guild, &desired);
+
+        assert!(matches!(
+            &plan.channels[..],
+            [DiscordChannelChange::Rename { to, .. }] if to == "01-rusty-crabs"
+        ));
+        assert!(plan.roles.is_empty());
+        assert!(plan.categories.is_empty());
+        assert_eq!(plan.destructive_changes, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_team_channel_templates() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let config = DiscordConfig::parse(&format!(
+            "{CONFIG}
+team_channels:
+  - name: \"{{index}}-{{name}}\"
+    category: teams
+  - name: \"{{index}}-{{name}} voice\"
+    category: teams
+    voice: true
+    visible_to: [admin, team]
+"
+        ))?;
+
+        let member_roles = HashMap::from([(
+            "42".to_string(),
+            vec!["team-01".to_string(), "team-02-mentors".to_string()],
+        )]);
+
+        let teams = [(1, "Rusty Crabs".to_string()), (2, "Gophers".to_string())];
+        let desired = DesiredGuild::new(config, &teams, member_roles)?;
+
+        let guild = GuildSnapshot {
+            guild_id: GUILD_ID,
+            roles: vec![],
+            channels: vec![
+                channel(11, "Teams", true, None),
+                channel(13, "01-old-name-voice", false, Some(11)),
+                channel(14, "01-old-name", false, Some(11)),
+            ],
+            members: HashMap::from([("42".to_string(), vec![])]),
+        };
+
+        let plan = plan(Uuid::nil(), &guild, &desired);
+
+        let renames = plan
+            .channels
+            .iter()
+            .filter_map(|change| match change {
+                DiscordChannelChange::Rename { from, to, .. } => Some((from.as_str(), to.as_str())),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        assert_eq!(
+            renames,
+            HashSet::from([
+                ("01-old-name-voice", "01-rusty-crabs-voice"),
+                ("01-old-name", "01-rusty-crabs"),
+            ])
+        );
+        assert_eq!(
+            plan.member_roles[0].add,
+            vec!["team-01".to_string(), "team-02-mentors".to_string()]
+        );
+        assert!(desired
+            .channels
+            .iter()
+            .any(|channel| channel.name == "02-gophers-voice" && channel.voice));
+
+        Ok(())
+    }
+}
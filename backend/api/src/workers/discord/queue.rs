@@ -0,0 +1,143 @@
+use serenity::http::RatelimitInfo;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Users are dropped from the queue after this many failed syncs, the full sync picks them up
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Users whose Discord roles must be synced after a role change, and events whose Discord
+/// servers must be synced after a change to their teams. Also tracks the rate limits reported by
+/// serenity so the queue worker can back off.
+#[derive(Default)]
+pub struct DiscordSyncQueue {
+    /// Failed attempts by event and user ID
+    pending: Mutex<HashMap<(Uuid, Uuid), u32>>,
+    /// Events to sync in full, e.g. after a team was renamed or deleted
+    pending_events: Mutex<HashSet<Uuid>>,
+    notify: Notify,
+    rate_limited_until: Mutex<Option<Instant>>,
+}
+
+impl DiscordSyncQueue {
+    pub fn enqueue(&self, event_id: Uuid, user_ids: impl IntoIterator<Item = Uuid>) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for user_id in user_ids {
+            pending.insert((event_id, user_id), 0);
+        }
+
+        drop(pending);
+        self.notify.notify_one();
+    }
+
+    /// Failed event syncs are not retried, the scheduled full sync picks them up.
+    pub fn enqueue_event(&self, event_id: Uuid) {
+        self.pending_events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(event_id);
+
+        self.notify.notify_one();
+    }
+
+    /// Requeues a failed sync, returns `false` if the user was dropped.
+    pub(super) fn retry(&self, event_id: Uuid, user_id: Uuid, attempts: u32) -> bool {
+        if attempts + 1 >= MAX_ATTEMPTS {
+            return false;
+        }
+
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((event_id, user_id))
+            .or_insert(attempts + 1);
+
+        self.notify.notify_one();
+        true
+    }
+
+    pub(super) async fn wait(&self) {
+        self.notify.notified().await;
+    }
+
+    pub(super) fn drain(&self) -> Vec<((Uuid, Uuid), u32)> {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+            .collect()
+    }
+
+    pub(super) fn drain_events(&self) -> Vec<Uuid> {
+        self.pending_events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+            .collect()
+    }
+
+    pub fn record_rate_limit(&self, info: &RatelimitInfo) {
+        warn!(
+            path = info.path,
+            global = info.global,
+            timeout_ms = info.timeout.as_millis(),
+            "Discord rate limit hit"
+        );
+
+        let until = Instant::now() + info.timeout;
+        let mut rate_limited_until = self
+            .rate_limited_until
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if rate_limited_until.is_none_or(|current| current < until) {
+            *rate_limited_until = Some(until);
+        }
+    }
+
+    /// Remaining time until the last reported rate limit resets.
+    pub(super) fn backoff(&self) -> Option<Duration> {
+        self.rate_limited_until
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_drops_after_max_attempts() {
+        let queue = DiscordSyncQueue::default();
+        let (event_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        queue.enqueue(event_id, [user_id, user_id]);
+        assert_eq!(queue.drain(), vec![((event_id, user_id), 0)]);
+
+        assert!(queue.retry(event_id, user_id, 0));
+        assert!(queue.retry(event_id, user_id, 1));
+        assert_eq!(queue.drain(), vec![((event_id, user_id), 1)]);
+
+        assert!(!queue.retry(event_id, user_id, 2));
+        assert!(queue.drain().is_empty());
+    }
+
+    #[test]
+    fn events_are_queued_once() {
+        let queue = DiscordSyncQueue::default();
+        let event_id = Uuid::new_v4();
+
+        queue.enqueue_event(event_id);
+        queue.enqueue_event(event_id);
+
+        assert_eq!(queue.drain_events(), vec![event_id]);
+        assert!(queue.drain_events().is_empty());
+        assert!(queue.drain().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::api_state::ApiState;
+use crate::ApiResult;
+use chrono::Utc;
+use hackathon_portal_repositories::db::TeamHealthStatus;
+use hackathon_portal_services::infrastructure::models::{
+    TeamHealthProbeResult, TeamHealthProbeTarget,
+};
+use reqwest::Client;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+use tokio_cron_scheduler::Job;
+use tracing::{error, info, info_span, Instrument};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+static HEALTH_PROBE_LOCK: std::sync::LazyLock<Mutex<()>> =
+    std::sync::LazyLock::new(|| Mutex::new(()));
+
+pub fn create_job(api_state: ApiState) -> ApiResult<Job> {
+    let job = Job::new_async("30 * * * * *", move |job_id, _| {
+        // every minute
+        Box::pin({
+            let api_state = api_state.clone();
+            let span = info_span!("health_probe_job", job_id = %job_id);
+
+            async move {
+                info!("Starting");
+
+                match run(api_state).await {
+                    Ok(()) => info!("Finished"),
+                    Err(e) => error!(error = %e, "Failed"),
+                }
+            }
+            .instrument(span)
+        })
+    })?;
+
+    Ok(job)
+}
+
+async fn run(api_state: ApiState) -> ApiResult<()> {
+    let Ok(_guard) = HEALTH_PROBE_LOCK.try_lock() else {
+        info!("Health probe job already running, skipping");
+        return Ok(());
+    };
+
+    let targets = api_state
+        .infrastructure_service
+        .get_health_probe_targets()
+        .await?;
+
+    // Team servers usually present self-signed certificates, we only care about reachability
+    let client = Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .danger_accept_invalid_certs(true)
+        .build()?;
+
+    let mut probes = JoinSet::new();
+
+    for target in targets {
+        probes.spawn(probe(client.clone(), target));
+    }
+
+    let results = probes.join_all().await;
+
+    let down = results
+        .iter()
+        .filter(|r| r.status == TeamHealthStatus::Down)
+        .count();
+
+    info!(probed = results.len(), down = down, "Probed team servers");
+
+    api_state
+        .infrastructure_service
+        .record_health_probes(results)
+        .await?;
+
+    Ok(())
+}
+
+/// A team server is up if it answers with anything but a server error.
+async fn probe(client: Client, target: TeamHealthProbeTarget) -> TeamHealthProbeResult {
+    let checked_at = Utc::now().naive_utc();
+    let start = Instant::now();
+
+    let response = client.get(&target.url).send().await;
+    let latency_ms = u32::try_from(start.elapsed().as_millis()).unwrap_or(u32::MAX);
+
+    let (status, status_code, latency_ms, error) = match response {
+        Ok(response) if response.status().is_server_error() => (
+            TeamHealthStatus::Down,
+            Some(response.status().as_u16()),
+            Some(latency_ms),
+            None,
+        ),
+        Ok(response) => (
+            TeamHealthStatus::Up,
+            Some(response.status().as_u16()),
+            Some(latency_ms),
+            None,
+        ),
+        Err(e) => (TeamHealthStatus::Down, None, None, Some(e.to_string())),
+    };
+
+    TeamHealthProbeResult {
+        target,
+        status,
+        status_code,
+        latency_ms,
+        error,
+        checked_at,
+    }
+}
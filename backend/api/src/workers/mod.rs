@@ -4,9 +4,11 @@ use tokio_cron_scheduler::JobScheduler;
 use tracing::info;
 
 mod aggregator;
-mod discord;
+pub mod discord;
+mod health;
 
 pub struct Workers {
+    api_state: ApiState,
     scheduler: JobScheduler,
 }
 
@@ -22,11 +24,24 @@ impl Workers {
             .add(discord::create_job(api_state.clone())?)
             .await?;
 
-        Ok(Self { scheduler })
+        scheduler
+            .add(discord::announcements::create_job(api_state.clone())?)
+            .await?;
+
+        scheduler
+            .add(health::create_job(api_state.clone())?)
+            .await?;
+
+        Ok(Self {
+            api_state,
+            scheduler,
+        })
     }
 
     pub async fn start(&self) -> ApiResult<()> {
         self.scheduler.start().await?;
+        tokio::spawn(discord::process_queue(self.api_state.clone()));
+        tokio::spawn(discord::commands::run(self.api_state.clone()));
         info!("Workers started");
         Ok(())
     }
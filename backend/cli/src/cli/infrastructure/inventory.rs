@@ -0,0 +1,66 @@
+use crate::cli::infrastructure::Args as InfrastructureArgs;
+use crate::cli::Args as CliArgs;
+use crate::cli_state::CliState;
+use crate::CliResult;
+use clap::{Parser, ValueEnum};
+use hackathon_portal_services::infrastructure::inventory::InventoryFormat;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Format {
+    Json,
+    AnsibleYaml,
+    AnsibleIni,
+}
+
+impl From<Format> for InventoryFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Json => Self::Json,
+            Format::AnsibleYaml => Self::AnsibleYaml,
+            Format::AnsibleIni => Self::AnsibleIni,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(long, short)]
+    pub event_id: Uuid,
+
+    #[clap(value_enum)]
+    pub format: Format,
+
+    #[clap(long, short)]
+    pub output: PathBuf,
+
+    /// Include the decrypted team passwords and AI API keys
+    #[clap(long)]
+    pub include_credentials: bool,
+}
+
+pub async fn run(
+    state: &CliState,
+    _: &CliArgs,
+    _: &InfrastructureArgs,
+    inventory_args: &Args,
+) -> CliResult<()> {
+    let inventory = state
+        .infrastructure_service()
+        .await?
+        .get_inventory(inventory_args.event_id, inventory_args.include_credentials)
+        .await?;
+
+    let contents = inventory.render(inventory_args.format.into())?;
+
+    std::fs::write(&inventory_args.output, contents)?;
+
+    println!(
+        "Wrote {} hosts to {}",
+        inventory.hosts.len(),
+        inventory_args.output.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+mod inventory;
+mod proxy_config;
+
+use crate::cli_state::CliState;
+use crate::CliResult;
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    ProxyConfig(proxy_config::Args),
+    Inventory(inventory::Args),
+}
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+pub async fn run(
+    state: &CliState,
+    cli_args: &crate::cli::Args,
+    infrastructure_args: &Args,
+) -> CliResult<()> {
+    match &infrastructure_args.command {
+        Command::ProxyConfig(proxy_config_args) => {
+            proxy_config::run(state, cli_args, infrastructure_args, proxy_config_args).await?;
+        }
+        Command::Inventory(inventory_args) => {
+            inventory::run(state, cli_args, infrastructure_args, inventory_args).await?;
+        }
+    }
+
+    Ok(())
+}
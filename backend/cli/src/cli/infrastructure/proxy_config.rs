@@ -0,0 +1,70 @@
+use crate::cli::infrastructure::Args as InfrastructureArgs;
+use crate::cli::Args as CliArgs;
+use crate::cli_state::CliState;
+use crate::{CliError, CliResult};
+use clap::{Parser, ValueEnum};
+use hackathon_portal_services::infrastructure::proxy::ProxyConfigRenderer;
+use std::path::PathBuf;
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Format {
+    Traefik,
+    Caddy,
+    Nginx,
+}
+
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[clap(value_enum)]
+    pub format: Format,
+
+    #[clap(long, short)]
+    pub output: PathBuf,
+}
+
+fn require<'a, T>(config: Option<&'a T>, field: &str) -> CliResult<&'a T> {
+    config.ok_or_else(|| CliError::ConfigMissing {
+        field: format!("infrastructure.{field}"),
+    })
+}
+
+pub async fn run(
+    state: &CliState,
+    _: &CliArgs,
+    _: &InfrastructureArgs,
+    proxy_config_args: &Args,
+) -> CliResult<()> {
+    let config = &state.config().infrastructure;
+    let routes = state
+        .infrastructure_service()
+        .await?
+        .get_proxy_routes()
+        .await?;
+
+    let contents = match proxy_config_args.format {
+        Format::Traefik => serde_json::to_string_pretty(
+            &require(config.traefik.as_ref(), "traefik")?.render(&routes),
+        )?,
+        Format::Caddy => {
+            serde_json::to_string_pretty(&require(config.caddy.as_ref(), "caddy")?.render(&routes))?
+        }
+        Format::Nginx => require(config.nginx.as_ref(), "nginx")?.render(&routes),
+    };
+
+    std::fs::write(&proxy_config_args.output, contents)?;
+
+    println!(
+        "Wrote {} routes to {}",
+        routes.routes.len(),
+        proxy_config_args.output.display()
+    );
+
+    for skipped in &routes.skipped_teams {
+        println!(
+            "Skipped team {} ({}): {:?}",
+            skipped.team_slug, skipped.team_id, skipped.reason
+        );
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+mod infrastructure;
 mod s3;
 
 use crate::cli_config::CliConfig;
@@ -9,6 +10,7 @@ use std::path::PathBuf;
 
 #[derive(Debug, Subcommand)]
 pub enum Command {
+    Infrastructure(infrastructure::Args),
     S3(s3::Args),
 }
 
@@ -33,6 +35,9 @@ pub async fn run() -> CliResult<()> {
     let state = CliState::new(config);
 
     match &args.command {
+        Command::Infrastructure(infrastructure_args) => {
+            infrastructure::run(&state, &args, infrastructure_args).await?;
+        }
         Command::S3(coop_args) => {
             s3::run(&state, &args, coop_args).await?;
         }
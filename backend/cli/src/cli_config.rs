@@ -4,7 +4,10 @@ use directories::ProjectDirs;
 use dotenvy::dotenv;
 use hackathon_portal_repositories::db::DbConfig;
 use hackathon_portal_repositories::discord::DiscordConfig;
+use hackathon_portal_repositories::lite_llm::LiteLLMConfig;
 use hackathon_portal_repositories::s3::S3Config;
+use hackathon_portal_services::crypto::CryptoConfig;
+use hackathon_portal_services::infrastructure::InfrastructureConfig;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -13,6 +16,10 @@ pub struct CliConfig {
     pub postgres: Option<DbConfig>,
     pub s3: Option<S3Config>,
     pub discord: Option<DiscordConfig>,
+    pub litellm: Option<LiteLLMConfig>,
+    pub crypto: Option<CryptoConfig>,
+    #[serde(default)]
+    pub infrastructure: InfrastructureConfig,
     #[serde(skip, default = "CliConfig::default_dirs")]
     pub dirs: ProjectDirs,
 }
@@ -33,6 +40,10 @@ impl CliConfig {
                     .separator("__")
                     .list_separator(",")
                     .with_list_parse_key("server.allowed_origins")
+                    .with_list_parse_key("infrastructure.traefik.entrypoints")
+                    .with_list_parse_key("infrastructure.traefik.auth_middlewares")
+                    .with_list_parse_key("infrastructure.traefik.default_middlewares")
+                    .with_list_parse_key("infrastructure.caddy.listen")
                     .try_parsing(true),
             )
             .build()?;
@@ -63,4 +74,18 @@ impl CliConfig {
                 field: "discord".to_string(),
             })
     }
+
+    pub fn litellm(&self) -> CliResult<&LiteLLMConfig> {
+        self.litellm
+            .as_ref()
+            .ok_or_else(|| CliError::ConfigMissing {
+                field: "litellm".to_string(),
+            })
+    }
+
+    pub fn crypto(&self) -> CliResult<&CryptoConfig> {
+        self.crypto.as_ref().ok_or_else(|| CliError::ConfigMissing {
+            field: "crypto".to_string(),
+        })
+    }
 }
@@ -1,11 +1,14 @@
 use crate::cli_config::CliConfig;
 use crate::CliResult;
+use hackathon_portal_repositories::lite_llm::LiteLLMRepository;
 use hackathon_portal_repositories::s3::S3Repository;
 use hackathon_portal_repositories::DbRepository;
 use hackathon_portal_services::appointment::AppointmentService;
 use hackathon_portal_services::authorization::AuthorizationService;
+use hackathon_portal_services::crypto::CryptoService;
 use hackathon_portal_services::event::EventService;
 use hackathon_portal_services::health::HealthService;
+use hackathon_portal_services::infrastructure::InfrastructureService;
 use hackathon_portal_services::project::ProjectService;
 use hackathon_portal_services::rating::RatingService;
 use hackathon_portal_services::sidequest::SidequestService;
@@ -31,6 +34,7 @@ pub struct CliState {
     sidequest_service: OnceCell<Arc<SidequestService>>,
     appointment_service: OnceCell<Arc<AppointmentService>>,
     upload_service: OnceCell<Arc<UploadService>>,
+    infrastructure_service: OnceCell<Arc<InfrastructureService>>,
 }
 
 impl CliState {
@@ -49,6 +53,7 @@ impl CliState {
             sidequest_service: OnceCell::new(),
             appointment_service: OnceCell::new(),
             upload_service: OnceCell::new(),
+            infrastructure_service: OnceCell::new(),
         }
     }
 
@@ -81,4 +86,65 @@ impl CliState {
             .await
             .map(Arc::clone)
     }
+
+    pub async fn authorization_service(&self) -> CliResult<Arc<AuthorizationService>> {
+        self.authorization_service
+            .get_or_try_init(async || {
+                let db_repo = self.pg_repo().await?;
+
+                Ok(Arc::new(AuthorizationService::new((*db_repo).clone())))
+            })
+            .await
+            .map(Arc::clone)
+    }
+
+    pub async fn upload_service(&self) -> CliResult<Arc<UploadService>> {
+        self.upload_service
+            .get_or_try_init(async || {
+                let db_repo = self.pg_repo().await?;
+                let s3_repo = self.s3_repo().await?;
+
+                Ok(Arc::new(UploadService::new(
+                    (*db_repo).clone(),
+                    (*s3_repo).clone(),
+                )))
+            })
+            .await
+            .map(Arc::clone)
+    }
+
+    pub async fn team_service(&self) -> CliResult<Arc<TeamService>> {
+        self.team_service
+            .get_or_try_init(async || {
+                let db_repo = self.pg_repo().await?;
+                let crypto_service = CryptoService::from_config(self.config.crypto()?)?;
+                let ai_gateway_repo =
+                    Arc::new(LiteLLMRepository::from_config(self.config.litellm()?));
+
+                Ok(Arc::new(TeamService::new(
+                    self.authorization_service().await?,
+                    self.upload_service().await?,
+                    Arc::new(crypto_service),
+                    (*db_repo).clone(),
+                    ai_gateway_repo,
+                )))
+            })
+            .await
+            .map(Arc::clone)
+    }
+
+    pub async fn infrastructure_service(&self) -> CliResult<Arc<InfrastructureService>> {
+        self.infrastructure_service
+            .get_or_try_init(async || {
+                let db_repo = self.pg_repo().await?;
+
+                Ok(Arc::new(InfrastructureService::new(
+                    self.config.infrastructure.clone(),
+                    self.team_service().await?,
+                    (*db_repo).clone(),
+                )))
+            })
+            .await
+            .map(Arc::clone)
+    }
 }
@@ -25,6 +25,12 @@ pub enum CliError {
 
     #[from]
     Dialoguer(#[serde_as(as = "DisplayFromStr")] dialoguer::Error),
+
+    #[from]
+    Io(#[serde_as(as = "DisplayFromStr")] std::io::Error),
+
+    #[from]
+    SerdeJson(#[serde_as(as = "DisplayFromStr")] serde_json::Error),
     // endregion
 }
 
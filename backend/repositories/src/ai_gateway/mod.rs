@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::RepositoryResult;
+
+#[derive(Debug, Clone)]
+pub struct AIGatewayTeamKey {
+    /// Team on the gateway owning the key, budgets and spend are tracked per team
+    pub team_id: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AIGatewayTeamUsage {
+    #[serde(default)]
+    pub spend: f64,
+    pub max_budget: Option<f64>,
+    #[serde(default)]
+    pub models: Vec<String>,
+}
+
+/// Admin API of an AI gateway issuing budgeted API keys to teams. Every operation is
+/// authorized with the event's master key.
+#[async_trait]
+pub trait AIGatewayRepository: Send + Sync {
+    /// Creates a gateway team with the given budget and models, and a key for it.
+    async fn generate_team_key(
+        &self,
+        team_index: i32,
+        budget: f64,
+        models: &[String],
+        master_key: &str,
+    ) -> RepositoryResult<AIGatewayTeamKey>;
+
+    /// Generates an additional key for an existing team, sharing the team's budget.
+    async fn generate_key(&self, team_id: &str, master_key: &str) -> RepositoryResult<String>;
+
+    /// Fields set to `None` are left unchanged.
+    async fn update_team(
+        &self,
+        team_id: &str,
+        budget: Option<f64>,
+        models: Option<&[String]>,
+        master_key: &str,
+    ) -> RepositoryResult<()>;
+
+    async fn delete_key(&self, team_key: &str, master_key: &str) -> RepositoryResult<()>;
+
+    async fn get_team_usage(
+        &self,
+        team_id: &str,
+        master_key: &str,
+    ) -> RepositoryResult<AIGatewayTeamUsage>;
+}
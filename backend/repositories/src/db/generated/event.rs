@@ -5,7 +5,7 @@ use super::sea_orm_active_enums::EventVisibility;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "event")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -43,20 +43,39 @@ pub struct Model {
     pub discord_config: Option<String>,
     #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
     pub master_ai_api_key: Option<Vec<u8>>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub discord_client_id: Option<String>,
+    #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
+    pub discord_client_secret: Option<Vec<u8>>,
+    #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
+    pub discord_bot_token: Option<Vec<u8>>,
     pub finalists_visible: bool,
     pub voting_open: bool,
+    pub ai_models: Option<Vec<String>>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub ai_default_budget: Option<f64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::appointment::Entity")]
     Appointment,
+    #[sea_orm(has_many = "super::discord_notification::Entity")]
+    DiscordNotification,
+    #[sea_orm(has_many = "super::discord_guild::Entity")]
+    DiscordGuild,
+    #[sea_orm(has_many = "super::discord_sync_status::Entity")]
+    DiscordSyncStatus,
+    #[sea_orm(has_many = "super::discord_unlinked_member::Entity")]
+    DiscordUnlinkedMember,
     #[sea_orm(has_many = "super::event_role_assignment::Entity")]
     EventRoleAssignment,
     #[sea_orm(has_many = "super::event_user_discord_id::Entity")]
     EventUserDiscordId,
     #[sea_orm(has_many = "super::project::Entity")]
     Project,
+    #[sea_orm(has_many = "super::project_matching::Entity")]
+    ProjectMatching,
     #[sea_orm(has_many = "super::sidequest::Entity")]
     Sidequest,
     #[sea_orm(has_many = "super::team::Entity")]
@@ -71,6 +90,30 @@ impl Related<super::appointment::Entity> for Entity {
     }
 }
 
+impl Related<super::discord_notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DiscordNotification.def()
+    }
+}
+
+impl Related<super::discord_guild::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DiscordGuild.def()
+    }
+}
+
+impl Related<super::discord_sync_status::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DiscordSyncStatus.def()
+    }
+}
+
+impl Related<super::discord_unlinked_member::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DiscordUnlinkedMember.def()
+    }
+}
+
 impl Related<super::event_role_assignment::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::EventRoleAssignment.def()
@@ -89,6 +132,12 @@ impl Related<super::project::Entity> for Entity {
     }
 }
 
+impl Related<super::project_matching::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectMatching.def()
+    }
+}
+
 impl Related<super::sidequest::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Sidequest.def()
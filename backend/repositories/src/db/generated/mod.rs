@@ -3,17 +3,26 @@
 pub mod prelude;
 
 pub mod appointment;
+pub mod discord_guild;
+pub mod discord_notification;
+pub mod discord_sync_status;
+pub mod discord_unlinked_member;
 pub mod event;
 pub mod event_role_assignment;
 pub mod event_user_discord_id;
 pub mod expert_rating;
 pub mod project;
+pub mod project_attachment;
+pub mod project_matching;
+pub mod project_owner;
 pub mod project_preference;
 pub mod sea_orm_active_enums;
 pub mod sidequest;
 pub mod sidequest_attempt;
 pub mod sidequest_score;
 pub mod team;
+pub mod team_credential_log;
+pub mod team_health_check;
 pub mod team_role_assignment;
 pub mod technical_question;
 pub mod technical_rating;
@@ -1,16 +1,25 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.16
 
 pub use super::appointment::Entity as Appointment;
+pub use super::discord_guild::Entity as DiscordGuild;
+pub use super::discord_notification::Entity as DiscordNotification;
+pub use super::discord_sync_status::Entity as DiscordSyncStatus;
+pub use super::discord_unlinked_member::Entity as DiscordUnlinkedMember;
 pub use super::event::Entity as Event;
 pub use super::event_role_assignment::Entity as EventRoleAssignment;
 pub use super::event_user_discord_id::Entity as EventUserDiscordId;
 pub use super::expert_rating::Entity as ExpertRating;
 pub use super::project::Entity as Project;
+pub use super::project_attachment::Entity as ProjectAttachment;
+pub use super::project_matching::Entity as ProjectMatching;
+pub use super::project_owner::Entity as ProjectOwner;
 pub use super::project_preference::Entity as ProjectPreference;
 pub use super::sidequest::Entity as Sidequest;
 pub use super::sidequest_attempt::Entity as SidequestAttempt;
 pub use super::sidequest_score::Entity as SidequestScore;
 pub use super::team::Entity as Team;
+pub use super::team_credential_log::Entity as TeamCredentialLog;
+pub use super::team_health_check::Entity as TeamHealthCheck;
 pub use super::team_role_assignment::Entity as TeamRoleAssignment;
 pub use super::technical_question::Entity as TechnicalQuestion;
 pub use super::technical_rating::Entity as TechnicalRating;
@@ -15,6 +15,10 @@ pub struct Model {
     pub slug: String,
     #[sea_orm(column_type = "Text")]
     pub content: String,
+    pub min_teams: i32,
+    pub max_teams: Option<i32>,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub requirements: Json,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -27,6 +31,10 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Event,
+    #[sea_orm(has_many = "super::project_attachment::Entity")]
+    ProjectAttachment,
+    #[sea_orm(has_many = "super::project_owner::Entity")]
+    ProjectOwner,
     #[sea_orm(has_many = "super::project_preference::Entity")]
     ProjectPreference,
     #[sea_orm(has_many = "super::team::Entity")]
@@ -39,6 +47,18 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+impl Related<super::project_attachment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectAttachment.def()
+    }
+}
+
+impl Related<super::project_owner::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectOwner.def()
+    }
+}
+
 impl Related<super::project_preference::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::ProjectPreference.def()
@@ -54,4 +74,22 @@ impl Related<super::team::Entity> for Entity {
     }
 }
 
+impl Related<super::upload::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::project_attachment::Relation::Upload.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::project_attachment::Relation::Project.def().rev())
+    }
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::project_owner::Relation::User.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::project_owner::Relation::Project.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
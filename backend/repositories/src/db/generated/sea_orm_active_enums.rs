@@ -3,6 +3,38 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Copy,
+    Hash,
+    strum :: Display,
+    strum :: VariantArray,
+    utoipa :: ToSchema,
+)]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "discord_sync_outcome"
+)]
+pub enum DiscordSyncOutcome {
+    #[sea_orm(string_value = "SUCCEEDED")]
+    Succeeded,
+    #[sea_orm(string_value = "PARTIALLY_FAILED")]
+    PartiallyFailed,
+    #[sea_orm(string_value = "FAILED")]
+    Failed,
+    #[sea_orm(string_value = "SKIPPED")]
+    Skipped,
+    #[sea_orm(string_value = "NOT_IN_GUILD")]
+    NotInGuild,
+}
 #[derive(
     Debug,
     Clone,
@@ -124,6 +156,8 @@ pub enum ExpertRatingCategory {
 )]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "media_usage")]
 pub enum MediaUsage {
+    #[sea_orm(string_value = "PROJECT_ATTACHMENT")]
+    ProjectAttachment,
     #[sea_orm(string_value = "TEAM_PHOTO")]
     TeamPhoto,
 }
@@ -142,6 +176,58 @@ pub enum MediaUsage {
     strum :: VariantArray,
     utoipa :: ToSchema,
 )]
+#[sea_orm(
+    rs_type = "String",
+    db_type = "Enum",
+    enum_name = "team_credential_action"
+)]
+pub enum TeamCredentialAction {
+    #[sea_orm(string_value = "CONFIRM_ROTATION")]
+    ConfirmRotation,
+    #[sea_orm(string_value = "GENERATE")]
+    Generate,
+    #[sea_orm(string_value = "REVEAL")]
+    Reveal,
+    #[sea_orm(string_value = "ROTATE")]
+    Rotate,
+}
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Copy,
+    Hash,
+    strum :: Display,
+    strum :: VariantArray,
+    utoipa :: ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "team_health_status")]
+pub enum TeamHealthStatus {
+    #[sea_orm(string_value = "DOWN")]
+    Down,
+    #[sea_orm(string_value = "UP")]
+    Up,
+}
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Copy,
+    Hash,
+    strum :: Display,
+    strum :: VariantArray,
+    utoipa :: ToSchema,
+)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "team_role")]
 pub enum TeamRole {
     #[sea_orm(string_value = "MENTOR")]
@@ -36,6 +36,18 @@ pub struct Model {
     #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
     pub ai_api_key: Option<Vec<u8>>,
     pub finalist: bool,
+    #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
+    pub previous_password: Option<Vec<u8>>,
+    #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
+    pub previous_ssh_private_key: Option<Vec<u8>>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub previous_ssh_public_key: Option<String>,
+    #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
+    pub ssh_private_key: Option<Vec<u8>>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub ssh_public_key: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub ai_team_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -62,6 +74,10 @@ pub enum Relation {
     ProjectPreference,
     #[sea_orm(has_many = "super::sidequest_score::Entity")]
     SidequestScore,
+    #[sea_orm(has_many = "super::team_credential_log::Entity")]
+    TeamCredentialLog,
+    #[sea_orm(has_many = "super::team_health_check::Entity")]
+    TeamHealthCheck,
     #[sea_orm(has_many = "super::team_role_assignment::Entity")]
     TeamRoleAssignment,
     #[sea_orm(has_many = "super::technical_rating::Entity")]
@@ -102,6 +118,18 @@ impl Related<super::sidequest_score::Entity> for Entity {
     }
 }
 
+impl Related<super::team_credential_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamCredentialLog.def()
+    }
+}
+
+impl Related<super::team_health_check::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamHealthCheck.def()
+    }
+}
+
 impl Related<super::team_role_assignment::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::TeamRoleAssignment.def()
@@ -21,6 +21,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::project_attachment::Entity")]
+    ProjectAttachment,
     #[sea_orm(has_many = "super::team::Entity")]
     Team,
     #[sea_orm(
@@ -33,6 +35,12 @@ pub enum Relation {
     User,
 }
 
+impl Related<super::project_attachment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectAttachment.def()
+    }
+}
+
 impl Related<super::team::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Team.def()
@@ -45,4 +53,13 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::project_attachment::Relation::Project.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::project_attachment::Relation::Upload.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -23,8 +23,12 @@ pub enum Relation {
     EventUserDiscordId,
     #[sea_orm(has_many = "super::expert_rating::Entity")]
     ExpertRating,
+    #[sea_orm(has_many = "super::project_owner::Entity")]
+    ProjectOwner,
     #[sea_orm(has_many = "super::sidequest_attempt::Entity")]
     SidequestAttempt,
+    #[sea_orm(has_many = "super::team_credential_log::Entity")]
+    TeamCredentialLog,
     #[sea_orm(has_many = "super::team_role_assignment::Entity")]
     TeamRoleAssignment,
     #[sea_orm(has_many = "super::upload::Entity")]
@@ -51,12 +55,24 @@ impl Related<super::expert_rating::Entity> for Entity {
     }
 }
 
+impl Related<super::project_owner::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProjectOwner.def()
+    }
+}
+
 impl Related<super::sidequest_attempt::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SidequestAttempt.def()
     }
 }
 
+impl Related<super::team_credential_log::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TeamCredentialLog.def()
+    }
+}
+
 impl Related<super::team_role_assignment::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::TeamRoleAssignment.def()
@@ -84,4 +100,13 @@ impl Related<super::event::Entity> for Entity {
     }
 }
 
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::project_owner::Relation::Project.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::project_owner::Relation::User.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -14,16 +14,25 @@ pub use extensions::*;
 pub use repositories::*;
 
 pub use generated::appointment as db_appointment;
+pub use generated::discord_guild as db_discord_guild;
+pub use generated::discord_notification as db_discord_notification;
+pub use generated::discord_sync_status as db_discord_sync_status;
+pub use generated::discord_unlinked_member as db_discord_unlinked_member;
 pub use generated::event as db_event;
 pub use generated::event_role_assignment as db_event_role_assignment;
 pub use generated::event_user_discord_id as db_event_user_discord_id;
 pub use generated::expert_rating as db_expert_rating;
 pub use generated::project as db_project;
+pub use generated::project_attachment as db_project_attachment;
+pub use generated::project_matching as db_project_matching;
+pub use generated::project_owner as db_project_owner;
 pub use generated::project_preference as db_project_preference;
 pub use generated::sidequest as db_sidequest;
 pub use generated::sidequest_attempt as db_sidequest_attempt;
 pub use generated::sidequest_score as db_sidequest_score;
 pub use generated::team as db_team;
+pub use generated::team_credential_log as db_team_credential_log;
+pub use generated::team_health_check as db_team_health_check;
 pub use generated::team_role_assignment as db_team_role_assignment;
 pub use generated::technical_question as db_technical_question;
 pub use generated::technical_rating as db_technical_rating;
@@ -32,7 +41,8 @@ pub use generated::user as db_user;
 pub use generated::vote as db_vote;
 
 pub use generated::sea_orm_active_enums::{
-    EventPhase, EventRole, EventVisibility, ExpertRatingCategory, MediaUsage, TeamRole,
+    DiscordSyncOutcome, EventPhase, EventRole, EventVisibility, ExpertRatingCategory, MediaUsage,
+    TeamCredentialAction, TeamHealthStatus, TeamRole,
 };
 // endregion
 
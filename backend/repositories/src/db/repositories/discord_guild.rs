@@ -0,0 +1,30 @@
+use crate::db::generated::discord_guild;
+use crate::db::OrFailExt;
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+
+pub struct DiscordGuildRepository;
+
+impl DiscordGuildRepository {
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<discord_guild::Model>> {
+        discord_guild::Entity::find()
+            .filter(discord_guild::Column::EventId.eq(event_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    pub async fn fetch_by_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+        guild_id: &str,
+    ) -> RepositoryResult<discord_guild::Model> {
+        discord_guild::Entity::find_by_id((event_id, guild_id.to_string()))
+            .one(db)
+            .await?
+            .or_fail(discord_guild::Entity.table_name(), guild_id)
+    }
+}
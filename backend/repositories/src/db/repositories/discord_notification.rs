@@ -0,0 +1,18 @@
+use crate::db::generated::discord_notification;
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+
+pub struct DiscordNotificationRepository;
+
+impl DiscordNotificationRepository {
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<discord_notification::Model>> {
+        discord_notification::Entity::find()
+            .filter(discord_notification::Column::EventId.eq(event_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::db::generated::discord_sync_status;
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+
+pub struct DiscordSyncStatusRepository;
+
+impl DiscordSyncStatusRepository {
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<discord_sync_status::Model>> {
+        discord_sync_status::Entity::find()
+            .filter(discord_sync_status::Column::EventId.eq(event_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::db::generated::discord_unlinked_member;
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+
+pub struct DiscordUnlinkedMemberRepository;
+
+impl DiscordUnlinkedMemberRepository {
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<discord_unlinked_member::Model>> {
+        discord_unlinked_member::Entity::find()
+            .filter(discord_unlinked_member::Column::EventId.eq(event_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
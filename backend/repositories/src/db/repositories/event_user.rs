@@ -21,4 +21,26 @@ impl EventUserRepository {
             .await
             .map_err(RepositoryError::from)
     }
+
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<event_user_discord_id::Model>> {
+        event_user_discord_id::Entity::find()
+            .filter(event_user_discord_id::Column::EventId.eq(event_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    pub async fn fetch_all_by_discord_id<C: ConnectionTrait>(
+        db: &C,
+        discord_id: &str,
+    ) -> RepositoryResult<Vec<event_user_discord_id::Model>> {
+        event_user_discord_id::Entity::find()
+            .filter(event_user_discord_id::Column::DiscordId.eq(discord_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
 }
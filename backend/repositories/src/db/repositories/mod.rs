@@ -1,13 +1,22 @@
 mod appointment;
+mod discord_guild;
+mod discord_notification;
+mod discord_sync_status;
+mod discord_unlinked_member;
 mod event;
 mod event_role_assignment;
 mod event_user;
 mod expert_rating;
 mod project;
+mod project_attachment;
+mod project_matching;
+mod project_owner;
 mod project_preference;
 mod sidequest;
 mod sidequest_attempt;
 mod team;
+mod team_credential_log;
+mod team_health_check;
 mod team_role_assignment;
 mod technical_question;
 mod upload;
@@ -15,15 +24,24 @@ mod user;
 mod vote;
 
 pub use appointment::AppointmentRepository;
+pub use discord_guild::DiscordGuildRepository;
+pub use discord_notification::DiscordNotificationRepository;
+pub use discord_sync_status::DiscordSyncStatusRepository;
+pub use discord_unlinked_member::DiscordUnlinkedMemberRepository;
 pub use event::EventRepository;
 pub use event_role_assignment::EventRoleAssignmentRepository;
 pub use event_user::EventUserRepository;
 pub use expert_rating::ExpertRatingRepository;
 pub use project::ProjectRepository;
+pub use project_attachment::ProjectAttachmentRepository;
+pub use project_matching::ProjectMatchingRepository;
+pub use project_owner::ProjectOwnerRepository;
 pub use project_preference::ProjectPreferenceRepository;
 pub use sidequest::SidequestRepository;
 pub use sidequest_attempt::SidequestAttemptRepository;
 pub use team::TeamRepository;
+pub use team_credential_log::TeamCredentialLogRepository;
+pub use team_health_check::TeamHealthCheckRepository;
 pub use team_role_assignment::TeamRoleAssignmentRepository;
 pub use technical_question::TechnicalQuestionRepository;
 pub use upload::UploadRepository;
@@ -7,6 +7,14 @@ use sea_orm::{Condition, QueryOrder};
 pub struct ProjectRepository;
 
 impl ProjectRepository {
+    pub async fn fetch_all<C: ConnectionTrait>(db: &C) -> RepositoryResult<Vec<project::Model>> {
+        project::Entity::find()
+            .order_by_asc(project::Column::Name)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
     pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
         db: &C,
         event_id: Uuid,
@@ -0,0 +1,33 @@
+use crate::db::generated::{project, project_attachment};
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+use sea_orm::QueryOrder;
+
+pub struct ProjectAttachmentRepository;
+
+impl ProjectAttachmentRepository {
+    pub async fn fetch_all_by_project_id<C: ConnectionTrait>(
+        db: &C,
+        project_id: Uuid,
+    ) -> RepositoryResult<Vec<project_attachment::Model>> {
+        project_attachment::Entity::find()
+            .filter(project_attachment::Column::ProjectId.eq(project_id))
+            .order_by_asc(project_attachment::Column::Name)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<project_attachment::Model>> {
+        project_attachment::Entity::find()
+            .inner_join(project::Entity)
+            .filter(project::Column::EventId.eq(event_id))
+            .order_by_asc(project_attachment::Column::Name)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::db::generated::project_matching;
+use crate::db::OrFailExt;
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+use sea_orm::QueryOrder;
+
+pub struct ProjectMatchingRepository;
+
+impl ProjectMatchingRepository {
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<project_matching::Model>> {
+        project_matching::Entity::find()
+            .filter(project_matching::Column::EventId.eq(event_id))
+            .order_by_desc(project_matching::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    pub async fn fetch_by_id<C: ConnectionTrait>(
+        db: &C,
+        id: Uuid,
+    ) -> RepositoryResult<project_matching::Model> {
+        project_matching::Entity::find_by_id(id)
+            .one(db)
+            .await?
+            .or_fail(project_matching::Entity.table_name(), id)
+    }
+}
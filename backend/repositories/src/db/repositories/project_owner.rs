@@ -0,0 +1,30 @@
+use crate::db::generated::{project, project_owner};
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+
+pub struct ProjectOwnerRepository;
+
+impl ProjectOwnerRepository {
+    pub async fn fetch_all_by_project_id<C: ConnectionTrait>(
+        db: &C,
+        project_id: Uuid,
+    ) -> RepositoryResult<Vec<project_owner::Model>> {
+        project_owner::Entity::find()
+            .filter(project_owner::Column::ProjectId.eq(project_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<project_owner::Model>> {
+        project_owner::Entity::find()
+            .inner_join(project::Entity)
+            .filter(project::Column::EventId.eq(event_id))
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
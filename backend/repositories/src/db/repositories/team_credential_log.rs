@@ -0,0 +1,20 @@
+use crate::db::generated::team_credential_log;
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+use sea_orm::QueryOrder;
+
+pub struct TeamCredentialLogRepository;
+
+impl TeamCredentialLogRepository {
+    pub async fn fetch_all_by_team_id<C: ConnectionTrait>(
+        db: &C,
+        team_id: Uuid,
+    ) -> RepositoryResult<Vec<team_credential_log::Model>> {
+        team_credential_log::Entity::find()
+            .filter(team_credential_log::Column::TeamId.eq(team_id))
+            .order_by_desc(team_credential_log::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::db::generated::{team, team_health_check};
+use crate::{RepositoryError, RepositoryResult};
+use sea_orm::prelude::*;
+use sea_orm::{JoinType, QueryOrder, QuerySelect, RelationTrait};
+
+pub struct TeamHealthCheckRepository;
+
+impl TeamHealthCheckRepository {
+    pub async fn fetch_all_by_team_id<C: ConnectionTrait>(
+        db: &C,
+        team_id: Uuid,
+    ) -> RepositoryResult<Vec<team_health_check::Model>> {
+        team_health_check::Entity::find()
+            .filter(team_health_check::Column::TeamId.eq(team_id))
+            .order_by_asc(team_health_check::Column::Port)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+
+    pub async fn fetch_all_by_event_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+    ) -> RepositoryResult<Vec<team_health_check::Model>> {
+        team_health_check::Entity::find()
+            .join(JoinType::InnerJoin, team_health_check::Relation::Team.def())
+            .filter(team::Column::EventId.eq(event_id))
+            .order_by_asc(team::Column::Index)
+            .order_by_asc(team_health_check::Column::Port)
+            .all(db)
+            .await
+            .map_err(RepositoryError::from)
+    }
+}
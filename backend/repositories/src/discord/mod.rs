@@ -1,4 +1,14 @@
+use crate::{RepositoryError, RepositoryResult};
+use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Discord JSON error code of a user that joined the maximum number of servers
+const MAX_GUILDS_REACHED: u64 = 30001;
+
+/// Discord rejects longer nicknames
+const MAX_NICK_LENGTH: usize = 32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -6,4 +16,378 @@ pub struct DiscordConfig {
     pub client_id: String,
     pub client_secret: String,
     pub bot_token: String,
+    /// Including the scheme, can point at a local stand-in for Discord
+    #[serde(default = "DiscordConfig::default_api_base_url")]
+    pub api_base_url: String,
+    /// Syncs deleting more roles, categories and channels are skipped until confirmed by an admin
+    #[serde(default = "DiscordConfig::default_max_destructive_changes")]
+    pub max_destructive_changes: usize,
+}
+
+impl DiscordConfig {
+    fn default_api_base_url() -> String {
+        "https://discord.com/api".to_string()
+    }
+
+    const fn default_max_destructive_changes() -> usize {
+        5
+    }
+
+    #[must_use]
+    pub fn credentials(&self) -> DiscordCredentials {
+        DiscordCredentials {
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+            bot_token: self.bot_token.clone(),
+        }
+    }
+}
+
+/// Application and bot acting on behalf of the portal, events can bring their own
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DiscordCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub bot_token: String,
+}
+
+impl fmt::Debug for DiscordCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiscordCredentials")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("bot_token", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscordUser {
+    pub id: String,
+    #[serde(default)]
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscordGuildJoin {
+    Added,
+    /// Discord ignores the nickname of members that already joined
+    AlreadyMember,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscordRepository {
+    pub base_url: String,
+    pub client: Client,
+    credentials: DiscordCredentials,
+}
+
+impl DiscordRepository {
+    #[must_use]
+    pub fn new(config: &DiscordConfig) -> Self {
+        Self {
+            base_url: config.api_base_url.trim_end_matches('/').to_string(),
+            client: Client::new(),
+            credentials: config.credentials(),
+        }
+    }
+
+    /// Same API and HTTP client, acting as another application and bot.
+    #[must_use]
+    pub fn with_credentials(&self, credentials: DiscordCredentials) -> Self {
+        Self {
+            base_url: self.base_url.clone(),
+            client: self.client.clone(),
+            credentials,
+        }
+    }
+
+    #[must_use]
+    pub const fn credentials(&self) -> &DiscordCredentials {
+        &self.credentials
+    }
+
+    /// Exchanges an OAuth authorization code for an access token of the user.
+    pub async fn exchange_code(&self, code: &str, redirect_uri: &str) -> RepositoryResult<String> {
+        let params = [
+            ("client_id", self.credentials.client_id.as_str()),
+            ("client_secret", self.credentials.client_secret.as_str()),
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+        ];
+
+        let res = self
+            .client
+            .post(format!("{}/oauth2/token", self.base_url))
+            .form(&params)
+            .send()
+            .await?;
+
+        let token = Self::check(res).await?.json::<TokenResponse>().await?;
+
+        Ok(token.access_token)
+    }
+
+    pub async fn get_current_user(&self, access_token: &str) -> RepositoryResult<DiscordUser> {
+        let res = self
+            .client
+            .get(format!("{}/users/@me", self.base_url))
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        Ok(Self::check(res).await?.json::<DiscordUser>().await?)
+    }
+
+    /// Adds the user to the guild with the bot, requires an access token with the `guilds.join`
+    /// scope.
+    pub async fn add_guild_member(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+        access_token: &str,
+        nick: &str,
+    ) -> RepositoryResult<DiscordGuildJoin> {
+        let res = self
+            .client
+            .put(format!(
+                "{}/guilds/{guild_id}/members/{user_id}",
+                self.base_url
+            ))
+            .header(
+                "Authorization",
+                format!("Bot {}", self.credentials.bot_token),
+            )
+            .json(&json!({
+                "access_token": access_token,
+                "nick": Self::truncate_nick(nick),
+            }))
+            .send()
+            .await?;
+
+        match Self::check(res).await?.status() {
+            StatusCode::NO_CONTENT => Ok(DiscordGuildJoin::AlreadyMember),
+            _ => Ok(DiscordGuildJoin::Added),
+        }
+    }
+
+    pub async fn update_member_nick(
+        &self,
+        guild_id: &str,
+        user_id: &str,
+        nick: &str,
+    ) -> RepositoryResult<()> {
+        let res = self
+            .client
+            .patch(format!(
+                "{}/guilds/{guild_id}/members/{user_id}",
+                self.base_url
+            ))
+            .header(
+                "Authorization",
+                format!("Bot {}", self.credentials.bot_token),
+            )
+            .json(&json!({ "nick": Self::truncate_nick(nick) }))
+            .send()
+            .await?;
+
+        Self::check(res).await?;
+
+        Ok(())
+    }
+
+    fn truncate_nick(nick: &str) -> String {
+        nick.chars().take(MAX_NICK_LENGTH).collect()
+    }
+
+    async fn check(res: Response) -> RepositoryResult<Response> {
+        let status = res.status();
+
+        if status.is_success() {
+            return Ok(res);
+        }
+
+        let body = res.text().await?;
+
+        Err(Self::parse_error(status, &body))
+    }
+
+    /// OAuth errors are reported as `{"error": .., "error_description": ..}`, API errors as
+    /// `{"code": .., "message": ..}`.
+    fn parse_error(status: StatusCode, body: &str) -> RepositoryError {
+        let json = serde_json::from_str::<Value>(body).unwrap_or(Value::Null);
+
+        let message = match (&json["error_description"], &json["error"], &json["message"]) {
+            (Value::String(message), _, _)
+            | (_, Value::String(message), _)
+            | (_, _, Value::String(message)) => message.clone(),
+            _ if body.is_empty() => status.to_string(),
+            _ => body.to_string(),
+        };
+
+        match (json["error"].as_str(), json["code"].as_u64(), status) {
+            (Some("invalid_grant" | "invalid_request"), _, _) => {
+                RepositoryError::DiscordInvalidCode { message }
+            }
+            (_, Some(MAX_GUILDS_REACHED), _) => {
+                RepositoryError::DiscordGuildLimitReached { message }
+            }
+            (_, _, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+                RepositoryError::DiscordUnauthorized { message }
+            }
+            (_, _, status) if status.is_client_error() => RepositoryError::DiscordRejected {
+                status: status.as_u16(),
+                message,
+            },
+            (_, _, status) => RepositoryError::DiscordUnavailable {
+                status: status.as_u16(),
+                message,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{mock_server, recorded, Requests};
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    /// Stand-in for the Discord API, user `1` joins the guild and user `2` is already a member
+    async fn mock_discord() -> std::io::Result<(DiscordRepository, Requests)> {
+        let (base_url, requests) = mock_server(respond).await?;
+
+        let repo = DiscordRepository::new(&DiscordConfig {
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            bot_token: "bot".to_string(),
+            api_base_url: format!("{base_url}/"),
+            max_destructive_changes: 5,
+        });
+
+        Ok((repo, requests))
+    }
+
+    fn respond(request_line: &str) -> (&'static str, Value) {
+        let mut parts = request_line.split(' ');
+
+        match (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        ) {
+            ("POST", "/oauth2/token") => ("200 OK", json!({ "access_token": "user-token" })),
+            ("GET", "/users/@me") => ("200 OK", json!({ "id": "1", "username": "hacker" })),
+            ("PUT", "/guilds/10/members/1") => ("201 Created", json!({ "nick": "Hacker" })),
+            ("PUT", "/guilds/10/members/2") | ("PATCH", _) => ("204 No Content", Value::Null),
+            ("PUT", "/guilds/11/members/1") => (
+                "400 Bad Request",
+                json!({ "code": 30001, "message": "Maximum number of guilds reached (100)" }),
+            ),
+            _ => (
+                "404 Not Found",
+                json!({ "code": 0, "message": "404: Not Found" }),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn exchange_code_and_get_user() -> TestResult {
+        let (repo, requests) = mock_discord().await?;
+
+        let access_token = repo.exchange_code("code", "https://portal/discord").await?;
+        let user = repo.get_current_user(&access_token).await?;
+
+        assert_eq!(access_token, "user-token");
+        assert_eq!(user.id, "1");
+
+        let requests = recorded(&requests);
+        let form = requests[0].1.as_str().unwrap_or_default();
+        assert!(form.contains("grant_type=authorization_code"));
+        assert!(form.contains("code=code"));
+        assert!(form.contains("redirect_uri=https%3A%2F%2Fportal%2Fdiscord"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_guild_member() -> TestResult {
+        let (repo, requests) = mock_discord().await?;
+
+        let nick = "A very long name that exceeds the limit";
+
+        assert_eq!(
+            repo.add_guild_member("10", "1", "user-token", nick).await?,
+            DiscordGuildJoin::Added
+        );
+        assert_eq!(
+            repo.add_guild_member("10", "2", "user-token", nick).await?,
+            DiscordGuildJoin::AlreadyMember
+        );
+
+        repo.update_member_nick("10", "2", nick).await?;
+
+        let requests = recorded(&requests);
+        assert_eq!(
+            requests[0].1,
+            json!({ "access_token": "user-token", "nick": "A very long name that exceeds th" })
+        );
+        assert!(requests[2].0.starts_with("PATCH /guilds/10/members/2 "));
+
+        let result = repo.add_guild_member("11", "1", "user-token", nick).await;
+        assert!(matches!(
+            result,
+            Err(RepositoryError::DiscordGuildLimitReached { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let invalid_code = DiscordRepository::parse_error(
+            StatusCode::BAD_REQUEST,
+            r#"{"error": "invalid_grant", "error_description": "Invalid \"code\" in request."}"#,
+        );
+        assert!(matches!(
+            invalid_code,
+            RepositoryError::DiscordInvalidCode { message } if message == "Invalid \"code\" in request."
+        ));
+
+        let unauthorized = DiscordRepository::parse_error(
+            StatusCode::FORBIDDEN,
+            r#"{"code": 50013, "message": "Missing Permissions"}"#,
+        );
+        assert!(matches!(
+            unauthorized,
+            RepositoryError::DiscordUnauthorized { message } if message == "Missing Permissions"
+        ));
+
+        let unavailable = DiscordRepository::parse_error(StatusCode::BAD_GATEWAY, "");
+        assert!(matches!(
+            unavailable,
+            RepositoryError::DiscordUnavailable { status: 502, .. }
+        ));
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let credentials = DiscordCredentials {
+            client_id: "client-id".to_string(),
+            client_secret: "client-secret".to_string(),
+            bot_token: "bot-token".to_string(),
+        };
+
+        let debug = format!("{credentials:?}");
+
+        assert!(debug.contains("client-id"));
+        assert!(!debug.contains("client-secret"));
+        assert!(!debug.contains("bot-token"));
+    }
 }
@@ -21,6 +21,47 @@ pub enum RepositoryError {
         message: String,
     },
 
+    AIGatewayUnauthorized {
+        message: String,
+    },
+
+    AIGatewayNotFound {
+        message: String,
+    },
+
+    AIGatewayRejected {
+        status: u16,
+        message: String,
+    },
+
+    AIGatewayUnavailable {
+        status: u16,
+        message: String,
+    },
+
+    DiscordInvalidCode {
+        message: String,
+    },
+
+    /// The user joined the maximum number of Discord servers
+    DiscordGuildLimitReached {
+        message: String,
+    },
+
+    DiscordUnauthorized {
+        message: String,
+    },
+
+    DiscordRejected {
+        status: u16,
+        message: String,
+    },
+
+    DiscordUnavailable {
+        status: u16,
+        message: String,
+    },
+
     // region: external library errors
     #[from]
     SeaORM(#[serde_as(as = "DisplayFromStr")] sea_orm::DbErr),
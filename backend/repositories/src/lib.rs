@@ -1,7 +1,10 @@
+pub mod ai_gateway;
 pub mod db;
 pub mod discord;
 pub mod error;
 pub mod lite_llm;
+#[cfg(test)]
+mod mock_server;
 pub mod s3;
 
 pub use db::DbRepository;
@@ -1,7 +1,9 @@
-use reqwest::Client;
+use async_trait::async_trait;
+use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
+use crate::ai_gateway::{AIGatewayRepository, AIGatewayTeamKey, AIGatewayTeamUsage};
 use crate::{RepositoryError, RepositoryResult};
 
 #[derive(Debug, Deserialize)]
@@ -14,14 +16,35 @@ struct NewKeyRequest {
     key: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct TeamInfoRequest {
+    team_info: AIGatewayTeamUsage,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiteLLMConfig {
-    pub host: String,
+    /// Including the scheme, e.g. `http://localhost:4000`. A bare host (formerly `host`) is
+    /// still accepted and defaults to HTTPS.
+    #[serde(alias = "host")]
+    pub base_url: String,
+}
+
+impl LiteLLMConfig {
+    #[must_use]
+    pub fn normalized_base_url(&self) -> String {
+        let base_url = self.base_url.trim_end_matches('/');
+
+        if base_url.contains("://") {
+            base_url.to_string()
+        } else {
+            format!("https://{base_url}")
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct LiteLLMRepository {
-    pub host: String,
+    pub base_url: String,
     pub client: Client,
 }
 
@@ -29,7 +52,7 @@ impl LiteLLMRepository {
     #[must_use]
     pub fn new(config: &LiteLLMConfig) -> Self {
         Self {
-            host: config.host.clone(),
+            base_url: config.normalized_base_url(),
             client: Client::new(),
         }
     }
@@ -39,37 +62,95 @@ impl LiteLLMRepository {
         Self::new(config)
     }
 
-    async fn request(
-        &self,
-        endpoint: &str,
-        payload: serde_json::Value,
-        key: &str,
-    ) -> RepositoryResult<serde_json::Value> {
+    async fn request(&self, endpoint: &str, payload: Value, key: &str) -> RepositoryResult<Value> {
         let res = self
             .client
-            .post(format!("https://{}{}", self.host, endpoint))
+            .post(format!("{}{}", self.base_url, endpoint))
             .header("Authorization", format!("Bearer {}", key))
             .header("Content-Type", "application/json")
             .json(&payload)
             .send()
             .await?;
-        let json = res.json::<serde_json::Value>().await?;
-        Ok(json)
+
+        Self::parse_response(res).await
     }
 
-    pub async fn generate_team_key(
+    async fn get(
+        &self,
+        endpoint: &str,
+        query: &[(&str, &str)],
+        key: &str,
+    ) -> RepositoryResult<Value> {
+        let res = self
+            .client
+            .get(format!("{}{}", self.base_url, endpoint))
+            .header("Authorization", format!("Bearer {key}"))
+            .query(query)
+            .send()
+            .await?;
+
+        Self::parse_response(res).await
+    }
+
+    async fn parse_response(res: Response) -> RepositoryResult<Value> {
+        let status = res.status();
+
+        if status.is_success() {
+            return Ok(res.json::<Value>().await?);
+        }
+
+        let body = res.text().await?;
+
+        Err(Self::parse_error(status, &body))
+    }
+
+    /// `LiteLLM` reports errors either as `{"error": {"message": ..}}` or, for validation
+    /// errors, as `{"detail": ..}`.
+    fn parse_error(status: StatusCode, body: &str) -> RepositoryError {
+        let json = serde_json::from_str::<Value>(body).unwrap_or(Value::Null);
+
+        let message = match (&json["error"]["message"], &json["detail"]) {
+            (Value::String(message), _) | (_, Value::String(message)) => message.clone(),
+            (_, Value::Null) if body.is_empty() => status.to_string(),
+            (_, Value::Null) => body.to_string(),
+            (_, detail) => detail.to_string(),
+        };
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                RepositoryError::AIGatewayUnauthorized { message }
+            }
+            StatusCode::NOT_FOUND => RepositoryError::AIGatewayNotFound { message },
+            status if status.is_client_error() => RepositoryError::AIGatewayRejected {
+                status: status.as_u16(),
+                message,
+            },
+            status => RepositoryError::AIGatewayUnavailable {
+                status: status.as_u16(),
+                message,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl AIGatewayRepository for LiteLLMRepository {
+    async fn generate_team_key(
         &self,
         team_index: i32,
         budget: f64,
-        key: &str,
-    ) -> RepositoryResult<String> {
+        models: &[String],
+        master_key: &str,
+    ) -> RepositoryResult<AIGatewayTeamKey> {
         let create_team_body = json!({
             "team_alias": format!("team-{:02}", team_index),
             "max_budget": budget,
-            "models": ["openai/*"],
+            "models": models,
         });
 
-        let res = self.request("/team/new", create_team_body, key).await?;
+        let res = self
+            .request("/team/new", create_team_body, master_key)
+            .await?;
 
         let team = serde_json::from_value::<NewTeamRequest>(res).map_err(|_| {
             RepositoryError::Parsing {
@@ -77,11 +158,22 @@ impl LiteLLMRepository {
             }
         })?;
 
+        let team_key = self.generate_key(&team.team_id, master_key).await?;
+
+        Ok(AIGatewayTeamKey {
+            team_id: team.team_id,
+            key: team_key,
+        })
+    }
+
+    async fn generate_key(&self, team_id: &str, master_key: &str) -> RepositoryResult<String> {
         let create_key_body = json!({
-            "team_id": team.team_id,
+            "team_id": team_id,
         });
 
-        let res = self.request("/key/generate", create_key_body, key).await?;
+        let res = self
+            .request("/key/generate", create_key_body, master_key)
+            .await?;
 
         let key =
             serde_json::from_value::<NewKeyRequest>(res).map_err(|_| RepositoryError::Parsing {
@@ -90,4 +182,223 @@ impl LiteLLMRepository {
 
         Ok(key.key)
     }
+
+    async fn update_team(
+        &self,
+        team_id: &str,
+        budget: Option<f64>,
+        models: Option<&[String]>,
+        master_key: &str,
+    ) -> RepositoryResult<()> {
+        let mut update_team_body = json!({
+            "team_id": team_id,
+        });
+
+        if let Some(budget) = budget {
+            update_team_body["max_budget"] = json!(budget);
+        }
+
+        if let Some(models) = models {
+            update_team_body["models"] = json!(models);
+        }
+
+        self.request("/team/update", update_team_body, master_key)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_key(&self, team_key: &str, master_key: &str) -> RepositoryResult<()> {
+        let delete_key_body = json!({
+            "keys": [team_key],
+        });
+
+        self.request("/key/delete", delete_key_body, master_key)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_team_usage(
+        &self,
+        team_id: &str,
+        master_key: &str,
+    ) -> RepositoryResult<AIGatewayTeamUsage> {
+        let res = self
+            .get("/team/info", &[("team_id", team_id)], master_key)
+            .await?;
+
+        let info = serde_json::from_value::<TeamInfoRequest>(res).map_err(|_| {
+            RepositoryError::Parsing {
+                message: "Failed to parse LiteLLM response (team info)".to_string(),
+            }
+        })?;
+
+        Ok(info.team_info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{mock_server, recorded, Requests};
+
+    type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+    /// Stand-in for a `LiteLLM` proxy
+    async fn mock_lite_llm() -> std::io::Result<(LiteLLMRepository, Requests)> {
+        let (base_url, requests) = mock_server(respond).await?;
+
+        let repo = LiteLLMRepository {
+            base_url,
+            client: Client::new(),
+        };
+
+        Ok((repo, requests))
+    }
+
+    fn respond(request_line: &str) -> (&'static str, Value) {
+        match request_line.split(' ').nth(1).unwrap_or_default() {
+            "/team/new" => ("200 OK", json!({ "team_id": "litellm-team" })),
+            "/key/generate" => ("200 OK", json!({ "key": "sk-team" })),
+            "/team/info?team_id=litellm-team" => (
+                "200 OK",
+                json!({
+                    "team_id": "litellm-team",
+                    "team_info": { "spend": 1.5, "max_budget": 10.0, "models": ["openai/*"] },
+                }),
+            ),
+            "/team/info?team_id=unknown" => (
+                "404 Not Found",
+                json!({
+                    "error": {
+                        "message": "Team not found, passed team_id=unknown",
+                        "type": "not_found",
+                        "param": "team_id",
+                        "code": "404",
+                    },
+                }),
+            ),
+            _ => ("200 OK", json!({})),
+        }
+    }
+
+    #[tokio::test]
+    async fn generate_team_key() -> TestResult {
+        let (repo, requests) = mock_lite_llm().await?;
+
+        let team_key = repo
+            .generate_team_key(7, 25.0, &["openai/gpt-4o".to_string()], "sk-master")
+            .await?;
+
+        assert_eq!(team_key.team_id, "litellm-team");
+        assert_eq!(team_key.key, "sk-team");
+
+        let requests = recorded(&requests);
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].0.starts_with("POST /team/new "));
+        assert_eq!(requests[0].1["team_alias"], "team-07");
+        assert_eq!(requests[0].1["max_budget"], 25.0);
+        assert_eq!(requests[0].1["models"], json!(["openai/gpt-4o"]));
+        assert_eq!(requests[1].1["team_id"], "litellm-team");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_team_only_sends_given_fields() -> TestResult {
+        let (repo, requests) = mock_lite_llm().await?;
+
+        repo.update_team("litellm-team", Some(50.0), None, "sk-master")
+            .await?;
+
+        let requests = recorded(&requests);
+        assert!(requests[0].0.starts_with("POST /team/update "));
+        assert_eq!(
+            requests[0].1,
+            json!({ "team_id": "litellm-team", "max_budget": 50.0 })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_key_and_get_team_usage() -> TestResult {
+        let (repo, requests) = mock_lite_llm().await?;
+
+        repo.delete_key("sk-team", "sk-master").await?;
+        let usage = repo.get_team_usage("litellm-team", "sk-master").await?;
+
+        assert!((usage.spend - 1.5).abs() < f64::EPSILON);
+        assert_eq!(usage.max_budget, Some(10.0));
+        assert_eq!(usage.models, vec!["openai/*".to_string()]);
+
+        let requests = recorded(&requests);
+        assert_eq!(requests[0].1, json!({ "keys": ["sk-team"] }));
+        assert!(requests[1]
+            .0
+            .starts_with("GET /team/info?team_id=litellm-team "));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_team_usage_of_unknown_team() -> TestResult {
+        let (repo, _) = mock_lite_llm().await?;
+
+        let result = repo.get_team_usage("unknown", "sk-master").await;
+
+        assert!(matches!(
+            result,
+            Err(RepositoryError::AIGatewayNotFound { message })
+                if message == "Team not found, passed team_id=unknown"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let unauthorized = LiteLLMRepository::parse_error(
+            StatusCode::UNAUTHORIZED,
+            r#"{"error": {"message": "Authentication Error, Invalid proxy server token passed"}}"#,
+        );
+        assert!(matches!(
+            unauthorized,
+            RepositoryError::AIGatewayUnauthorized { .. }
+        ));
+
+        let rejected = LiteLLMRepository::parse_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            r#"{"detail": [{"loc": ["body", "max_budget"], "msg": "Input should be a valid number"}]}"#,
+        );
+        assert!(matches!(
+            rejected,
+            RepositoryError::AIGatewayRejected { status: 422, message }
+                if message.contains("Input should be a valid number")
+        ));
+
+        let unavailable = LiteLLMRepository::parse_error(StatusCode::BAD_GATEWAY, "");
+        assert!(matches!(
+            unavailable,
+            RepositoryError::AIGatewayUnavailable { status: 502, message }
+                if message == "502 Bad Gateway"
+        ));
+    }
+
+    #[test]
+    fn normalized_base_url() {
+        let config = |base_url: &str| LiteLLMConfig {
+            base_url: base_url.to_string(),
+        };
+
+        assert_eq!(
+            config("llm.example.com").normalized_base_url(),
+            "https://llm.example.com"
+        );
+        assert_eq!(
+            config("http://localhost:4000/").normalized_base_url(),
+            "http://localhost:4000"
+        );
+    }
 }
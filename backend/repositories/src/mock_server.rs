@@ -0,0 +1,99 @@
+//! Minimal HTTP server standing in for external APIs in tests.
+
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Request lines and bodies, parsed as JSON if possible and kept as a string otherwise
+pub type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+/// Returns the status line and JSON body for a request line, `Value::Null` sends no body
+pub type Respond = fn(&str) -> (&'static str, Value);
+
+/// Starts a server answering every request with a canned response, returns its base URL.
+pub async fn mock_server(respond: Respond) -> std::io::Result<(String, Requests)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let port = listener.local_addr()?.port();
+
+    let requests = Requests::default();
+
+    tokio::spawn({
+        let requests = requests.clone();
+
+        async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle(stream, requests.clone(), respond));
+            }
+        }
+    });
+
+    Ok((format!("http://127.0.0.1:{port}"), requests))
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    requests: Requests,
+    respond: Respond,
+) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+
+    let header_end = loop {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await?;
+        buf.extend_from_slice(&chunk[..n]);
+
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+
+        if n == 0 {
+            return Ok(());
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buf.len() < header_end + content_length {
+        let mut chunk = [0; 4096];
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let request_line = head.lines().next().unwrap_or_default().to_string();
+    let raw_body = &buf[header_end..];
+    let body = serde_json::from_slice(raw_body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(raw_body).to_string()));
+
+    let (status, response) = respond(&request_line);
+
+    requests
+        .lock()
+        .map_or((), |mut r| r.push((request_line, body)));
+
+    let response = if response.is_null() {
+        String::new()
+    } else {
+        response.to_string()
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    );
+
+    stream.write_all(response.as_bytes()).await
+}
+
+pub fn recorded(requests: &Requests) -> Vec<(String, Value)> {
+    requests.lock().map(|r| r.clone()).unwrap_or_default()
+}
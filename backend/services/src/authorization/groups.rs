@@ -24,6 +24,8 @@ pub enum Group {
     TeamMember,
     TeamMentor,
     TeamAffiliate,
+    // Project roles
+    ProjectOwner,
     // Other roles
     ExpertRater,
 }
@@ -71,6 +73,18 @@ impl Groups {
             &team_roles.into_iter().collect::<Vec<_>>(),
         )
     }
+
+    /// Ownership only counts while the user is still a stakeholder of the event.
+    #[must_use]
+    pub fn from_event_and_project(roles: &UserRoles, event_id: Uuid, is_owner: bool) -> Self {
+        let mut groups = Self::from_event(roles, event_id);
+
+        if is_owner && groups.0.contains(&Group::EventStakeholder) {
+            groups.0.insert(Group::ProjectOwner);
+        }
+
+        groups
+    }
 }
 
 impl From<EventRole> for Groups {
@@ -198,6 +212,11 @@ impl Groups {
         self == &Group::EventAdmin
     }
 
+    #[must_use]
+    pub fn can_view_team_health(&self) -> bool {
+        self == &Group::EventAdmin || self == &Group::EventMentor
+    }
+
     #[must_use]
     pub fn can_create_team(
         &self,
@@ -368,6 +387,10 @@ impl Groups {
             return decision;
         }
 
+        if self == &Group::ProjectOwner {
+            return event_phase != EventPhase::Finished;
+        }
+
         if self == &Group::EventStakeholder {
             return event_phase == EventPhase::Registration;
         }
@@ -1,3 +1,4 @@
+use aes_gcm::aead::rand_core::RngCore;
 use aes_gcm::aead::Aead;
 use aes_gcm::AeadCore;
 use aes_gcm::KeyInit;
@@ -7,9 +8,13 @@ use aes_gcm::{
     Aes256Gcm, AesGcm, Key, Nonce,
 };
 use serde::{Deserialize, Serialize};
+use ssh_key::{Algorithm, LineEnding, PrivateKey};
 
 use crate::ServiceResult;
 
+/// Characters used for generated passwords, without easily confused ones like `0`, `O`, `1`, `l`
+const PASSWORD_ALPHABET: &[u8] = b"abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CryptoConfig {
@@ -54,4 +59,52 @@ impl CryptoService {
         })?;
         Ok(plaintext)
     }
+
+    /// Generates a random password, ~5.8 bits of entropy per character.
+    #[must_use]
+    pub fn generate_password(length: usize) -> String {
+        // Rejection sampling avoids a bias towards the first characters of the alphabet
+        let limit = u8::MAX - (u8::MAX % PASSWORD_ALPHABET.len() as u8);
+        let mut password = String::with_capacity(length);
+
+        while password.len() < length {
+            let mut buf = [0u8; 32];
+            OsRng.fill_bytes(&mut buf);
+
+            for byte in buf.into_iter().filter(|b| *b < limit) {
+                if password.len() == length {
+                    break;
+                }
+
+                password.push(PASSWORD_ALPHABET[byte as usize % PASSWORD_ALPHABET.len()] as char);
+            }
+        }
+
+        password
+    }
+
+    /// Generates an Ed25519 keypair, returns the public and private key in OpenSSH format.
+    pub fn generate_ssh_keypair(comment: &str) -> ServiceResult<(String, String)> {
+        let mut private_key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)?;
+        private_key.set_comment(comment);
+
+        let public_key = private_key.public_key().to_openssh()?;
+        let private_key = private_key.to_openssh(LineEnding::LF)?.to_string();
+
+        Ok((public_key, private_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_password_from_alphabet() {
+        let password = CryptoService::generate_password(24);
+
+        assert_eq!(password.len(), 24);
+        assert!(password.bytes().all(|c| PASSWORD_ALPHABET.contains(&c)));
+        assert_ne!(password, CryptoService::generate_password(24));
+    }
 }
@@ -0,0 +1,253 @@
+pub mod models;
+
+use crate::crypto::CryptoService;
+use crate::discord::models::{DiscordGuild, DiscordGuildForUpdate, DiscordSyncStatus};
+use crate::user::UserService;
+use crate::{ServiceError, ServiceResult};
+use hackathon_portal_repositories::db::{
+    db_discord_guild, db_discord_sync_status, DiscordGuildRepository, DiscordSyncStatusRepository,
+    EventRepository, EventUserRepository,
+};
+use hackathon_portal_repositories::discord::{
+    DiscordCredentials, DiscordGuildJoin, DiscordRepository,
+};
+use hackathon_portal_repositories::DbRepository;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{Set, TransactionTrait};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct DiscordService {
+    user_service: Arc<UserService>,
+    crypto_service: Arc<CryptoService>,
+    db_repo: DbRepository,
+    discord_repo: Arc<DiscordRepository>,
+}
+
+impl DiscordService {
+    #[must_use]
+    pub const fn new(
+        user_service: Arc<UserService>,
+        crypto_service: Arc<CryptoService>,
+        db_repo: DbRepository,
+        discord_repo: Arc<DiscordRepository>,
+    ) -> Self {
+        Self {
+            user_service,
+            crypto_service,
+            db_repo,
+            discord_repo,
+        }
+    }
+
+    /// Returns the bot of the event if client ID, client secret and bot token are set, the
+    /// portal's bot otherwise.
+    pub async fn get_credentials(&self, event_id: Uuid) -> ServiceResult<DiscordCredentials> {
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
+
+        let (client_id, client_secret, bot_token) = match (
+            event.discord_client_id,
+            event.discord_client_secret,
+            event.discord_bot_token,
+        ) {
+            (Some(client_id), Some(client_secret), Some(bot_token)) => {
+                (client_id, client_secret, bot_token)
+            }
+            (None, None, None) => return Ok(self.discord_repo.credentials().clone()),
+            _ => return Err(ServiceError::IncompleteDiscordCredentials),
+        };
+
+        Ok(DiscordCredentials {
+            client_id,
+            client_secret: self.crypto_service.decrypt(&client_secret)?,
+            bot_token: self.crypto_service.decrypt(&bot_token)?,
+        })
+    }
+
+    /// Returns the Discord servers of the event in addition to its own.
+    pub async fn get_guilds(&self, event_id: Uuid) -> ServiceResult<Vec<DiscordGuild>> {
+        let guilds =
+            DiscordGuildRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
+
+        Ok(guilds.into_iter().map(DiscordGuild::from).collect())
+    }
+
+    /// Returns all Discord servers of the event with the configuration they are synced with,
+    /// starting with the event's own server.
+    pub async fn get_synced_guilds(&self, event_id: Uuid) -> ServiceResult<Vec<DiscordGuild>> {
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
+
+        let primary = event.discord_server_id.map(|guild_id| DiscordGuild {
+            guild_id,
+            discord_config: event.discord_config.clone(),
+        });
+
+        let additional = self
+            .get_guilds(event_id)
+            .await?
+            .into_iter()
+            .map(|guild| DiscordGuild {
+                discord_config: guild
+                    .discord_config
+                    .or_else(|| event.discord_config.clone()),
+                ..guild
+            });
+
+        Ok(primary.into_iter().chain(additional).collect())
+    }
+
+    /// Returns the event and user the Discord account is linked to, among the events synced to the
+    /// Discord server.
+    pub async fn get_event_user_by_discord_id(
+        &self,
+        guild_id: &str,
+        discord_user_id: &str,
+    ) -> ServiceResult<Option<(Uuid, Uuid)>> {
+        let links =
+            EventUserRepository::fetch_all_by_discord_id(self.db_repo.conn(), discord_user_id)
+                .await?;
+
+        for link in links {
+            let guilds = self.get_synced_guilds(link.event_id).await?;
+
+            if guilds.iter().any(|guild| guild.guild_id == guild_id) {
+                return Ok(Some((link.event_id, link.user_id)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub async fn update_guild(
+        &self,
+        event_id: Uuid,
+        guild_id: String,
+        guild_fu: DiscordGuildForUpdate,
+    ) -> ServiceResult<DiscordGuild> {
+        let active_guild = db_discord_guild::ActiveModel {
+            event_id: Set(event_id),
+            guild_id: Set(guild_id),
+            discord_config: Set(guild_fu.discord_config),
+        };
+
+        let guild = db_discord_guild::Entity::insert(active_guild)
+            .on_conflict(
+                OnConflict::columns([
+                    db_discord_guild::Column::EventId,
+                    db_discord_guild::Column::GuildId,
+                ])
+                .update_column(db_discord_guild::Column::DiscordConfig)
+                .to_owned(),
+            )
+            .exec_with_returning(self.db_repo.conn())
+            .await?;
+
+        Ok(guild.into())
+    }
+
+    /// Removes the Discord server from the event along with its sync status, the server itself
+    /// is left untouched.
+    pub async fn delete_guild(&self, event_id: Uuid, guild_id: &str) -> ServiceResult<()> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let guild = DiscordGuildRepository::fetch_by_id(&txn, event_id, guild_id).await?;
+
+        db_discord_sync_status::Entity::delete_many()
+            .filter(db_discord_sync_status::Column::EventId.eq(event_id))
+            .filter(db_discord_sync_status::Column::GuildId.eq(guild_id))
+            .exec(&txn)
+            .await?;
+
+        guild.delete(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    /// Completes the OAuth flow of a user: adds them to all Discord servers of the event with
+    /// their name as nickname and links their Discord account. Returns the Discord user ID.
+    pub async fn link_account(
+        &self,
+        user_id: Uuid,
+        event_id: Uuid,
+        code: &str,
+        redirect_uri: &str,
+        nick: &str,
+    ) -> ServiceResult<String> {
+        let discord_repo = self
+            .discord_repo
+            .with_credentials(self.get_credentials(event_id).await?);
+
+        let access_token = discord_repo.exchange_code(code, redirect_uri).await?;
+        let discord_user = discord_repo.get_current_user(&access_token).await?;
+
+        for guild in self.get_synced_guilds(event_id).await? {
+            let join = discord_repo
+                .add_guild_member(&guild.guild_id, &discord_user.id, &access_token, nick)
+                .await?;
+
+            if join == DiscordGuildJoin::AlreadyMember {
+                discord_repo
+                    .update_member_nick(&guild.guild_id, &discord_user.id, nick)
+                    .await?;
+            }
+        }
+
+        self.user_service
+            .update_discord_id(user_id, event_id, discord_user.id.clone())
+            .await?;
+
+        Ok(discord_user.id)
+    }
+
+    pub async fn get_sync_statuses(&self, event_id: Uuid) -> ServiceResult<Vec<DiscordSyncStatus>> {
+        DiscordSyncStatusRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id)
+            .await?
+            .into_iter()
+            .map(DiscordSyncStatus::try_from)
+            .collect()
+    }
+
+    /// Replaces the recorded outcome of the last sync of the Discord server.
+    pub async fn record_sync_status(&self, status: &DiscordSyncStatus) -> ServiceResult<()> {
+        let active_status = db_discord_sync_status::ActiveModel {
+            event_id: Set(status.event_id),
+            guild_id: Set(status.guild_id.clone()),
+            outcome: Set(status.outcome),
+            bot_in_guild: Set(status.bot_in_guild),
+            started_at: Set(status.started_at),
+            duration_ms: Set(status.duration_ms as i32),
+            created: Set(status.created as i32),
+            updated: Set(status.updated as i32),
+            deleted: Set(status.deleted as i32),
+            member_failures: Set(serde_json::to_value(&status.member_failures)?),
+            error: Set(status.error.clone()),
+        };
+
+        db_discord_sync_status::Entity::insert(active_status)
+            .on_conflict(
+                OnConflict::columns([
+                    db_discord_sync_status::Column::EventId,
+                    db_discord_sync_status::Column::GuildId,
+                ])
+                .update_columns([
+                    db_discord_sync_status::Column::Outcome,
+                    db_discord_sync_status::Column::BotInGuild,
+                    db_discord_sync_status::Column::StartedAt,
+                    db_discord_sync_status::Column::DurationMs,
+                    db_discord_sync_status::Column::Created,
+                    db_discord_sync_status::Column::Updated,
+                    db_discord_sync_status::Column::Deleted,
+                    db_discord_sync_status::Column::MemberFailures,
+                    db_discord_sync_status::Column::Error,
+                ])
+                .to_owned(),
+            )
+            .exec(self.db_repo.conn())
+            .await?;
+
+        Ok(())
+    }
+}
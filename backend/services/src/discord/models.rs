@@ -0,0 +1,79 @@
+use crate::ServiceError;
+use chrono::NaiveDateTime;
+use hackathon_portal_repositories::db::{
+    db_discord_guild, db_discord_sync_status, DiscordSyncOutcome,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Discord server of an event in addition to its own
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordGuild {
+    pub guild_id: String,
+    /// Falls back to the configuration of the event if `None`
+    pub discord_config: Option<String>,
+}
+
+impl From<db_discord_guild::Model> for DiscordGuild {
+    fn from(value: db_discord_guild::Model) -> Self {
+        Self {
+            guild_id: value.guild_id,
+            discord_config: value.discord_config,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordGuildForUpdate {
+    /// Falls back to the configuration of the event if `None`
+    pub discord_config: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+pub struct DiscordMemberRoleFailure {
+    pub discord_user_id: String,
+    /// Role name
+    pub role: String,
+    pub error: String,
+}
+
+/// Outcome of the last Discord sync of a Discord server of an event.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct DiscordSyncStatus {
+    pub event_id: Uuid,
+    pub guild_id: String,
+    pub outcome: DiscordSyncOutcome,
+    /// `None` if the membership check failed
+    pub bot_in_guild: Option<bool>,
+    pub started_at: NaiveDateTime,
+    pub duration_ms: u32,
+    /// Number of created roles, categories and channels
+    pub created: u32,
+    /// Number of updated roles, channels and permissions
+    pub updated: u32,
+    /// Number of deleted roles, categories and channels
+    pub deleted: u32,
+    pub member_failures: Vec<DiscordMemberRoleFailure>,
+    pub error: Option<String>,
+}
+
+impl TryFrom<db_discord_sync_status::Model> for DiscordSyncStatus {
+    type Error = ServiceError;
+
+    fn try_from(value: db_discord_sync_status::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            event_id: value.event_id,
+            guild_id: value.guild_id,
+            outcome: value.outcome,
+            bot_in_guild: value.bot_in_guild,
+            started_at: value.started_at,
+            duration_ms: value.duration_ms as u32,
+            created: value.created as u32,
+            updated: value.updated as u32,
+            deleted: value.deleted as u32,
+            member_failures: serde_json::from_value(value.member_failures)?,
+            error: value.error,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::project::models::MatchingDiagnostics;
 use chrono::NaiveDateTime;
 use derive_more::From;
 use hackathon_portal_repositories::db::{EventPhase, MediaUsage};
@@ -46,9 +47,12 @@ pub enum ServiceError {
 
     ProjectPreferenceDuplicate,
 
-    ProjectPreferenceWrongCount {
-        expected: usize,
-        actual: usize,
+    ProjectPreferenceInvalid {
+        project_id: String,
+    },
+
+    ProjectOwnerNotStakeholder {
+        user_id: String,
     },
 
     WrongVotingRank {
@@ -64,10 +68,19 @@ pub enum ServiceError {
         current_phase: EventPhase,
     },
 
+    ProjectTeamLimits {
+        min_teams: u32,
+        max_teams: u32,
+    },
+
     Matching {
         message: String,
     },
 
+    MatchingInfeasible {
+        diagnostics: MatchingDiagnostics,
+    },
+
     UploadRateLimitExceeded,
 
     UploadContentLengthExceeded {
@@ -86,9 +99,21 @@ pub enum ServiceError {
 
     MissingMasterAIAPIKey,
 
+    MissingTeamAIAPIKey,
+
+    MissingAIBudget,
+
+    IncompleteDiscordCredentials,
+
     Parsing {
         message: String,
     },
+
+    InvalidTemplate {
+        field: String,
+        message: String,
+    },
+
     WrongTechnicalQuestionPoints {
         given_score: f64,
         allowed_scores: String,
@@ -123,8 +148,14 @@ pub enum ServiceError {
     #[from]
     SerdeJson(#[serde_as(as = "DisplayFromStr")] serde_json::Error),
 
+    #[from]
+    SerdeYaml(#[serde_as(as = "DisplayFromStr")] serde_yaml::Error),
+
     #[from]
     Crypto(#[serde_as(as = "DisplayFromStr")] aes_gcm::Error),
+
+    #[from]
+    SshKey(#[serde_as(as = "DisplayFromStr")] ssh_key::Error),
     // endregion
 }
 
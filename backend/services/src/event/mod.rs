@@ -5,6 +5,7 @@ use crate::crypto::CryptoService;
 use crate::event::models::{Event, EventForCreate, EventForUpdate};
 use crate::rating::RatingService;
 use crate::sidequest::SidequestService;
+use crate::team::template::validate_template;
 use crate::user::models::{ReducedUser, UserForCreate};
 use crate::user::UserService;
 use crate::{ServiceError, ServiceResult};
@@ -114,6 +115,24 @@ impl EventService {
         let txn = self.db_repo.conn().begin().await?;
 
         let event = EventRepository::fetch_by_id(&txn, event_id).await?;
+
+        for (field, template) in [
+            (
+                "managed_address_template",
+                &event_fu.managed_address_template,
+            ),
+            ("direct_address_template", &event_fu.direct_address_template),
+            (
+                "private_address_template",
+                &event_fu.private_address_template,
+            ),
+            ("ssh_config_template", &event_fu.ssh_config_template),
+        ] {
+            if let Some(template) = template.as_deref().filter(|t| !t.is_empty()) {
+                validate_template(field, template, &event)?;
+            }
+        }
+
         let mut active_event = event.into_active_model();
 
         if let Some(name) = &event_fu.name {
@@ -224,6 +243,14 @@ impl EventService {
             active_event.master_ai_api_key = Set(Some(enc_key));
         }
 
+        if let Some(ai_models) = event_fu.ai_models {
+            active_event.ai_models = Set(Some(ai_models));
+        }
+
+        if let Some(ai_default_budget) = event_fu.ai_default_budget {
+            active_event.ai_default_budget = Set(Some(ai_default_budget));
+        }
+
         if let Some(discord_server_id) = event_fu.discord_server_id {
             if !discord_server_id.is_empty() {
                 active_event.discord_server_id = Set(Some(discord_server_id));
@@ -236,6 +263,30 @@ impl EventService {
             }
         }
 
+        if let Some(discord_client_id) = event_fu.discord_client_id {
+            active_event.discord_client_id =
+                Set(Some(discord_client_id).filter(|id| !id.is_empty()));
+        }
+
+        if let Some(discord_client_secret) = event_fu.discord_client_secret {
+            active_event.discord_client_secret =
+                Set(self.encrypt_non_empty(&discord_client_secret)?);
+        }
+
+        if let Some(discord_bot_token) = event_fu.discord_bot_token {
+            active_event.discord_bot_token = Set(self.encrypt_non_empty(&discord_bot_token)?);
+        }
+
+        let discord_credentials = [
+            active_event.discord_client_id.as_ref().is_some(),
+            active_event.discord_client_secret.as_ref().is_some(),
+            active_event.discord_bot_token.as_ref().is_some(),
+        ];
+
+        if discord_credentials.contains(&true) && discord_credentials.contains(&false) {
+            return Err(ServiceError::IncompleteDiscordCredentials);
+        }
+
         let event = active_event.update(&txn).await?;
 
         txn.commit().await?;
@@ -334,4 +385,12 @@ impl EventService {
 
         Ok(slug)
     }
+
+    fn encrypt_non_empty(&self, plaintext: &str) -> ServiceResult<Option<Vec<u8>>> {
+        if plaintext.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(self.crypto_service.encrypt(plaintext)?))
+    }
 }
@@ -29,8 +29,14 @@ pub struct Event {
     pub phase: EventPhase,
     pub discord_server_id: Option<String>,
     pub discord_config: Option<String>,
+    /// Application of the event's own bot, `None` if the portal's bot is used
+    pub discord_client_id: Option<String>,
     pub vote_enabled: bool,
     pub finalists_visible: bool,
+    /// Models teams may use through their AI API key, empty allows all models
+    pub ai_models: Vec<String>,
+    /// Budget in USD used when provisioning AI API keys for all teams
+    pub ai_default_budget: Option<f64>,
 }
 
 impl From<db_event::Model> for Event {
@@ -58,8 +64,11 @@ impl From<db_event::Model> for Event {
             phase: value.phase,
             discord_server_id: value.discord_server_id,
             discord_config: value.discord_config,
+            discord_client_id: value.discord_client_id,
             vote_enabled: value.voting_open,
             finalists_visible: value.finalists_visible,
+            ai_models: value.ai_models.unwrap_or_default(),
+            ai_default_budget: value.ai_default_budget,
         }
     }
 }
@@ -95,7 +104,15 @@ pub struct EventForUpdate {
     pub phase: Option<EventPhase>,
     pub discord_server_id: Option<String>,
     pub discord_config: Option<String>,
+    /// The event's own bot replaces the portal's, client ID, client secret and bot token have to
+    /// be set or removed together. An empty string removes the value.
+    pub discord_client_id: Option<String>,
+    pub discord_client_secret: Option<String>,
+    pub discord_bot_token: Option<String>,
     pub master_ai_api_key: Option<String>,
+    /// Applied to existing teams when their AI API key is updated
+    pub ai_models: Option<Vec<String>>,
+    pub ai_default_budget: Option<f64>,
     pub vote_enabled: Option<bool>,
     pub finalists_visible: Option<bool>,
 }
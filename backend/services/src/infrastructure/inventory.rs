@@ -0,0 +1,287 @@
+use crate::infrastructure::models::IngressMode;
+use crate::team::models::Team;
+use crate::ServiceResult;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fmt::Write;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Ansible group containing all team hosts
+const ANSIBLE_GROUP: &str = "teams";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, ToSchema, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum InventoryFormat {
+    #[default]
+    Json,
+    AnsibleYaml,
+    AnsibleIni,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct InventoryHost {
+    pub team_id: Uuid,
+    pub team_index: i32,
+    pub team_slug: String,
+    pub team_name: String,
+    pub private_address: Option<String>,
+    pub direct_address: Option<String>,
+    pub managed_address: Option<String>,
+    pub ssh_config: Option<String>,
+    pub ssh_public_key: Option<String>,
+    pub ingress_enabled: bool,
+    /// Ports the team server has to listen on to be reachable through the ingress
+    pub ingress_ports: Vec<u16>,
+    /// Only included if credentials were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Still deployed on the team server until the rotation is confirmed, only included if
+    /// credentials were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_password: Option<String>,
+    /// Only included if credentials were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_private_key: Option<String>,
+    /// Only included if credentials were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ai_api_key: Option<String>,
+}
+
+impl InventoryHost {
+    #[must_use]
+    pub fn from_team(team: &Team, include_credentials: bool) -> Self {
+        let mut ingress_ports = match &team.ingress_config.mode {
+            IngressMode::Managed(ingress_config) => ingress_config
+                .routes
+                .iter()
+                .map(|route| route.server_port)
+                .collect(),
+            IngressMode::Custom(ingress_config) => vec![ingress_config.server_port],
+        };
+
+        ingress_ports.sort_unstable();
+        ingress_ports.dedup();
+
+        let credential = |value: &Option<String>| value.clone().filter(|_| include_credentials);
+
+        Self {
+            team_id: team.id,
+            team_index: team.index,
+            team_slug: team.slug.clone(),
+            team_name: team.name.clone(),
+            private_address: team.private_address.clone(),
+            direct_address: team.direct_address.clone(),
+            managed_address: team.managed_address.clone(),
+            ssh_config: team.ssh_config.clone(),
+            ssh_public_key: team.ssh_public_key.clone(),
+            ingress_enabled: team.ingress_enabled,
+            ingress_ports,
+            password: credential(&team.password),
+            previous_password: credential(&team.previous_password),
+            ssh_private_key: credential(&team.ssh_private_key),
+            ai_api_key: credential(&team.ai_api_key),
+        }
+    }
+
+    /// Address Ansible connects to, the private address if available.
+    #[must_use]
+    pub fn ansible_host(&self) -> Option<&str> {
+        self.private_address
+            .as_deref()
+            .or(self.direct_address.as_deref())
+    }
+
+    fn ansible_vars(&self, ansible_host: &str) -> Map<String, Value> {
+        let mut vars = Map::new();
+
+        vars.insert("ansible_host".to_string(), json!(ansible_host));
+        vars.insert("team_id".to_string(), json!(self.team_id));
+        vars.insert("team_index".to_string(), json!(self.team_index));
+        vars.insert("team_slug".to_string(), json!(self.team_slug));
+        vars.insert("team_name".to_string(), json!(self.team_name));
+        vars.insert("ingress_enabled".to_string(), json!(self.ingress_enabled));
+        vars.insert("ingress_ports".to_string(), json!(self.ingress_ports));
+
+        let optional = [
+            ("private_address", &self.private_address),
+            ("direct_address", &self.direct_address),
+            ("managed_address", &self.managed_address),
+            ("ssh_config", &self.ssh_config),
+            ("team_ssh_public_key", &self.ssh_public_key),
+            ("ansible_password", &self.password),
+            ("team_previous_password", &self.previous_password),
+            ("team_ssh_private_key", &self.ssh_private_key),
+            ("team_ai_api_key", &self.ai_api_key),
+        ];
+
+        for (key, value) in optional {
+            if let Some(value) = value {
+                vars.insert(key.to_string(), json!(value));
+            }
+        }
+
+        vars
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct Inventory {
+    pub event_id: Uuid,
+    pub hosts: Vec<InventoryHost>,
+}
+
+impl Inventory {
+    #[must_use]
+    pub fn from_teams(event_id: Uuid, teams: &[Team], include_credentials: bool) -> Self {
+        let mut hosts = teams
+            .iter()
+            .map(|team| InventoryHost::from_team(team, include_credentials))
+            .collect::<Vec<_>>();
+
+        hosts.sort_by_key(|host| host.team_index);
+
+        Self { event_id, hosts }
+    }
+
+    pub fn render(&self, format: InventoryFormat) -> ServiceResult<String> {
+        let rendered = match format {
+            InventoryFormat::Json => serde_json::to_string_pretty(self)?,
+            InventoryFormat::AnsibleYaml => self.render_ansible_yaml()?,
+            InventoryFormat::AnsibleIni => self.render_ansible_ini(),
+        };
+
+        Ok(rendered)
+    }
+
+    /// Hosts without any address cannot be provisioned and are only listed in a comment.
+    fn render_skipped_header(&self) -> String {
+        let mut out = String::new();
+
+        for host in self.hosts.iter().filter(|h| h.ansible_host().is_none()) {
+            // Writing to a `String` cannot fail
+            let _ = writeln!(
+                out,
+                "# Skipped team {} ({}): no private or direct address",
+                host.team_slug, host.team_id
+            );
+        }
+
+        out
+    }
+
+    fn render_ansible_yaml(&self) -> ServiceResult<String> {
+        let hosts = self
+            .hosts
+            .iter()
+            .filter_map(|host| {
+                let ansible_host = host.ansible_host()?;
+                Some((
+                    host.team_slug.clone(),
+                    Value::Object(host.ansible_vars(ansible_host)),
+                ))
+            })
+            .collect::<Map<_, _>>();
+
+        let inventory = json!({
+            "all": {
+                "children": {
+                    ANSIBLE_GROUP: { "hosts": hosts },
+                },
+            },
+        });
+
+        Ok(self.render_skipped_header() + &serde_yaml::to_string(&inventory)?)
+    }
+
+    fn render_ansible_ini(&self) -> String {
+        let mut out = self.render_skipped_header();
+
+        // Writing to a `String` cannot fail
+        let _ = writeln!(out, "[{ANSIBLE_GROUP}]");
+
+        for host in &self.hosts {
+            let Some(ansible_host) = host.ansible_host() else {
+                continue;
+            };
+
+            let _ = write!(out, "{}", host.team_slug);
+
+            for (key, value) in host.ansible_vars(ansible_host) {
+                match value {
+                    // Host vars have to fit on a single line, e.g. `ssh_config` is only exported
+                    // in the other formats
+                    Value::String(s) if s.contains('\n') => {}
+                    value => {
+                        let _ = write!(out, " {key}={value}");
+                    }
+                }
+            }
+
+            let _ = writeln!(out);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Inventory {
+        let host = |index: i32, private_address: Option<&str>| InventoryHost {
+            team_id: Uuid::nil(),
+            team_index: index,
+            team_slug: format!("team-{index:02}"),
+            team_name: format!("Team {index}"),
+            private_address: private_address.map(str::to_string),
+            direct_address: None,
+            managed_address: None,
+            ssh_config: Some("Host team\n  User root".to_string()),
+            ssh_public_key: None,
+            ingress_enabled: true,
+            ingress_ports: vec![80, 8080],
+            password: Some("secret".to_string()),
+            previous_password: None,
+            ssh_private_key: None,
+            ai_api_key: None,
+        };
+
+        Inventory {
+            event_id: Uuid::nil(),
+            hosts: vec![host(1, Some("10.0.0.1")), host(2, None)],
+        }
+    }
+
+    #[test]
+    fn render_ansible_ini() -> ServiceResult<()> {
+        let rendered = inventory().render(InventoryFormat::AnsibleIni)?;
+        let lines = rendered.lines().collect::<Vec<_>>();
+
+        assert!(lines[0].starts_with("# Skipped team team-02"));
+        assert_eq!(lines[1], "[teams]");
+        assert!(lines[2].starts_with("team-01 "));
+        assert!(lines[2].contains(" ansible_host=\"10.0.0.1\""));
+        assert!(lines[2].contains(" ansible_password=\"secret\""));
+        assert!(lines[2].contains(" team_name=\"Team 1\""));
+        assert!(lines[2].contains(" ingress_ports=[80,8080]"));
+        assert!(!lines[2].contains("ssh_config"));
+        assert_eq!(lines.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_ansible_yaml() -> ServiceResult<()> {
+        let rendered = inventory().render(InventoryFormat::AnsibleYaml)?;
+        let parsed = serde_yaml::from_str::<Value>(&rendered)?;
+        let hosts = &parsed["all"]["children"]["teams"]["hosts"];
+
+        assert_eq!(hosts["team-01"]["ansible_host"], "10.0.0.1");
+        assert_eq!(hosts["team-01"]["ssh_config"], "Host team\n  User root");
+        assert!(hosts.get("team-02").is_none());
+
+        Ok(())
+    }
+}
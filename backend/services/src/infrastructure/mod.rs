@@ -1,156 +1,351 @@
+use crate::infrastructure::inventory::Inventory;
 use crate::infrastructure::models::{
-    AccessControlMode, IngressMode, ManagedIngressConfig, TraefikDynamicConfig, TraefikHttpConfig,
-    TraefikLoadBalancerConfig, TraefikRouterConfig, TraefikServerConfig, TraefikServiceConfig,
+    EventHealthOverview, IngressMode, ProxySkippedTeam, ServerProtocol, TeamHealthCheck,
+    TeamHealthProbeResult, TeamHealthProbeTarget, TeamHealthSummary, TraefikDynamicConfig,
+};
+use crate::infrastructure::proxy::{
+    CaddyConfig, NginxConfig, ProxyConfigRenderer, ProxyRoutes, TraefikConfig,
 };
 use crate::team::models::Team;
 use crate::team::TeamService;
 use crate::{ServiceError, ServiceResult};
+use hackathon_portal_repositories::db::{
+    db_event, db_team_health_check, EventPhase, EventRepository, TeamHealthCheckRepository,
+    TeamHealthStatus, TeamRepository,
+};
+use hackathon_portal_repositories::DbRepository;
 use itertools::Itertools;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{EntityTrait, ModelTrait, Set, TransactionTrait};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tracing::warn;
+use uuid::Uuid;
 
+pub mod inventory;
 pub mod models;
+pub mod proxy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct TraefikConfig {
-    #[serde(default = "TraefikConfig::default_entrypoints")]
-    pub entrypoints: Vec<String>,
-    pub auth_middlewares: Vec<String>,
-    #[serde(default = "TraefikConfig::default_default_middlewares")]
-    pub default_middlewares: Vec<String>, // applies after auth middlewares
+pub struct InfrastructureConfig {
+    pub traefik: Option<TraefikConfig>,
+    pub caddy: Option<CaddyConfig>,
+    pub nginx: Option<NginxConfig>,
+    /// Skip certificate verification for teams serving HTTPS in custom ingress mode. Team
+    /// servers usually present self-signed certificates.
+    #[serde(default = "InfrastructureConfig::default_https_insecure_skip_verify")]
+    pub https_insecure_skip_verify: bool,
 }
 
-impl TraefikConfig {
-    #[must_use]
-    pub fn default_entrypoints() -> Vec<String> {
-        vec!["web".to_string()]
-    }
-
+impl InfrastructureConfig {
     #[must_use]
-    pub fn default_default_middlewares() -> Vec<String> {
-        vec![]
+    pub const fn default_https_insecure_skip_verify() -> bool {
+        true
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(deny_unknown_fields)]
-pub struct InfrastructureConfig {
-    pub traefik: Option<TraefikConfig>,
+impl Default for InfrastructureConfig {
+    fn default() -> Self {
+        Self {
+            traefik: None,
+            caddy: None,
+            nginx: None,
+            https_insecure_skip_verify: Self::default_https_insecure_skip_verify(),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct InfrastructureService {
     config: InfrastructureConfig,
     team_service: Arc<TeamService>,
+    db_repo: DbRepository,
 }
 
 impl InfrastructureService {
     #[must_use]
-    pub const fn new(config: InfrastructureConfig, team_service: Arc<TeamService>) -> Self {
+    pub const fn new(
+        config: InfrastructureConfig,
+        team_service: Arc<TeamService>,
+        db_repo: DbRepository,
+    ) -> Self {
         Self {
             config,
             team_service,
+            db_repo,
         }
     }
 
+    pub async fn get_proxy_routes(&self) -> ServiceResult<ProxyRoutes> {
+        let teams = self.team_service.get_all_teams().await?;
+
+        Ok(ProxyRoutes::from_teams(
+            &teams,
+            self.config.https_insecure_skip_verify,
+        ))
+    }
+
+    /// Teams with ingress enabled that did not get a route in the proxy configs.
+    pub async fn get_proxy_skipped_teams(&self) -> ServiceResult<Vec<ProxySkippedTeam>> {
+        Ok(self.get_proxy_routes().await?.skipped_teams)
+    }
+
     pub async fn get_traefik_dynamic_config(&self) -> ServiceResult<TraefikDynamicConfig> {
-        let Some(config) = &self.config.traefik else {
-            return Err(ServiceError::DependencyMissing {
-                dependency: "InfrastructureService.config.traefik".to_string(),
-            });
+        let config = Self::require(self.config.traefik.as_ref(), "traefik")?;
+
+        Ok(config.render(&self.get_proxy_routes().await?))
+    }
+
+    /// Returns a complete Caddy JSON config, to be loaded via `caddy run --config`.
+    pub async fn get_caddy_config(&self) -> ServiceResult<serde_json::Value> {
+        let config = Self::require(self.config.caddy.as_ref(), "caddy")?;
+
+        Ok(config.render(&self.get_proxy_routes().await?))
+    }
+
+    /// Returns nginx server blocks, to be included in the `http` context.
+    pub async fn get_nginx_config(&self) -> ServiceResult<String> {
+        let config = Self::require(self.config.nginx.as_ref(), "nginx")?;
+
+        Ok(config.render(&self.get_proxy_routes().await?))
+    }
+
+    /// Exports the team servers of an event, credentials are only included on request and
+    /// their reveal is audit logged.
+    pub async fn get_inventory(
+        &self,
+        event_id: Uuid,
+        include_credentials: bool,
+    ) -> ServiceResult<Inventory> {
+        let teams = if include_credentials {
+            self.team_service
+                .reveal_teams_credentials(None, event_id)
+                .await?
+        } else {
+            self.team_service.get_teams(event_id).await?
         };
 
-        let teams_with_ingress_config = self
-            .team_service
-            .get_all_teams()
-            .await?
+        Ok(Inventory::from_teams(event_id, &teams, include_credentials))
+    }
+
+    /// Endpoints of all teams with ingress enabled in events that are currently running.
+    pub async fn get_health_probe_targets(&self) -> ServiceResult<Vec<TeamHealthProbeTarget>> {
+        let mut targets = Vec::new();
+
+        for event in self.fetch_probed_events().await? {
+            let teams = self.team_service.get_teams(event.id).await?;
+
+            targets.extend(
+                teams
+                    .iter()
+                    .filter(|team| team.ingress_enabled)
+                    .flat_map(Self::get_team_health_probe_targets),
+            );
+        }
+
+        Ok(targets)
+    }
+
+    fn get_team_health_probe_targets(team: &Team) -> Vec<TeamHealthProbeTarget> {
+        let Some(private_address) = team.private_address.as_deref() else {
+            return vec![];
+        };
+
+        let ports = match &team.ingress_config.mode {
+            IngressMode::Managed(ingress_config) => ingress_config
+                .routes
+                .iter()
+                .map(|route| (route.server_port, ServerProtocol::Http))
+                .collect(),
+            IngressMode::Custom(ingress_config) => vec![(
+                ingress_config.server_port,
+                ingress_config.server_protocol.clone(),
+            )],
+        };
+
+        ports
             .into_iter()
-            .filter(|team| team.ingress_enabled)
-            .filter_map(|team| match &team.ingress_config.mode {
-                IngressMode::Managed(config) => Some((team.clone(), config.clone())),
-                IngressMode::Custom(_) => None,
+            .unique_by(|(port, _)| *port)
+            .map(|(port, protocol)| {
+                let scheme = match protocol {
+                    ServerProtocol::Http => "http",
+                    ServerProtocol::Https => "https",
+                };
+
+                TeamHealthProbeTarget {
+                    team_id: team.id,
+                    port,
+                    url: format!("{scheme}://{private_address}:{port}/"),
+                }
             })
-            .collect_vec();
+            .collect()
+    }
+
+    async fn fetch_probed_events(&self) -> ServiceResult<Vec<db_event::Model>> {
+        let events = EventRepository::fetch_all(self.db_repo.conn()).await?;
+
+        Ok(events
+            .into_iter()
+            .filter(|e| matches!(e.phase, EventPhase::Hacking | EventPhase::Grading))
+            .collect())
+    }
+
+    /// Stores the results of a probing run. Checks of teams in probed events whose endpoints
+    /// are no longer probed are removed, checks of other events are kept.
+    pub async fn record_health_probes(
+        &self,
+        results: Vec<TeamHealthProbeResult>,
+    ) -> ServiceResult<()> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let mut probed_teams = HashSet::new();
+        for event in self.fetch_probed_events().await? {
+            probed_teams.extend(
+                TeamRepository::fetch_all_by_event_id(&txn, event.id)
+                    .await?
+                    .into_iter()
+                    .map(|team| team.id),
+            );
+        }
+
+        let existing = db_team_health_check::Entity::find()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|check| ((check.team_id, check.port), check))
+            .collect::<HashMap<_, _>>();
+
+        let probed = results
+            .iter()
+            .map(|result| (result.target.team_id, i32::from(result.target.port)))
+            .collect::<HashSet<_>>();
+
+        for (key, check) in &existing {
+            if probed_teams.contains(&check.team_id) && !probed.contains(key) {
+                check.clone().delete(&txn).await?;
+            }
+        }
 
-        let mut routers = HashMap::new();
-        let mut services = HashMap::new();
+        for result in results {
+            let key = (result.target.team_id, i32::from(result.target.port));
 
-        for (team, ingress_config) in teams_with_ingress_config {
-            let key = format!("team-{}-{}", team.slug, team.id);
+            let status_changed_at = existing
+                .get(&key)
+                .filter(|check| check.status == result.status)
+                .map_or(result.checked_at, |check| check.status_changed_at);
 
-            let (Some(router), Some(service)) = (
-                Self::get_traefik_team_router(config, &key, &team, &ingress_config),
-                Self::get_traefik_team_service(&team, &ingress_config),
-            ) else {
-                continue;
+            let check = db_team_health_check::ActiveModel {
+                team_id: Set(result.target.team_id),
+                port: Set(i32::from(result.target.port)),
+                url: Set(result.target.url),
+                status: Set(result.status),
+                status_code: Set(result.status_code.map(i32::from)),
+                latency_ms: Set(result.latency_ms.map(|l| l as i32)),
+                error: Set(result.error),
+                checked_at: Set(result.checked_at),
+                status_changed_at: Set(status_changed_at),
             };
 
-            routers.insert(key.clone(), router);
-            services.insert(key, service);
+            db_team_health_check::Entity::insert(check)
+                .on_conflict(
+                    OnConflict::columns([
+                        db_team_health_check::Column::TeamId,
+                        db_team_health_check::Column::Port,
+                    ])
+                    .update_columns([
+                        db_team_health_check::Column::Url,
+                        db_team_health_check::Column::Status,
+                        db_team_health_check::Column::StatusCode,
+                        db_team_health_check::Column::LatencyMs,
+                        db_team_health_check::Column::Error,
+                        db_team_health_check::Column::CheckedAt,
+                        db_team_health_check::Column::StatusChangedAt,
+                    ])
+                    .to_owned(),
+                )
+                .exec(&txn)
+                .await?;
         }
 
-        let dynamic_config = TraefikDynamicConfig {
-            http: TraefikHttpConfig { routers, services },
-        };
+        txn.commit().await?;
 
-        Ok(dynamic_config)
+        Ok(())
     }
 
-    fn get_traefik_team_router(
-        config: &TraefikConfig,
-        key: &str,
-        team: &Team,
-        ingress_config: &ManagedIngressConfig,
-    ) -> Option<TraefikRouterConfig> {
-        let Some(managed_address) = team.managed_address.as_deref() else {
-            warn!(team = ?team.id, "Team has ingress enabled but no managed address, skipping");
-            return None;
-        };
+    pub async fn get_team_health_checks(
+        &self,
+        team_id: Uuid,
+    ) -> ServiceResult<Vec<TeamHealthCheck>> {
+        let checks =
+            TeamHealthCheckRepository::fetch_all_by_team_id(self.db_repo.conn(), team_id).await?;
 
-        let rule =
-            format!("Host(`{managed_address}`) || Header(`X-Forwarded-Host`, `{managed_address}`)");
+        Ok(checks.into_iter().map(TeamHealthCheck::from).collect())
+    }
 
-        let mut middlewares = if matches!(
-            ingress_config.access_control_mode,
-            AccessControlMode::AuthenticationAuthorization | AccessControlMode::Authentication
-        ) {
-            config.auth_middlewares.clone()
-        } else {
-            vec![]
-        };
+    /// Health checks of all teams of the event, grouped by team.
+    pub async fn get_event_health_checks(
+        &self,
+        event_id: Uuid,
+    ) -> ServiceResult<HashMap<Uuid, Vec<TeamHealthCheck>>> {
+        let checks =
+            TeamHealthCheckRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
 
-        middlewares.extend(config.default_middlewares.clone());
+        Ok(checks
+            .into_iter()
+            .map(TeamHealthCheck::from)
+            .into_group_map_by(|check| check.team_id))
+    }
 
-        let service = TraefikRouterConfig {
-            rule,
-            service: key.to_string(),
-            entry_points: config.entrypoints.clone(),
-            middlewares,
-        };
+    pub async fn get_event_health_overview(
+        &self,
+        event_id: Uuid,
+    ) -> ServiceResult<EventHealthOverview> {
+        let teams = self.team_service.get_teams(event_id).await?;
+        let mut checks = self.get_event_health_checks(event_id).await?;
 
-        Some(service)
-    }
+        let teams = teams
+            .into_iter()
+            .filter_map(|team| {
+                let checks = checks.remove(&team.id).unwrap_or_default();
 
-    fn get_traefik_team_service(
-        team: &Team,
-        ingress_config: &ManagedIngressConfig,
-    ) -> Option<TraefikServiceConfig> {
-        let Some(private_address) = team.private_address.as_deref() else {
-            warn!(team = ?team.id, "Team has ingress enabled but no private address");
-            return None;
-        };
+                if !team.ingress_enabled && checks.is_empty() {
+                    return None;
+                }
 
-        let service = TraefikServiceConfig {
-            load_balancer: TraefikLoadBalancerConfig {
-                servers: vec![TraefikServerConfig {
-                    url: format!("http://{private_address}:{}", ingress_config.server_port),
-                }],
-            },
-        };
+                let status = if checks.is_empty() {
+                    None
+                } else if checks.iter().all(|c| c.status == TeamHealthStatus::Up) {
+                    Some(TeamHealthStatus::Up)
+                } else {
+                    Some(TeamHealthStatus::Down)
+                };
+
+                Some(TeamHealthSummary {
+                    team_id: team.id,
+                    team_index: team.index,
+                    team_slug: team.slug,
+                    team_name: team.name,
+                    status,
+                    checks,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let count = |status| teams.iter().filter(|t| t.status == status).count();
+
+        Ok(EventHealthOverview {
+            event_id,
+            teams_up: count(Some(TeamHealthStatus::Up)),
+            teams_down: count(Some(TeamHealthStatus::Down)),
+            teams_unknown: count(None),
+            teams,
+        })
+    }
 
-        Some(service)
+    fn require<'a, T>(config: Option<&'a T>, name: &str) -> ServiceResult<&'a T> {
+        config.ok_or_else(|| ServiceError::DependencyMissing {
+            dependency: format!("InfrastructureService.config.{name}"),
+        })
     }
 }
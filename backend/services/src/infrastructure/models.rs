@@ -1,6 +1,9 @@
+use chrono::NaiveDateTime;
+use hackathon_portal_repositories::db::{db_team_health_check, TeamHealthStatus};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema, Default)]
 pub enum AccessControlMode {
@@ -10,6 +13,16 @@ pub enum AccessControlMode {
     None,
 }
 
+impl AccessControlMode {
+    #[must_use]
+    pub const fn requires_authentication(&self) -> bool {
+        matches!(
+            self,
+            Self::AuthenticationAuthorization | Self::Authentication
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema, Default)]
 pub enum ServerProtocol {
     #[default]
@@ -19,16 +32,140 @@ pub enum ServerProtocol {
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[serde(deny_unknown_fields)]
-pub struct ManagedIngressConfig {
+pub struct IngressRoute {
+    /// Unique name of the route within the team, e.g. `frontend` or `api`
+    pub name: String,
+    /// Subdomain of the team's managed address, e.g. `api` for `api.team-01.example.com`
+    pub subdomain: Option<String>,
+    /// Restricts the route to requests whose path starts with the prefix, e.g. `/api`
+    pub path_prefix: Option<String>,
     pub server_port: u16,
     pub access_control_mode: AccessControlMode,
 }
 
+impl IngressRoute {
+    #[must_use]
+    pub fn host(&self, managed_address: &str) -> String {
+        match &self.subdomain {
+            Some(subdomain) => format!("{subdomain}.{managed_address}"),
+            None => managed_address.to_string(),
+        }
+    }
+
+    fn matches_path(&self, path: &str) -> bool {
+        let Some(prefix) = &self.path_prefix else {
+            return true;
+        };
+
+        let prefix = prefix.trim_end_matches('/');
+
+        path == prefix
+            || path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/') || rest.starts_with('?'))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let is_label = |value: &str| {
+            !value.is_empty()
+                && !value.starts_with('-')
+                && !value.ends_with('-')
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        };
+
+        if !is_label(&self.name) {
+            return Err(format!(
+                "route name '{}' must consist of lowercase letters, digits and dashes",
+                self.name
+            ));
+        }
+
+        if let Some(subdomain) = &self.subdomain {
+            if !subdomain.split('.').all(is_label) {
+                return Err(format!(
+                    "subdomain '{subdomain}' of route '{}' is not a valid domain name",
+                    self.name
+                ));
+            }
+        }
+
+        if let Some(path_prefix) = &self.path_prefix {
+            if !path_prefix.starts_with('/') || path_prefix.contains(['`', ' ', '?', '#']) {
+                return Err(format!(
+                    "path prefix '{path_prefix}' of route '{}' must be an absolute path",
+                    self.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ManagedIngressConfig {
+    pub routes: Vec<IngressRoute>,
+}
+
+impl ManagedIngressConfig {
+    /// Finds the route serving the given host and path. Routes with a longer path prefix take
+    /// precedence, just like Traefik prioritizes longer rules.
+    #[must_use]
+    pub fn resolve_route(
+        &self,
+        managed_address: &str,
+        host: &str,
+        path: &str,
+    ) -> Option<&IngressRoute> {
+        self.routes
+            .iter()
+            .filter(|route| route.host(managed_address) == host && route.matches_path(path))
+            .max_by_key(|route| route.path_prefix.as_ref().map_or(0, String::len))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.routes.is_empty() {
+            return Err("at least one route is required".to_string());
+        }
+
+        let mut names = HashSet::new();
+        let mut matchers = HashSet::new();
+
+        for route in &self.routes {
+            route.validate()?;
+
+            if !names.insert(&route.name) {
+                return Err(format!(
+                    "route name '{}' is used more than once",
+                    route.name
+                ));
+            }
+
+            if !matchers.insert((&route.subdomain, &route.path_prefix)) {
+                return Err(format!(
+                    "route '{}' has the same subdomain and path prefix as another route",
+                    route.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl Default for ManagedIngressConfig {
     fn default() -> Self {
         Self {
-            server_port: 8080,
-            access_control_mode: AccessControlMode::default(),
+            routes: vec![IngressRoute {
+                name: "default".to_string(),
+                subdomain: None,
+                path_prefix: None,
+                server_port: 8080,
+                access_control_mode: AccessControlMode::default(),
+            }],
         }
     }
 }
@@ -62,8 +199,40 @@ impl Default for IngressMode {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+/// Version 1 only supported a single port per team. It is migrated to a single route when read.
+#[derive(Deserialize)]
 #[serde(deny_unknown_fields)]
+struct ManagedIngressConfigV1 {
+    server_port: u16,
+    access_control_mode: AccessControlMode,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "mode", content = "config", deny_unknown_fields)]
+enum IngressModeV1 {
+    Managed(ManagedIngressConfigV1),
+    Custom(CustomIngressConfig),
+}
+
+impl From<IngressModeV1> for IngressMode {
+    fn from(value: IngressModeV1) -> Self {
+        match value {
+            IngressModeV1::Managed(config) => Self::Managed(ManagedIngressConfig {
+                routes: vec![IngressRoute {
+                    name: "default".to_string(),
+                    subdomain: None,
+                    path_prefix: None,
+                    server_port: config.server_port,
+                    access_control_mode: config.access_control_mode,
+                }],
+            }),
+            IngressModeV1::Custom(config) => Self::Custom(config),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(try_from = "serde_json::Value")]
 pub struct IngressConfig {
     pub version: u32,
 
@@ -72,6 +241,8 @@ pub struct IngressConfig {
 }
 
 impl IngressConfig {
+    pub const VERSION: u32 = 2;
+
     #[must_use]
     pub fn assemble_url(
         &self,
@@ -79,7 +250,17 @@ impl IngressConfig {
         direct_address: Option<&str>,
     ) -> Option<String> {
         match &self.mode {
-            IngressMode::Managed(_) => managed_address.map(|address| format!("https://{address}")),
+            IngressMode::Managed(config) => {
+                let route = config.routes.first()?;
+
+                managed_address.map(|address| {
+                    format!(
+                        "https://{}{}",
+                        route.host(address),
+                        route.path_prefix.as_deref().unwrap_or_default()
+                    )
+                })
+            }
             IngressMode::Custom(config) => {
                 let protocol = match config.server_protocol {
                     ServerProtocol::Http => "http",
@@ -93,15 +274,127 @@ impl IngressConfig {
     }
 }
 
+impl TryFrom<serde_json::Value> for IngressConfig {
+    type Error = serde_json::Error;
+
+    fn try_from(mut value: serde_json::Value) -> Result<Self, Self::Error> {
+        let version = value
+            .as_object_mut()
+            .and_then(|object| object.remove("version"))
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| serde::de::Error::missing_field("version"))?;
+
+        let mode = match version {
+            1 => serde_json::from_value::<IngressModeV1>(value)?.into(),
+            2 => serde_json::from_value::<IngressMode>(value)?,
+            _ => {
+                return Err(serde::de::Error::custom(format!(
+                    "unsupported ingress config version {version}"
+                )))
+            }
+        };
+
+        if let IngressMode::Managed(config) = &mode {
+            config.validate().map_err(serde::de::Error::custom)?;
+        }
+
+        Ok(Self {
+            version: Self::VERSION,
+            mode,
+        })
+    }
+}
+
 impl Default for IngressConfig {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: Self::VERSION,
             mode: IngressMode::default(),
         }
     }
 }
 
+/// Team server endpoint probed by the health worker
+#[derive(Debug, Clone)]
+pub struct TeamHealthProbeTarget {
+    pub team_id: Uuid,
+    pub port: u16,
+    pub url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TeamHealthProbeResult {
+    pub target: TeamHealthProbeTarget,
+    pub status: TeamHealthStatus,
+    pub status_code: Option<u16>,
+    pub latency_ms: Option<u32>,
+    pub error: Option<String>,
+    pub checked_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamHealthCheck {
+    pub team_id: Uuid,
+    pub port: u16,
+    pub url: String,
+    pub status: TeamHealthStatus,
+    pub status_code: Option<u16>,
+    pub latency_ms: Option<u32>,
+    pub error: Option<String>,
+    pub checked_at: NaiveDateTime,
+    pub status_changed_at: NaiveDateTime,
+}
+
+impl From<db_team_health_check::Model> for TeamHealthCheck {
+    fn from(value: db_team_health_check::Model) -> Self {
+        Self {
+            team_id: value.team_id,
+            port: value.port as u16,
+            url: value.url,
+            status: value.status,
+            status_code: value.status_code.map(|c| c as u16),
+            latency_ms: value.latency_ms.map(|l| l as u32),
+            error: value.error,
+            checked_at: value.checked_at,
+            status_changed_at: value.status_changed_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamHealthSummary {
+    pub team_id: Uuid,
+    pub team_index: i32,
+    pub team_slug: String,
+    pub team_name: String,
+    /// `Up` if all endpoints are up, `None` if the team has not been probed yet
+    pub status: Option<TeamHealthStatus>,
+    pub checks: Vec<TeamHealthCheck>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct EventHealthOverview {
+    pub event_id: Uuid,
+    pub teams_up: usize,
+    pub teams_down: usize,
+    pub teams_unknown: usize,
+    pub teams: Vec<TeamHealthSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum ProxySkipReason {
+    MissingManagedAddress,
+    MissingPrivateAddress,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxySkippedTeam {
+    pub team_id: Uuid,
+    pub team_slug: String,
+    pub reason: ProxySkipReason,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TraefikDynamicConfig {
@@ -116,12 +409,14 @@ pub struct TraefikHttpConfig {
     pub routers: HashMap<String, TraefikRouterConfig>,
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub services: HashMap<String, TraefikServiceConfig>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub servers_transports: HashMap<String, TraefikServersTransportConfig>,
 }
 
 impl TraefikHttpConfig {
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.routers.is_empty() && self.services.is_empty()
+        self.routers.is_empty() && self.services.is_empty() && self.servers_transports.is_empty()
     }
 }
 
@@ -147,6 +442,8 @@ pub struct TraefikServiceConfig {
 pub struct TraefikLoadBalancerConfig {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<TraefikServerConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers_transport: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
@@ -155,6 +452,12 @@ pub struct TraefikServerConfig {
     pub url: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TraefikServersTransportConfig {
+    pub insecure_skip_verify: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,4 +470,91 @@ mod tests {
             serde_json::to_string(&IngressConfig::default()).unwrap()
         );
     }
+
+    #[test]
+    fn migrate_v1_ingress_config() -> serde_json::Result<()> {
+        let config = serde_json::from_value::<IngressConfig>(serde_json::json!({
+            "version": 1,
+            "mode": "Managed",
+            "config": { "server_port": 3000, "access_control_mode": "Authentication" },
+        }))?;
+
+        assert_eq!(config.version, IngressConfig::VERSION);
+
+        let IngressMode::Managed(managed) = config.mode else {
+            panic!("expected managed ingress mode");
+        };
+
+        assert_eq!(managed.routes.len(), 1);
+        assert_eq!(managed.routes[0].server_port, 3000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_routes() {
+        let config = |routes: serde_json::Value| {
+            serde_json::from_value::<IngressConfig>(serde_json::json!({
+                "version": 2,
+                "mode": "Managed",
+                "config": { "routes": routes },
+            }))
+        };
+
+        let route = |name: &str, subdomain: Option<&str>, path_prefix: Option<&str>| {
+            serde_json::json!({
+                "name": name,
+                "subdomain": subdomain,
+                "path_prefix": path_prefix,
+                "server_port": 8080,
+                "access_control_mode": "None",
+            })
+        };
+
+        assert!(config(serde_json::json!([])).is_err());
+        assert!(config(serde_json::json!([route("Web", None, None)])).is_err());
+        assert!(config(serde_json::json!([route("web", None, Some("api"))])).is_err());
+        assert!(config(serde_json::json!([
+            route("web", None, None),
+            route("api", None, None)
+        ]))
+        .is_err());
+        assert!(config(serde_json::json!([
+            route("web", None, None),
+            route("api", Some("api"), None),
+            route("docs", None, Some("/docs"))
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn resolve_longest_path_prefix() {
+        let route = |name: &str, subdomain: Option<&str>, path_prefix: Option<&str>| IngressRoute {
+            name: name.to_string(),
+            subdomain: subdomain.map(str::to_string),
+            path_prefix: path_prefix.map(str::to_string),
+            server_port: 8080,
+            access_control_mode: AccessControlMode::None,
+        };
+
+        let config = ManagedIngressConfig {
+            routes: vec![
+                route("web", None, None),
+                route("api", None, Some("/api")),
+                route("admin", Some("admin"), None),
+            ],
+        };
+
+        let resolve = |host: &str, path: &str| {
+            config
+                .resolve_route("team.example.com", host, path)
+                .map(|route| route.name.as_str())
+        };
+
+        assert_eq!(resolve("team.example.com", "/"), Some("web"));
+        assert_eq!(resolve("team.example.com", "/api/users"), Some("api"));
+        assert_eq!(resolve("team.example.com", "/apis"), Some("web"));
+        assert_eq!(resolve("admin.team.example.com", "/api"), Some("admin"));
+        assert_eq!(resolve("other.example.com", "/"), None);
+    }
 }
@@ -0,0 +1,161 @@
+use crate::infrastructure::models::ServerProtocol;
+use crate::infrastructure::proxy::{
+    ProxyConfigRenderer, ProxyRoute, ProxyRoutes, FORWARD_AUTH_HEADERS,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaddyConfig {
+    #[serde(default = "CaddyConfig::default_server_name")]
+    pub server_name: String,
+    #[serde(default = "CaddyConfig::default_listen")]
+    pub listen: Vec<String>,
+    /// Address of the portal's management API, e.g. `portal-backend:8001`
+    pub auth_upstream: String,
+    #[serde(default = "CaddyConfig::default_auth_uri")]
+    pub auth_uri: String,
+}
+
+impl CaddyConfig {
+    #[must_use]
+    pub fn default_server_name() -> String {
+        "teams".to_string()
+    }
+
+    #[must_use]
+    pub fn default_listen() -> Vec<String> {
+        vec![":443".to_string()]
+    }
+
+    #[must_use]
+    pub fn default_auth_uri() -> String {
+        "/api/auth/authorization".to_string()
+    }
+
+    /// Equivalent of Caddy's `forward_auth` directive, which only exists in the Caddyfile.
+    fn forward_auth_handler(&self) -> Value {
+        let copy_headers = FORWARD_AUTH_HEADERS
+            .iter()
+            .map(|header| {
+                (
+                    (*header).to_string(),
+                    json!([format!("{{http.reverse_proxy.header.{header}}}")]),
+                )
+            })
+            .collect::<Map<_, _>>();
+
+        json!({
+            "handler": "reverse_proxy",
+            "upstreams": [{ "dial": self.auth_upstream }],
+            "rewrite": { "method": "GET", "uri": self.auth_uri },
+            "headers": {
+                "request": {
+                    "set": {
+                        "X-Forwarded-Method": ["{http.request.method}"],
+                        "X-Forwarded-Uri": ["{http.request.uri}"],
+                    },
+                },
+            },
+            "handle_response": [{
+                "match": { "status_code": [2] },
+                "routes": [{
+                    "handle": [{
+                        "handler": "headers",
+                        "request": { "set": copy_headers },
+                    }],
+                }],
+            }],
+        })
+    }
+
+    fn route(&self, route: &ProxyRoute, https_insecure_skip_verify: bool) -> Value {
+        let mut matcher = json!({ "host": [route.host] });
+
+        if let Some(path_prefix) = &route.path_prefix {
+            matcher["path"] = json!([format!("{path_prefix}*")]);
+        }
+
+        let mut proxy_handler = json!({
+            "handler": "reverse_proxy",
+            "upstreams": [{ "dial": route.upstream }],
+        });
+
+        if matches!(route.protocol, ServerProtocol::Https) {
+            proxy_handler["transport"] = json!({
+                "protocol": "http",
+                "tls": { "insecure_skip_verify": https_insecure_skip_verify },
+            });
+        }
+
+        let mut handlers = Vec::new();
+
+        if route.authenticate {
+            handlers.push(self.forward_auth_handler());
+        }
+
+        handlers.push(proxy_handler);
+
+        json!({
+            "@id": route.key,
+            "match": [matcher],
+            "handle": handlers,
+            "terminal": true,
+        })
+    }
+}
+
+impl ProxyConfigRenderer for CaddyConfig {
+    type Output = Value;
+
+    fn render(&self, proxy_routes: &ProxyRoutes) -> Self::Output {
+        // Caddy evaluates routes in order, so more specific path prefixes have to come first
+        let caddy_routes = proxy_routes
+            .sorted()
+            .into_iter()
+            .map(|route| self.route(route, proxy_routes.https_insecure_skip_verify))
+            .collect::<Vec<_>>();
+
+        json!({
+            "apps": {
+                "http": {
+                    "servers": {
+                        self.server_name.clone(): {
+                            "listen": self.listen,
+                            "routes": caddy_routes,
+                        },
+                    },
+                },
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::proxy::tests::routes;
+
+    #[test]
+    fn render_routes() {
+        let config = CaddyConfig {
+            server_name: CaddyConfig::default_server_name(),
+            listen: CaddyConfig::default_listen(),
+            auth_upstream: "portal-backend:8001".to_string(),
+            auth_uri: CaddyConfig::default_auth_uri(),
+        };
+
+        let rendered = config.render(&routes());
+        let caddy_routes = &rendered["apps"]["http"]["servers"]["teams"]["routes"];
+
+        assert_eq!(caddy_routes[0]["@id"], "team-01-api");
+        assert_eq!(caddy_routes[0]["match"][0]["path"], json!(["/api*"]));
+        assert_eq!(caddy_routes[0]["handle"].as_array().map(Vec::len), Some(1));
+        assert_eq!(
+            caddy_routes[0]["handle"][0]["transport"]["tls"]["insecure_skip_verify"],
+            true
+        );
+        assert_eq!(caddy_routes[1]["handle"].as_array().map(Vec::len), Some(2));
+    }
+}
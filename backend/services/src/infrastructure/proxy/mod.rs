@@ -0,0 +1,258 @@
+mod caddy;
+mod nginx;
+mod traefik;
+
+pub use caddy::CaddyConfig;
+pub use nginx::NginxConfig;
+pub use traefik::TraefikConfig;
+
+use crate::infrastructure::models::{
+    IngressMode, ProxySkipReason, ProxySkippedTeam, ServerProtocol,
+};
+use crate::team::models::Team;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Headers set by the portal's forward authentication endpoint, passed on to team servers.
+pub const FORWARD_AUTH_HEADERS: [&str; 6] = [
+    "X-User-Id",
+    "X-User-Name",
+    "X-Team-Id",
+    "X-Team-Slug",
+    "X-Event-Roles",
+    "X-Team-Roles",
+];
+
+/// Route from a public host to a team server, independent of the reverse proxy serving it.
+#[derive(Debug, Clone)]
+pub struct ProxyRoute {
+    /// Unique name of the route, used to name the objects in the generated config
+    pub key: String,
+    pub team_id: Uuid,
+    pub host: String,
+    pub path_prefix: Option<String>,
+    pub protocol: ServerProtocol,
+    /// Address and port of the team server, e.g. `10.0.0.7:8080`
+    pub upstream: String,
+    /// Requests have to pass the portal's forward authentication first
+    pub authenticate: bool,
+}
+
+impl ProxyRoute {
+    #[must_use]
+    pub fn upstream_url(&self) -> String {
+        let scheme = match self.protocol {
+            ServerProtocol::Http => "http",
+            ServerProtocol::Https => "https",
+        };
+
+        format!("{scheme}://{}", self.upstream)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProxyRoutes {
+    pub routes: Vec<ProxyRoute>,
+    pub skipped_teams: Vec<ProxySkippedTeam>,
+    /// Skip certificate verification of HTTPS upstreams. Team servers usually present
+    /// self-signed certificates.
+    pub https_insecure_skip_verify: bool,
+}
+
+impl ProxyRoutes {
+    /// Collects the routes of all teams with ingress enabled.
+    #[must_use]
+    pub fn from_teams(teams: &[Team], https_insecure_skip_verify: bool) -> Self {
+        let mut routes = Vec::new();
+        let mut skipped_teams = Vec::new();
+
+        for team in teams.iter().filter(|team| team.ingress_enabled) {
+            match Self::get_team_routes(team) {
+                Ok(team_routes) => routes.extend(team_routes),
+                Err(reason) => {
+                    warn!(team = ?team.id, reason = ?reason, "Team has ingress enabled but cannot be routed, skipping");
+
+                    skipped_teams.push(ProxySkippedTeam {
+                        team_id: team.id,
+                        team_slug: team.slug.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        Self {
+            routes,
+            skipped_teams,
+            https_insecure_skip_verify,
+        }
+    }
+
+    fn get_team_routes(team: &Team) -> Result<Vec<ProxyRoute>, ProxySkipReason> {
+        let Some(managed_address) = team.managed_address.as_deref() else {
+            return Err(ProxySkipReason::MissingManagedAddress);
+        };
+
+        let Some(private_address) = team.private_address.as_deref() else {
+            return Err(ProxySkipReason::MissingPrivateAddress);
+        };
+
+        let key = format!("team-{}-{}", team.slug, team.id);
+
+        let routes = match &team.ingress_config.mode {
+            IngressMode::Managed(ingress_config) => ingress_config
+                .routes
+                .iter()
+                .map(|route| ProxyRoute {
+                    key: format!("{key}-{}", route.name),
+                    team_id: team.id,
+                    host: route.host(managed_address),
+                    path_prefix: route.path_prefix.clone(),
+                    protocol: ServerProtocol::Http,
+                    upstream: format!("{private_address}:{}", route.server_port),
+                    authenticate: route.access_control_mode.requires_authentication(),
+                })
+                .collect(),
+            // Teams in custom mode handle access control themselves
+            IngressMode::Custom(ingress_config) => vec![ProxyRoute {
+                key,
+                team_id: team.id,
+                host: managed_address.to_string(),
+                path_prefix: None,
+                protocol: ingress_config.server_protocol.clone(),
+                upstream: format!("{private_address}:{}", ingress_config.server_port),
+                authenticate: false,
+            }],
+        };
+
+        Ok(routes)
+    }
+
+    /// Routes ordered by host, more specific path prefixes first. Required by proxies that
+    /// match routes in order.
+    #[must_use]
+    pub fn sorted(&self) -> Vec<&ProxyRoute> {
+        let mut routes = self.routes.iter().collect::<Vec<_>>();
+
+        routes.sort_by(|a, b| {
+            let prefix_len = |route: &ProxyRoute| route.path_prefix.as_ref().map_or(0, String::len);
+
+            a.host
+                .cmp(&b.host)
+                .then_with(|| prefix_len(b).cmp(&prefix_len(a)))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+
+        routes
+    }
+}
+
+/// Renders the team routes into the configuration format of a reverse proxy.
+pub trait ProxyConfigRenderer {
+    type Output;
+
+    fn render(&self, proxy_routes: &ProxyRoutes) -> Self::Output;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::models::IngressConfig;
+
+    pub(super) fn routes() -> ProxyRoutes {
+        let route = |key: &str, path_prefix: Option<&str>, protocol, authenticate| ProxyRoute {
+            key: key.to_string(),
+            team_id: Uuid::nil(),
+            host: "team-01.example.com".to_string(),
+            path_prefix: path_prefix.map(str::to_string),
+            protocol,
+            upstream: "10.0.0.1:8080".to_string(),
+            authenticate,
+        };
+
+        ProxyRoutes {
+            routes: vec![
+                route("team-01-web", None, ServerProtocol::Http, true),
+                route("team-01-api", Some("/api"), ServerProtocol::Https, false),
+            ],
+            skipped_teams: vec![],
+            https_insecure_skip_verify: true,
+        }
+    }
+
+    #[test]
+    fn sort_specific_prefixes_first() {
+        let routes = routes();
+
+        let keys = routes
+            .sorted()
+            .into_iter()
+            .map(|route| route.key.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(keys, vec!["team-01-api", "team-01-web"]);
+    }
+
+    #[test]
+    fn skip_teams_without_addresses() {
+        let team =
+            |slug: &str, managed_address: Option<&str>, private_address: Option<&str>| Team {
+                id: Uuid::new_v4(),
+                event_id: Uuid::nil(),
+                project_id: None,
+                name: slug.to_string(),
+                slug: slug.to_string(),
+                index: 0,
+                photo_id: None,
+                photo_url: None,
+                password: None,
+                previous_password: None,
+                ssh_public_key: None,
+                ssh_private_key: None,
+                previous_ssh_public_key: None,
+                previous_ssh_private_key: None,
+                ai_api_key: None,
+                extra_score: None,
+                comment: None,
+                managed_address: managed_address.map(str::to_string),
+                managed_address_override: None,
+                direct_address: None,
+                direct_address_override: None,
+                private_address: private_address.map(str::to_string),
+                private_address_override: None,
+                ssh_config: None,
+                ssh_config_override: None,
+                ingress_enabled: true,
+                ingress_config: IngressConfig::default(),
+                ingress_url: None,
+                finalist: false,
+            };
+
+        let teams = vec![
+            team("team-01", Some("team-01.example.com"), Some("10.0.0.1")),
+            team("team-02", None, Some("10.0.0.2")),
+            team("team-03", Some("team-03.example.com"), None),
+        ];
+
+        let routes = ProxyRoutes::from_teams(&teams, false);
+
+        assert!(routes
+            .routes
+            .iter()
+            .all(|route| route.team_id == teams[0].id));
+
+        let skipped = routes
+            .skipped_teams
+            .iter()
+            .map(|skipped| (skipped.team_slug.as_str(), skipped.reason))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            skipped,
+            vec![
+                ("team-02", ProxySkipReason::MissingManagedAddress),
+                ("team-03", ProxySkipReason::MissingPrivateAddress),
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::infrastructure::models::ServerProtocol;
+use crate::infrastructure::proxy::{
+    ProxyConfigRenderer, ProxyRoute, ProxyRoutes, FORWARD_AUTH_HEADERS,
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Location used for the `auth_request` subrequests, not reachable from outside.
+const AUTH_LOCATION: &str = "/_portal_auth";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NginxConfig {
+    #[serde(default = "NginxConfig::default_listen")]
+    pub listen: String,
+    /// Forward authentication endpoint of the portal, e.g.
+    /// `http://portal-backend:8001/api/auth/authorization`
+    pub auth_url: String,
+}
+
+impl NginxConfig {
+    #[must_use]
+    pub fn default_listen() -> String {
+        "80".to_string()
+    }
+
+    fn write_location(
+        out: &mut String,
+        route: &ProxyRoute,
+        https_insecure_skip_verify: bool,
+    ) -> std::fmt::Result {
+        let location = route.path_prefix.as_deref().unwrap_or("/");
+
+        writeln!(out, "    # {}", route.key)?;
+        writeln!(out, "    location {location} {{")?;
+
+        if route.authenticate {
+            writeln!(out, "        auth_request {AUTH_LOCATION};")?;
+
+            for header in FORWARD_AUTH_HEADERS {
+                let name = header.to_lowercase().replace('-', "_");
+
+                writeln!(
+                    out,
+                    "        auth_request_set $portal_{name} $upstream_http_{name};"
+                )?;
+                writeln!(out, "        proxy_set_header {header} $portal_{name};")?;
+            }
+        }
+
+        writeln!(out, "        proxy_pass {};", route.upstream_url())?;
+        writeln!(out, "        proxy_http_version 1.1;")?;
+        writeln!(out, "        proxy_set_header Host $host;")?;
+        writeln!(out, "        proxy_set_header Upgrade $http_upgrade;")?;
+        writeln!(
+            out,
+            "        proxy_set_header Connection $connection_upgrade;"
+        )?;
+        writeln!(
+            out,
+            "        proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;"
+        )?;
+        writeln!(out, "        proxy_set_header X-Forwarded-Proto $scheme;")?;
+
+        if matches!(route.protocol, ServerProtocol::Https) {
+            let verify = if https_insecure_skip_verify {
+                "off"
+            } else {
+                "on"
+            };
+
+            writeln!(out, "        proxy_ssl_verify {verify};")?;
+            writeln!(out, "        proxy_ssl_server_name on;")?;
+        }
+
+        writeln!(out, "    }}")?;
+
+        Ok(())
+    }
+
+    fn write_auth_location(&self, out: &mut String) -> std::fmt::Result {
+        writeln!(out, "    location = {AUTH_LOCATION} {{")?;
+        writeln!(out, "        internal;")?;
+        writeln!(out, "        proxy_pass {};", self.auth_url)?;
+        writeln!(out, "        proxy_pass_request_body off;")?;
+        writeln!(out, "        proxy_set_header Content-Length \"\";")?;
+        writeln!(out, "        proxy_set_header X-Forwarded-Host $host;")?;
+        writeln!(
+            out,
+            "        proxy_set_header X-Forwarded-Uri $request_uri;"
+        )?;
+        writeln!(
+            out,
+            "        proxy_set_header X-Forwarded-Method $request_method;"
+        )?;
+        writeln!(out, "    }}")?;
+
+        Ok(())
+    }
+
+    fn write_config(&self, out: &mut String, routes: &ProxyRoutes) -> std::fmt::Result {
+        writeln!(
+            out,
+            "# Generated by the hackathon portal, do not edit manually"
+        )?;
+
+        for skipped in &routes.skipped_teams {
+            writeln!(
+                out,
+                "# Skipped team {} ({}): {:?}",
+                skipped.team_slug, skipped.team_id, skipped.reason
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "map $http_upgrade $connection_upgrade {{")?;
+        writeln!(out, "    default upgrade;")?;
+        writeln!(out, "    '' close;")?;
+        writeln!(out, "}}")?;
+
+        for (host, host_routes) in &routes.sorted().into_iter().chunk_by(|route| &route.host) {
+            let host_routes = host_routes.collect::<Vec<_>>();
+
+            writeln!(out)?;
+            writeln!(out, "server {{")?;
+            writeln!(out, "    listen {};", self.listen)?;
+            writeln!(out, "    server_name {host};")?;
+
+            for route in &host_routes {
+                writeln!(out)?;
+                Self::write_location(out, route, routes.https_insecure_skip_verify)?;
+            }
+
+            if host_routes.iter().any(|route| route.authenticate) {
+                writeln!(out)?;
+                self.write_auth_location(out)?;
+            }
+
+            writeln!(out, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ProxyConfigRenderer for NginxConfig {
+    type Output = String;
+
+    fn render(&self, proxy_routes: &ProxyRoutes) -> Self::Output {
+        let mut out = String::new();
+
+        // Writing to a `String` cannot fail
+        let _ = self.write_config(&mut out, proxy_routes);
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::proxy::tests::routes;
+
+    #[test]
+    fn render_server_blocks() {
+        let config = NginxConfig {
+            listen: NginxConfig::default_listen(),
+            auth_url: "http://portal-backend:8001/api/auth/authorization".to_string(),
+        };
+
+        let rendered = config.render(&routes());
+
+        assert_eq!(rendered.matches("server {").count(), 1);
+        assert!(rendered.contains("server_name team-01.example.com;"));
+
+        let api = rendered.find("location /api {");
+        let root = rendered.find("location / {");
+
+        assert!(api.is_some() && root.is_some() && api < root);
+        assert!(rendered.contains("proxy_pass https://10.0.0.1:8080;"));
+        assert!(rendered.contains("proxy_ssl_verify off;"));
+        assert_eq!(rendered.matches("auth_request /_portal_auth;").count(), 1);
+        assert!(rendered.contains("proxy_set_header X-Team-Slug $portal_x_team_slug;"));
+        assert!(rendered.contains("location = /_portal_auth {"));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::infrastructure::models::{
+    ServerProtocol, TraefikDynamicConfig, TraefikHttpConfig, TraefikLoadBalancerConfig,
+    TraefikRouterConfig, TraefikServerConfig, TraefikServersTransportConfig, TraefikServiceConfig,
+};
+use crate::infrastructure::proxy::{ProxyConfigRenderer, ProxyRoutes};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TraefikConfig {
+    #[serde(default = "TraefikConfig::default_entrypoints")]
+    pub entrypoints: Vec<String>,
+    pub auth_middlewares: Vec<String>,
+    #[serde(default = "TraefikConfig::default_default_middlewares")]
+    pub default_middlewares: Vec<String>, // applies after auth middlewares
+}
+
+impl TraefikConfig {
+    #[must_use]
+    pub fn default_entrypoints() -> Vec<String> {
+        vec!["web".to_string()]
+    }
+
+    #[must_use]
+    pub fn default_default_middlewares() -> Vec<String> {
+        vec![]
+    }
+}
+
+impl ProxyConfigRenderer for TraefikConfig {
+    type Output = TraefikDynamicConfig;
+
+    fn render(&self, proxy_routes: &ProxyRoutes) -> Self::Output {
+        let mut routers = HashMap::new();
+        let mut services = HashMap::new();
+        let mut servers_transports = HashMap::new();
+
+        for route in &proxy_routes.routes {
+            let mut rule = format!(
+                "Host(`{host}`) || Header(`X-Forwarded-Host`, `{host}`)",
+                host = route.host
+            );
+
+            if let Some(path_prefix) = &route.path_prefix {
+                rule = format!("({rule}) && PathPrefix(`{path_prefix}`)");
+            }
+
+            let mut middlewares = if route.authenticate {
+                self.auth_middlewares.clone()
+            } else {
+                vec![]
+            };
+
+            middlewares.extend(self.default_middlewares.clone());
+
+            let servers_transport = match route.protocol {
+                ServerProtocol::Http => None,
+                ServerProtocol::Https => {
+                    servers_transports.insert(
+                        route.key.clone(),
+                        TraefikServersTransportConfig {
+                            insecure_skip_verify: proxy_routes.https_insecure_skip_verify,
+                        },
+                    );
+
+                    Some(route.key.clone())
+                }
+            };
+
+            routers.insert(
+                route.key.clone(),
+                TraefikRouterConfig {
+                    rule,
+                    service: route.key.clone(),
+                    entry_points: self.entrypoints.clone(),
+                    middlewares,
+                },
+            );
+
+            services.insert(
+                route.key.clone(),
+                TraefikServiceConfig {
+                    load_balancer: TraefikLoadBalancerConfig {
+                        servers: vec![TraefikServerConfig {
+                            url: route.upstream_url(),
+                        }],
+                        servers_transport,
+                    },
+                },
+            );
+        }
+
+        TraefikDynamicConfig {
+            http: TraefikHttpConfig {
+                routers,
+                services,
+                servers_transports,
+            },
+        }
+    }
+}
@@ -1,11 +1,13 @@
 pub mod appointment;
 pub mod authorization;
 pub mod crypto;
+pub mod discord;
 pub mod error;
 pub mod event;
 pub mod health;
 pub mod infrastructure;
 pub mod logger;
+pub mod notification;
 pub mod project;
 pub mod rating;
 pub mod sidequest;
@@ -0,0 +1,62 @@
+use crate::ServiceResult;
+use chrono::Utc;
+use hackathon_portal_repositories::db::{db_discord_notification, DiscordNotificationRepository};
+use hackathon_portal_repositories::DbRepository;
+use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{Set, TryInsertResult};
+use std::collections::HashSet;
+
+/// Records sent Discord notifications so they are never sent twice, even across restarts.
+#[derive(Clone)]
+pub struct NotificationService {
+    db_repo: DbRepository,
+}
+
+impl NotificationService {
+    #[must_use]
+    pub const fn new(db_repo: DbRepository) -> Self {
+        Self { db_repo }
+    }
+
+    pub async fn get_sent_notifications(&self, event_id: Uuid) -> ServiceResult<HashSet<String>> {
+        let notifications =
+            DiscordNotificationRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id)
+                .await?;
+
+        Ok(notifications.into_iter().map(|n| n.key).collect())
+    }
+
+    /// Records a notification before it is sent, returns `false` if it was already recorded.
+    pub async fn claim_notification(&self, event_id: Uuid, key: &str) -> ServiceResult<bool> {
+        let notification = db_discord_notification::ActiveModel {
+            event_id: Set(event_id),
+            key: Set(key.to_string()),
+            sent_at: Set(Utc::now().naive_utc()),
+        };
+
+        let result = db_discord_notification::Entity::insert(notification)
+            .on_conflict(
+                OnConflict::columns([
+                    db_discord_notification::Column::EventId,
+                    db_discord_notification::Column::Key,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .do_nothing()
+            .exec(self.db_repo.conn())
+            .await?;
+
+        Ok(matches!(result, TryInsertResult::Inserted(_)))
+    }
+
+    /// Removes the record of a notification that could not be sent, so it is retried.
+    pub async fn release_notification(&self, event_id: Uuid, key: &str) -> ServiceResult<()> {
+        db_discord_notification::Entity::delete_by_id((event_id, key.to_string()))
+            .exec(self.db_repo.conn())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,125 +1,588 @@
-use minilp::{ComparisonOp, OptimizationDirection, Problem, Solution, Variable};
+use crate::project::models::{
+    MatchingDiagnostics, MatchingInputs, MatchingObjective, MatchingProject, MatchingTeam,
+    ProjectDemand,
+};
 use uuid::Uuid;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 type TeamId = Uuid;
 type ProjectId = Uuid;
-type Preference = HashMap<TeamId, ProjectPref>;
-type ProjectPref = HashMap<ProjectId, i32>;
-
-#[derive(Clone, Copy)]
-pub struct AssignmentVariable {
-    team: Uuid,
-    project: Uuid,
-    variable: Variable,
-}
 
+/// Assigns every team to exactly one project by solving a min-cost flow problem.
+///
+/// The network is `source -> team -> project -> sink`. Each project has two edges to the sink:
+/// one with capacity `min_teams` and a large negative cost, which forces minimums to be filled
+/// first, and one with the remaining capacity at no cost. Since all capacities are integral,
+/// the resulting assignment is integral as well.
+///
+/// Teams without preferences are steered towards the least requested projects, so they do not
+/// take places from teams that asked for a popular project.
 pub struct GroupAssignment {
-    teams: Vec<Uuid>,
-    project: Vec<Uuid>,
-    mps: i32,
-    problem: Problem,
-    variables: Vec<AssignmentVariable>,
-    preferences: Preference,
+    teams: Vec<MatchingTeam>,
+    projects: Vec<MatchingProject>,
+    objective: MatchingObjective,
 }
 
 impl GroupAssignment {
-    pub fn new(
-        teams: Vec<Uuid>,
-        project: Vec<Uuid>,
-        max_project_size: i32,
-        preferences: Preference,
-    ) -> Option<Self> {
-        if teams.len() > (project.len() * max_project_size as usize) {
+    #[must_use]
+    pub const fn new(
+        teams: Vec<MatchingTeam>,
+        projects: Vec<MatchingProject>,
+        objective: MatchingObjective,
+    ) -> Self {
+        Self {
+            teams,
+            projects,
+            objective,
+        }
+    }
+
+    /// Rank of the project in the team's preferences, unlisted projects rank last.
+    fn rank(&self, team: &MatchingTeam, project: ProjectId) -> usize {
+        team.preferences
+            .iter()
+            .position(|p| *p == project)
+            .unwrap_or(self.projects.len())
+    }
+
+    /// Position of the project when ordered by how many teams list it in their preferences,
+    /// least requested first.
+    fn demand_ranks(&self) -> HashMap<ProjectId, usize> {
+        let mut projects = self
+            .projects
+            .iter()
+            .map(|project| {
+                let requests = self
+                    .teams
+                    .iter()
+                    .filter(|t| t.preferences.contains(&project.id))
+                    .count();
+
+                (project.id, requests)
+            })
+            .collect::<Vec<_>>();
+
+        projects.sort_by_key(|(_, requests)| *requests);
+
+        projects
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (id, _))| (id, rank))
+            .collect()
+    }
+
+    /// Returns `None` if no assignment satisfies all constraints.
+    #[must_use]
+    pub fn solve(&self) -> Option<HashMap<TeamId, ProjectId>> {
+        let min_total = self.projects.iter().map(|p| p.min_teams).sum::<usize>();
+        let max_total = self.projects.iter().map(|p| p.max_teams).sum::<usize>();
+
+        if min_total > self.teams.len() || max_total < self.teams.len() {
             return None;
         }
-        let problem = Problem::new(OptimizationDirection::Minimize);
-        let variables = Vec::<AssignmentVariable>::new();
-        let mut res = Self {
-            teams,
-            project,
-            mps: max_project_size,
-            problem,
-            variables,
-            preferences,
-        };
-        res.create_variables();
-        res.create_project_constraints();
-        res.create_team_constraint();
-        Some(res)
-    }
-
-    fn create_variables(&mut self) {
-        for t in &self.teams {
-            for p in &self.project {
-                let score = self.preferences.get(t);
-                let coeff = match score {
-                    None => self.project.len() as i32,
-                    Some(perf) => *perf.get(p).unwrap_or(&(self.project.len() as i32)),
+
+        if self.projects.iter().any(|p| p.min_teams > p.max_teams) {
+            return None;
+        }
+
+        match self.objective {
+            MatchingObjective::TotalRank => self.solve_with_rank_limit(self.projects.len()),
+            // The smallest feasible rank limit is the best possible worst-off rank. Among all
+            // assignments respecting it, the one with the lowest total rank is returned.
+            MatchingObjective::WorstRank => {
+                (0..=self.projects.len()).find_map(|limit| self.solve_with_rank_limit(limit))
+            }
+        }
+    }
+
+    /// Only considers projects up to the given rank for teams with preferences.
+    /// Pinned teams and teams without preferences are not restricted.
+    fn solve_with_rank_limit(&self, rank_limit: usize) -> Option<HashMap<TeamId, ProjectId>> {
+        let source = 0;
+        let team_offset = 1;
+        let project_offset = team_offset + self.teams.len();
+        let sink = project_offset + self.projects.len();
+
+        let project_indices = self
+            .projects
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.id, i))
+            .collect::<HashMap<_, _>>();
+
+        // Preference ranks are scaled to outweigh the summed demand ranks of all teams without
+        // preferences, which only break ties between otherwise equal assignments
+        let demand_ranks = self.demand_ranks();
+        let teams_without_preferences = self
+            .teams
+            .iter()
+            .filter(|t| t.preferences.is_empty())
+            .count();
+        let rank_scale = (teams_without_preferences * self.projects.len() + 1) as i64;
+
+        // Exceeds the cost of any assignment, so filling minimums always takes priority
+        let min_bonus = ((self.teams.len() + 1) * (self.projects.len() + 1)) as i64 * rank_scale;
+
+        let mut flow = MinCostFlow::new(sink + 1);
+        let mut assignment_edges = Vec::new();
+
+        for (t, team) in self.teams.iter().enumerate() {
+            flow.add_edge(source, team_offset + t, 1, 0);
+
+            if let Some(pinned) = team.pinned {
+                let p = *project_indices.get(&pinned)?;
+                let edge = flow.add_edge(team_offset + t, project_offset + p, 1, 0);
+                assignment_edges.push((edge, team.id, pinned));
+                continue;
+            }
+
+            for (p, project) in self.projects.iter().enumerate() {
+                let rank = self.rank(team, project.id);
+
+                let cost = if team.preferences.is_empty() {
+                    demand_ranks[&project.id] as i64
+                } else if rank > rank_limit {
+                    continue;
+                } else {
+                    rank as i64 * rank_scale
                 };
 
-                let variable = self.problem.add_var(f64::from(coeff), (0.0, 1.0));
-                self.variables.push(AssignmentVariable {
-                    team: *t,
-                    project: *p,
-                    variable,
-                });
+                let edge = flow.add_edge(team_offset + t, project_offset + p, 1, cost);
+                assignment_edges.push((edge, team.id, project.id));
+            }
+        }
+
+        let mut min_edges = Vec::new();
+
+        for (p, project) in self.projects.iter().enumerate() {
+            if project.min_teams > 0 {
+                let edge = flow.add_edge(
+                    project_offset + p,
+                    sink,
+                    project.min_teams as i64,
+                    -min_bonus,
+                );
+                min_edges.push((edge, project.min_teams as i64));
             }
+
+            if project.max_teams > project.min_teams {
+                flow.add_edge(
+                    project_offset + p,
+                    sink,
+                    (project.max_teams - project.min_teams) as i64,
+                    0,
+                );
+            }
+        }
+
+        let total_flow = flow.run(source, sink);
+
+        if total_flow != self.teams.len() as i64 {
+            return None;
+        }
+
+        if min_edges
+            .iter()
+            .any(|(edge, min_teams)| flow.flow(*edge) != *min_teams)
+        {
+            return None;
         }
+
+        let solution = assignment_edges
+            .into_iter()
+            .filter(|(edge, _, _)| flow.flow(*edge) > 0)
+            .map(|(_, team, project)| (team, project))
+            .collect();
+
+        Some(solution)
     }
-    fn create_project_constraints(&mut self) {
-        for p in &self.project {
-            let vars = self
-                .variables
-                .clone()
-                .into_iter()
-                .filter_map(|var| {
-                    if var.project == *p {
-                        Some((var.variable, 1.0))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            self.problem
-                .add_constraint(&vars, ComparisonOp::Le, f64::from(self.mps));
-            self.problem.add_constraint(&vars, ComparisonOp::Ge, 0.0);
+}
+
+/// Collects hints on why a matching problem is (in)feasible and how well the solution fits.
+pub fn diagnose(
+    inputs: &MatchingInputs,
+    solution: Option<&HashMap<TeamId, ProjectId>>,
+) -> MatchingDiagnostics {
+    let team_count = inputs.teams.len();
+    let capacity = inputs.projects.iter().map(|p| p.max_teams).sum::<usize>();
+    let required = inputs.projects.iter().map(|p| p.min_teams).sum::<usize>();
+
+    let mut issues = Vec::new();
+
+    if team_count > capacity {
+        issues.push(format!(
+            "{team_count} teams exceed the total capacity of {capacity} teams over {} projects",
+            inputs.projects.len()
+        ));
+    }
+
+    if required > team_count {
+        issues.push(format!(
+            "projects require at least {required} teams in total, but there are only {team_count} teams"
+        ));
+    }
+
+    let mut demands = inputs
+        .projects
+        .iter()
+        .map(|project| {
+            let first_choices = inputs
+                .teams
+                .iter()
+                .filter(|t| t.preferences.first() == Some(&project.id))
+                .count();
+            let preferences = inputs
+                .teams
+                .iter()
+                .filter(|t| t.preferences.contains(&project.id))
+                .count();
+            let pinned = inputs
+                .teams
+                .iter()
+                .filter(|t| t.pinned == Some(project.id))
+                .count();
+
+            ProjectDemand {
+                project_id: project.id,
+                min_teams: project.min_teams,
+                max_teams: project.max_teams,
+                first_choices,
+                preferences,
+                pinned,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for demand in &demands {
+        if demand.pinned > demand.max_teams {
+            issues.push(format!(
+                "project '{}' has {} pinned teams, but allows at most {} teams",
+                demand.project_id, demand.pinned, demand.max_teams
+            ));
+        }
+
+        if demand.min_teams > demand.max_teams {
+            issues.push(format!(
+                "project '{}' requires at least {} teams, but allows at most {} teams",
+                demand.project_id, demand.min_teams, demand.max_teams
+            ));
         }
     }
 
-    fn create_team_constraint(&mut self) {
-        for t in &self.teams {
-            let vars = self
-                .variables
-                .clone()
-                .into_iter()
-                .filter_map(|var| {
-                    if var.team == *t {
-                        Some((var.variable, 1.0))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            self.problem.add_constraint(&vars, ComparisonOp::Le, 1.0);
-            self.problem.add_constraint(&vars, ComparisonOp::Ge, 1.0);
+    if solution.is_none() && issues.is_empty() {
+        issues.push(
+            "the pinned assignments and project minimums cannot be satisfied at the same time"
+                .to_string(),
+        );
+    }
+
+    demands.retain(|demand| demand.first_choices > demand.max_teams);
+    demands.sort_by_key(|demand| std::cmp::Reverse(demand.first_choices - demand.max_teams));
+
+    let teams_without_preferences = inputs
+        .teams
+        .iter()
+        .filter(|t| t.preferences.is_empty())
+        .map(|t| t.id)
+        .collect();
+
+    let team_ranks = solution
+        .map(|solution| {
+            solution
+                .iter()
+                .map(|(team_id, project_id)| (*team_id, inputs.rank(*team_id, *project_id)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    MatchingDiagnostics {
+        feasible: solution.is_some(),
+        team_count,
+        project_count: inputs.projects.len(),
+        max_teams_per_project: inputs.max_teams_per_project,
+        capacity,
+        required,
+        teams_without_preferences,
+        oversubscribed_projects: demands,
+        issues,
+        team_ranks,
+    }
+}
+
+struct Edge {
+    to: usize,
+    capacity: i64,
+    cost: i64,
+    flow: i64,
+}
+
+/// Successive shortest paths with Bellman-Ford (SPFA), which tolerates negative edge costs.
+struct MinCostFlow {
+    adjacency: Vec<Vec<usize>>,
+    edges: Vec<Edge>,
+}
+
+impl MinCostFlow {
+    fn new(nodes: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); nodes],
+            edges: Vec::new(),
         }
     }
 
-    fn get_mapping(&mut self, sol: &Solution) -> HashMap<Uuid, Uuid> {
-        let mut solution = HashMap::<Uuid, Uuid>::new();
-        for var in &self.variables {
-            let res = *sol.var_value(var.variable);
-            if res > 0.5 {
-                solution.insert(var.team, var.project);
+    /// Returns the index of the forward edge.
+    fn add_edge(&mut self, from: usize, to: usize, capacity: i64, cost: i64) -> usize {
+        let index = self.edges.len();
+
+        self.edges.push(Edge {
+            to,
+            capacity,
+            cost,
+            flow: 0,
+        });
+        self.adjacency[from].push(index);
+
+        self.edges.push(Edge {
+            to: from,
+            capacity: 0,
+            cost: -cost,
+            flow: 0,
+        });
+        self.adjacency[to].push(index + 1);
+
+        index
+    }
+
+    fn flow(&self, edge: usize) -> i64 {
+        self.edges[edge].flow
+    }
+
+    fn residual(&self, edge: usize) -> i64 {
+        self.edges[edge].capacity - self.edges[edge].flow
+    }
+
+    fn run(&mut self, source: usize, sink: usize) -> i64 {
+        let nodes = self.adjacency.len();
+        let mut total_flow = 0;
+
+        loop {
+            let mut distance = vec![i64::MAX; nodes];
+            let mut previous_edge = vec![None::<usize>; nodes];
+            let mut in_queue = vec![false; nodes];
+            let mut queue = VecDeque::from([source]);
+
+            distance[source] = 0;
+            in_queue[source] = true;
+
+            while let Some(node) = queue.pop_front() {
+                in_queue[node] = false;
+
+                for &edge in &self.adjacency[node] {
+                    if self.residual(edge) <= 0 {
+                        continue;
+                    }
+
+                    let to = self.edges[edge].to;
+                    let candidate = distance[node] + self.edges[edge].cost;
+
+                    if candidate < distance[to] {
+                        distance[to] = candidate;
+                        previous_edge[to] = Some(edge);
+
+                        if !in_queue[to] {
+                            in_queue[to] = true;
+                            queue.push_back(to);
+                        }
+                    }
+                }
             }
+
+            if distance[sink] == i64::MAX {
+                break;
+            }
+
+            // Find the bottleneck along the shortest path
+            let mut bottleneck = i64::MAX;
+            let mut node = sink;
+            while let Some(edge) = previous_edge[node] {
+                bottleneck = bottleneck.min(self.residual(edge));
+                node = self.edges[edge ^ 1].to;
+            }
+
+            let mut node = sink;
+            while let Some(edge) = previous_edge[node] {
+                self.edges[edge].flow += bottleneck;
+                self.edges[edge ^ 1].flow -= bottleneck;
+                node = self.edges[edge ^ 1].to;
+            }
+
+            total_flow += bottleneck;
+        }
+
+        total_flow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::models::MatchingParameters;
+
+    fn team(preferences: &[Uuid]) -> MatchingTeam {
+        MatchingTeam {
+            id: Uuid::new_v4(),
+            preferences: preferences.to_vec(),
+            pinned: None,
+        }
+    }
+
+    fn project(min_teams: usize, max_teams: usize) -> MatchingProject {
+        MatchingProject {
+            id: Uuid::new_v4(),
+            min_teams,
+            max_teams,
         }
-        solution
     }
 
-    pub fn solve(&mut self) -> Result<HashMap<Uuid, Uuid>, minilp::Error> {
-        let solution = self.problem.solve()?;
-        Ok(self.get_mapping(&solution))
+    #[test]
+    fn assigns_every_team_integrally() {
+        let projects = vec![project(0, 1), project(0, 1), project(0, 1)];
+        let (a, b, c) = (projects[0].id, projects[1].id, projects[2].id);
+        let teams = vec![team(&[a, b]), team(&[a]), team(&[])];
+
+        let solution = GroupAssignment::new(teams.clone(), projects, MatchingObjective::TotalRank)
+            .solve()
+            .expect("problem is feasible");
+
+        assert_eq!(solution.len(), 3);
+        assert_eq!(solution[&teams[1].id], a);
+        assert_eq!(solution[&teams[0].id], b);
+        assert_eq!(solution[&teams[2].id], c);
+    }
+
+    #[test]
+    fn steers_teams_without_preferences_to_unrequested_projects() {
+        let projects = vec![project(0, 2), project(0, 2), project(0, 2)];
+        let (a, b, c) = (projects[0].id, projects[1].id, projects[2].id);
+        let teams = vec![team(&[a, b]), team(&[b]), team(&[])];
+
+        let solution = GroupAssignment::new(teams.clone(), projects, MatchingObjective::TotalRank)
+            .solve()
+            .expect("problem is feasible");
+
+        assert_eq!(solution[&teams[0].id], a);
+        assert_eq!(solution[&teams[1].id], b);
+        assert_eq!(solution[&teams[2].id], c);
+    }
+
+    #[test]
+    fn fairness_minimizes_worst_rank() {
+        let projects = vec![project(0, 1), project(0, 1), project(0, 1), project(0, 1)];
+        let (a, b, c, d) = (
+            projects[0].id,
+            projects[1].id,
+            projects[2].id,
+            projects[3].id,
+        );
+
+        // The lowest total rank (2) can only be reached by giving some team its third choice,
+        // whereas a total rank of 3 keeps every team within its first two choices.
+        let teams = vec![
+            team(&[a, b, c, d]),
+            team(&[a, b, c, d]),
+            team(&[b, d, a, c]),
+            team(&[d, c, a, b]),
+        ];
+
+        let worst_rank = |objective| {
+            let solution = GroupAssignment::new(teams.clone(), projects.clone(), objective)
+                .solve()
+                .expect("problem is feasible");
+
+            teams
+                .iter()
+                .filter_map(|t| t.preferences.iter().position(|p| *p == solution[&t.id]))
+                .max()
+        };
+
+        assert_eq!(worst_rank(MatchingObjective::TotalRank), Some(2));
+        assert_eq!(worst_rank(MatchingObjective::WorstRank), Some(1));
+    }
+
+    #[test]
+    fn respects_minimums_and_pins() {
+        let projects = vec![project(0, 2), project(1, 1)];
+        let (a, b) = (projects[0].id, projects[1].id);
+        let mut teams = vec![team(&[a]), team(&[a]), team(&[a])];
+        teams[0].pinned = Some(a);
+
+        let solution = GroupAssignment::new(teams.clone(), projects, MatchingObjective::TotalRank)
+            .solve()
+            .expect("problem is feasible");
+
+        assert_eq!(solution[&teams[0].id], a);
+        assert_eq!(solution.values().filter(|p| **p == b).count(), 1);
+    }
+
+    #[test]
+    fn detects_infeasibility() {
+        let projects = vec![project(2, 2), project(2, 2)];
+        let teams = vec![team(&[]), team(&[]), team(&[])];
+
+        assert!(
+            GroupAssignment::new(teams, projects, MatchingObjective::TotalRank)
+                .solve()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn diagnoses_infeasible_problems() {
+        let projects = vec![project(0, 1), project(0, 1)];
+        let a = projects[0].id;
+        let teams = vec![team(&[a]), team(&[a]), team(&[a]), team(&[])];
+
+        let inputs = MatchingInputs {
+            parameters: MatchingParameters::default(),
+            max_teams_per_project: 1,
+            teams,
+            projects,
+        };
+
+        let diagnostics = diagnose(&inputs, None);
+
+        assert!(!diagnostics.feasible);
+        assert_eq!(diagnostics.capacity, 2);
+        assert_eq!(diagnostics.teams_without_preferences.len(), 1);
+        assert_eq!(diagnostics.oversubscribed_projects.len(), 1);
+        assert_eq!(diagnostics.oversubscribed_projects[0].first_choices, 3);
+        assert_eq!(diagnostics.issues.len(), 1);
+    }
+
+    #[test]
+    fn rejects_minimums_above_maximums() {
+        let projects = vec![project(2, 1), project(0, 2)];
+        let teams = vec![team(&[]), team(&[])];
+
+        let solution = GroupAssignment::new(
+            teams.clone(),
+            projects.clone(),
+            MatchingObjective::TotalRank,
+        )
+        .solve();
+
+        let inputs = MatchingInputs {
+            parameters: MatchingParameters::default(),
+            max_teams_per_project: 2,
+            teams,
+            projects,
+        };
+
+        let diagnostics = diagnose(&inputs, solution.as_ref());
+
+        assert!(solution.is_none());
+        assert!(!diagnostics.feasible);
+        assert_eq!(diagnostics.issues.len(), 1);
     }
 }
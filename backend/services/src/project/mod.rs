@@ -1,32 +1,60 @@
 mod matching;
 pub mod models;
-use crate::project::models::{Project, ProjectForCreate, ProjectForUpdate};
+use crate::project::models::{
+    MatchingDiagnostics, MatchingInputs, MatchingParameters, MatchingProject, MatchingTeam,
+    Project, ProjectAttachment, ProjectAttachmentForCreate, ProjectForCreate, ProjectForUpdate,
+    ProjectMatching, ProjectMatchingForUpdate,
+};
+use crate::upload::UploadService;
 use crate::{ServiceError, ServiceResult};
+use chrono::Utc;
+use futures::future::try_join_all;
 use hackathon_portal_repositories::db::{
-    db_project, db_team, EventRepository, ProjectPreferenceRepository, ProjectRepository,
-    TeamRepository,
+    db_event, db_event_role_assignment, db_project, db_project_attachment, db_project_matching,
+    db_project_owner, db_team, EventRepository, EventRole, MediaUsage, ProjectAttachmentRepository,
+    ProjectMatchingRepository, ProjectOwnerRepository, ProjectPreferenceRepository,
+    ProjectRepository, TeamRepository, TryInsertResultExt,
 };
 use hackathon_portal_repositories::DbRepository;
 use matching::GroupAssignment;
 use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
 use sea_orm::{ActiveModelTrait, IntoActiveModel, Set, TransactionTrait};
 use slug::slugify;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct ProjectService {
+    upload_service: Arc<UploadService>,
     db_repo: DbRepository,
 }
 
 impl ProjectService {
     #[must_use]
-    pub const fn new(db_repo: DbRepository) -> Self {
-        Self { db_repo }
+    pub const fn new(upload_service: Arc<UploadService>, db_repo: DbRepository) -> Self {
+        Self {
+            upload_service,
+            db_repo,
+        }
     }
 
-    pub async fn create_project(&self, project_fc: ProjectForCreate) -> ServiceResult<Project> {
+    /// If a creator is given, they become the first owner of the project.
+    pub async fn create_project(
+        &self,
+        creator_id: Option<Uuid>,
+        project_fc: ProjectForCreate,
+    ) -> ServiceResult<Project> {
         let txn = self.db_repo.conn().begin().await?;
 
+        let event = EventRepository::fetch_by_id(&txn, project_fc.event_id).await?;
+
+        Self::validate_team_limits(
+            project_fc.min_teams.unwrap_or_default(),
+            project_fc.max_teams,
+            &event,
+        )?;
+
         let slug = self
             .generate_slug(&txn, project_fc.event_id, &project_fc.name, None)
             .await?;
@@ -36,28 +64,67 @@ impl ProjectService {
             name: Set(project_fc.name),
             slug: Set(slug),
             content: Set(project_fc.content),
+            min_teams: Set(project_fc.min_teams.unwrap_or_default() as i32),
+            max_teams: Set(project_fc.max_teams.map(|max_teams| max_teams as i32)),
+            requirements: Set(serde_json::to_value(
+                project_fc.requirements.unwrap_or_default(),
+            )?),
             ..Default::default()
         };
 
         let project = active_project.insert(&txn).await?;
 
+        if let Some(creator_id) = creator_id {
+            db_project_owner::ActiveModel {
+                project_id: Set(project.id),
+                user_id: Set(creator_id),
+            }
+            .insert(&txn)
+            .await?;
+        }
+
         txn.commit().await?;
 
-        Ok(project.into())
+        self.assemble_project(project).await
     }
 
     pub async fn get_projects(&self, event_id: Uuid) -> ServiceResult<Vec<Project>> {
         let projects =
             ProjectRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
 
-        let projects = projects.into_iter().map(Project::from).collect();
+        let mut owners = HashMap::<Uuid, Vec<Uuid>>::new();
+        for owner in
+            ProjectOwnerRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?
+        {
+            owners
+                .entry(owner.project_id)
+                .or_default()
+                .push(owner.user_id);
+        }
+
+        let mut attachments = HashMap::<Uuid, Vec<db_project_attachment::Model>>::new();
+        for attachment in
+            ProjectAttachmentRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id)
+                .await?
+        {
+            attachments
+                .entry(attachment.project_id)
+                .or_default()
+                .push(attachment);
+        }
+
+        try_join_all(projects.into_iter().map(|project| {
+            let owners = owners.remove(&project.id).unwrap_or_default();
+            let attachments = attachments.remove(&project.id).unwrap_or_default();
 
-        Ok(projects)
+            self.assemble_project_with(project, owners, attachments)
+        }))
+        .await
     }
 
     pub async fn get_project(&self, project_id: Uuid) -> ServiceResult<Project> {
         let project = ProjectRepository::fetch_by_id(self.db_repo.conn(), project_id).await?;
-        Ok(project.into())
+        self.assemble_project(project).await
     }
 
     pub async fn get_project_by_slug(
@@ -68,7 +135,7 @@ impl ProjectService {
         let project =
             ProjectRepository::fetch_by_slug(self.db_repo.conn(), event_slug, project_slug).await?;
 
-        Ok(project.into())
+        self.assemble_project(project).await
     }
 
     pub async fn update_project(
@@ -99,11 +166,36 @@ impl ProjectService {
             active_project.content = Set(content.clone());
         }
 
+        if let Some(min_teams) = project_fu.min_teams {
+            active_project.min_teams = Set(min_teams as i32);
+        }
+
+        if let Some(max_teams) = project_fu.max_teams {
+            active_project.max_teams = Set(max_teams.map(|max_teams| max_teams as i32));
+        }
+
+        if project_fu.min_teams.is_some() || project_fu.max_teams.is_some() {
+            let event = EventRepository::fetch_by_id(&txn, event_id).await?;
+
+            Self::validate_team_limits(
+                *active_project.min_teams.as_ref() as u32,
+                active_project
+                    .max_teams
+                    .as_ref()
+                    .map(|max_teams| max_teams as u32),
+                &event,
+            )?;
+        }
+
+        if let Some(requirements) = &project_fu.requirements {
+            active_project.requirements = Set(serde_json::to_value(requirements)?);
+        }
+
         let project = active_project.update(&txn).await?;
 
         txn.commit().await?;
 
-        Ok(project.into())
+        self.assemble_project(project).await
     }
 
     /// Fails if the project is still assigned to a team.
@@ -124,62 +216,447 @@ impl ProjectService {
             });
         }
 
+        db_project_owner::Entity::delete_many()
+            .filter(db_project_owner::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await?;
+
+        db_project_attachment::Entity::delete_many()
+            .filter(db_project_attachment::Column::ProjectId.eq(project_id))
+            .exec(&txn)
+            .await?;
+
         project.delete(&txn).await?;
         txn.commit().await?;
 
         Ok(())
     }
 
-    pub async fn get_matching(&self, event_id: Uuid) -> ServiceResult<HashMap<Uuid, Uuid>> {
-        let projects =
-            ProjectRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
-        let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
-        let project_ids = projects.into_iter().map(|p| p.id).collect::<Vec<_>>();
+    /// Only stakeholders and admins of the project's event can become owners.
+    pub async fn add_project_owners(
+        &self,
+        project_id: Uuid,
+        user_ids: HashSet<Uuid>,
+    ) -> ServiceResult<u64> {
+        let txn = self.db_repo.conn().begin().await?;
 
-        let teams = TeamRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
-        let team_ids = teams.iter().map(|t| t.id).collect::<Vec<_>>();
+        let project = ProjectRepository::fetch_by_id(&txn, project_id).await?;
+
+        let eligible = db_event_role_assignment::Entity::find()
+            .filter(db_event_role_assignment::Column::EventId.eq(project.event_id))
+            .filter(
+                db_event_role_assignment::Column::Role
+                    .is_in([EventRole::Stakeholder, EventRole::Admin]),
+            )
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|assignment| assignment.user_id)
+            .collect::<HashSet<_>>();
+
+        if let Some(user_id) = user_ids.iter().find(|user_id| !eligible.contains(user_id)) {
+            return Err(ServiceError::ProjectOwnerNotStakeholder {
+                user_id: user_id.to_string(),
+            });
+        }
+
+        let active_owners = user_ids
+            .into_iter()
+            .map(|user_id| db_project_owner::ActiveModel {
+                project_id: Set(project_id),
+                user_id: Set(user_id),
+            })
+            .collect::<Vec<_>>();
+
+        let rows_affected = db_project_owner::Entity::insert_many(active_owners)
+            .on_conflict(
+                OnConflict::columns(vec![
+                    db_project_owner::Column::ProjectId,
+                    db_project_owner::Column::UserId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .on_empty_do_nothing()
+            .exec_without_returning(&txn)
+            .await?
+            .unwrap_or_default();
+
+        txn.commit().await?;
+
+        Ok(rows_affected)
+    }
+
+    pub async fn remove_project_owners(
+        &self,
+        project_id: Uuid,
+        user_ids: HashSet<Uuid>,
+    ) -> ServiceResult<u64> {
+        let result = db_project_owner::Entity::delete_many()
+            .filter(db_project_owner::Column::ProjectId.eq(project_id))
+            .filter(db_project_owner::Column::UserId.is_in(user_ids))
+            .exec(self.db_repo.conn())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    pub async fn add_project_attachment(
+        &self,
+        project_id: Uuid,
+        attachment_fc: ProjectAttachmentForCreate,
+    ) -> ServiceResult<Project> {
+        let project = ProjectRepository::fetch_by_id(self.db_repo.conn(), project_id).await?;
+
+        self.upload_service
+            .validate_upload(
+                attachment_fc.upload_id,
+                MediaUsage::ProjectAttachment,
+                false,
+            )
+            .await?;
+
+        db_project_attachment::ActiveModel {
+            project_id: Set(project_id),
+            upload_id: Set(attachment_fc.upload_id),
+            name: Set(attachment_fc.name),
+        }
+        .insert(self.db_repo.conn())
+        .await?;
+
+        self.assemble_project(project).await
+    }
+
+    pub async fn delete_project_attachment(
+        &self,
+        project_id: Uuid,
+        upload_id: Uuid,
+    ) -> ServiceResult<Project> {
+        let project = ProjectRepository::fetch_by_id(self.db_repo.conn(), project_id).await?;
+
+        db_project_attachment::Entity::delete_by_id((project_id, upload_id))
+            .exec(self.db_repo.conn())
+            .await?;
+
+        self.assemble_project(project).await
+    }
+
+    /// Computes a matching without persisting it.
+    pub async fn get_matching(
+        &self,
+        event_id: Uuid,
+        params: MatchingParameters,
+    ) -> ServiceResult<HashMap<Uuid, Uuid>> {
+        let inputs = self.collect_matching_inputs(event_id, params).await?;
+
+        Self::solve_matching(&inputs)
+    }
+
+    pub async fn get_matching_diagnostics(
+        &self,
+        event_id: Uuid,
+        params: MatchingParameters,
+    ) -> ServiceResult<MatchingDiagnostics> {
+        let inputs = self.collect_matching_inputs(event_id, params).await?;
+
+        // Infeasible problems already come with their diagnostics
+        match Self::solve_matching(&inputs) {
+            Ok(solution) => Ok(matching::diagnose(&inputs, Some(&solution))),
+            Err(ServiceError::MatchingInfeasible { diagnostics }) => Ok(diagnostics),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Computes a matching and stores it together with its inputs for later review.
+    pub async fn create_project_matching(
+        &self,
+        event_id: Uuid,
+        params: MatchingParameters,
+    ) -> ServiceResult<ProjectMatching> {
+        let inputs = self.collect_matching_inputs(event_id, params).await?;
+        let solution = Self::solve_matching(&inputs)?;
+        let (total_rank, worst_rank) = inputs.evaluate(&solution);
+
+        let active_matching = db_project_matching::ActiveModel {
+            event_id: Set(event_id),
+            inputs: Set(serde_json::to_value(&inputs)?),
+            solution: Set(serde_json::to_value(&solution)?),
+            assignments: Set(serde_json::to_value(&solution)?),
+            total_rank: Set(total_rank as i32),
+            worst_rank: Set(worst_rank.map(|rank| rank as i32)),
+            created_at: Set(Utc::now().naive_utc()),
+            applied_at: Set(None),
+            ..Default::default()
+        };
+
+        let matching = active_matching.insert(self.db_repo.conn()).await?;
+
+        ProjectMatching::try_from(matching)
+    }
+
+    pub async fn get_project_matchings(
+        &self,
+        event_id: Uuid,
+    ) -> ServiceResult<Vec<ProjectMatching>> {
+        let matchings =
+            ProjectMatchingRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
+
+        matchings
+            .into_iter()
+            .map(ProjectMatching::try_from)
+            .collect()
+    }
+
+    pub async fn get_project_matching(&self, matching_id: Uuid) -> ServiceResult<ProjectMatching> {
+        let matching =
+            ProjectMatchingRepository::fetch_by_id(self.db_repo.conn(), matching_id).await?;
+
+        ProjectMatching::try_from(matching)
+    }
+
+    /// Overrides individual assignments of a stored matching, the solver's solution is kept.
+    /// Fails once the matching has been applied.
+    pub async fn update_project_matching(
+        &self,
+        matching_id: Uuid,
+        matching_fu: ProjectMatchingForUpdate,
+    ) -> ServiceResult<ProjectMatching> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let matching_model = ProjectMatchingRepository::fetch_by_id(&txn, matching_id).await?;
+        let mut matching = ProjectMatching::try_from(matching_model.clone())?;
+
+        if matching.applied_at.is_some() {
+            return Err(ServiceError::Matching {
+                message: "matching has already been applied and can no longer be edited."
+                    .to_string(),
+            });
+        }
+
+        for (team_id, project_id) in matching_fu.assignments {
+            if !matching.inputs.teams.iter().any(|team| team.id == team_id) {
+                return Err(ServiceError::Matching {
+                    message: format!("team '{team_id}' is not part of this matching."),
+                });
+            }
+
+            let Some(project_id) = project_id else {
+                matching.assignments.remove(&team_id);
+                continue;
+            };
+
+            if !matching
+                .inputs
+                .projects
+                .iter()
+                .any(|project| project.id == project_id)
+            {
+                return Err(ServiceError::Matching {
+                    message: format!("project '{project_id}' is not part of this matching."),
+                });
+            }
+
+            matching.assignments.insert(team_id, project_id);
+        }
+
+        let (total_rank, worst_rank) = matching.inputs.evaluate(&matching.assignments);
+
+        let mut active_matching = matching_model.into_active_model();
+        active_matching.assignments = Set(serde_json::to_value(&matching.assignments)?);
+        active_matching.total_rank = Set(total_rank as i32);
+        active_matching.worst_rank = Set(worst_rank.map(|rank| rank as i32));
+
+        let matching = active_matching.update(&txn).await?;
+
+        txn.commit().await?;
+
+        ProjectMatching::try_from(matching)
+    }
+
+    /// Writes the assignments of a stored matching to the teams. Teams that are part of the
+    /// matching but not assigned lose their project, teams created afterwards are left untouched.
+    pub async fn apply_project_matching(
+        &self,
+        matching_id: Uuid,
+    ) -> ServiceResult<ProjectMatching> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let matching_model = ProjectMatchingRepository::fetch_by_id(&txn, matching_id).await?;
+        let matching = ProjectMatching::try_from(matching_model.clone())?;
+
+        let projects = ProjectRepository::fetch_all_by_event_id(&txn, matching.event_id).await?;
+        let teams = TeamRepository::fetch_all_by_event_id(&txn, matching.event_id).await?;
 
-        // Mapping from team_id -> project_id -> preference
-        let mut preference = HashMap::<Uuid, HashMap<Uuid, i32>>::new();
         for team in teams {
-            let team_pref =
-                ProjectPreferenceRepository::fetch_all_by_team_id(self.db_repo.conn(), team.id)
-                    .await?;
+            if !matching.inputs.teams.iter().any(|t| t.id == team.id) {
+                continue;
+            }
 
-            let team_pref =
-                team_pref
-                    .into_iter()
-                    .fold(HashMap::<Uuid, i32>::new(), |mut acc, pref| {
-                        acc.insert(pref.project_id, pref.score);
-                        acc
+            let project_id = matching.assignments.get(&team.id).copied();
+
+            if let Some(project_id) = project_id {
+                if !projects.iter().any(|project| project.id == project_id) {
+                    return Err(ServiceError::Matching {
+                        message: format!("project '{project_id}' no longer exists."),
                     });
-            preference.insert(team.id, team_pref);
+                }
+            }
+
+            let mut active_team = team.into_active_model();
+            active_team.project_id = Set(project_id);
+            active_team.update(&txn).await?;
         }
 
-        let matching_problem = GroupAssignment::new(
-            team_ids,
-            project_ids,
-            event.max_teams_per_project,
-            preference,
-        );
+        let mut active_matching = matching_model.into_active_model();
+        active_matching.applied_at = Set(Some(Utc::now().naive_utc()));
+        let matching = active_matching.update(&txn).await?;
 
-        let Some(mut matching) = matching_problem else {
-            return Err(ServiceError::Matching {
-                message: "failed to instantiate the problem.".to_string(),
+        txn.commit().await?;
+
+        ProjectMatching::try_from(matching)
+    }
+
+    async fn assemble_project(&self, project: db_project::Model) -> ServiceResult<Project> {
+        let owners =
+            ProjectOwnerRepository::fetch_all_by_project_id(self.db_repo.conn(), project.id)
+                .await?
+                .into_iter()
+                .map(|owner| owner.user_id)
+                .collect();
+
+        let attachments =
+            ProjectAttachmentRepository::fetch_all_by_project_id(self.db_repo.conn(), project.id)
+                .await?;
+
+        self.assemble_project_with(project, owners, attachments)
+            .await
+    }
+
+    /// Expects the owners and attachments to be prefetched, e.g. for all projects of an event.
+    async fn assemble_project_with(
+        &self,
+        project: db_project::Model,
+        owners: Vec<Uuid>,
+        attachments: Vec<db_project_attachment::Model>,
+    ) -> ServiceResult<Project> {
+        let attachments = try_join_all(attachments.into_iter().map(|attachment| async move {
+            let url = self
+                .upload_service
+                .generate_download_url(attachment.upload_id)
+                .await?;
+
+            ServiceResult::Ok(ProjectAttachment {
+                upload_id: attachment.upload_id,
+                name: attachment.name,
+                url,
+            })
+        }))
+        .await?;
+
+        Ok(Project {
+            id: project.id,
+            event_id: project.event_id,
+            name: project.name,
+            slug: project.slug,
+            content: project.content,
+            min_teams: project.min_teams as u32,
+            max_teams: project.max_teams.map(|max_teams| max_teams as u32),
+            requirements: serde_json::from_value(project.requirements)?,
+            owners,
+            attachments,
+        })
+    }
+
+    async fn collect_matching_inputs(
+        &self,
+        event_id: Uuid,
+        parameters: MatchingParameters,
+    ) -> ServiceResult<MatchingInputs> {
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
+        let projects =
+            ProjectRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
+        let teams = TeamRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
+
+        let default_max_teams = event.max_teams_per_project.max(0) as usize;
+
+        let matching_projects = projects
+            .iter()
+            .map(|project| MatchingProject {
+                id: project.id,
+                min_teams: project.min_teams.max(0) as usize,
+                max_teams: project
+                    .max_teams
+                    .map_or(default_max_teams, |max_teams| max_teams.max(0) as usize),
+            })
+            .collect::<Vec<_>>();
+
+        for (team_id, project_id) in &parameters.pinned {
+            if !teams.iter().any(|team| team.id == *team_id)
+                || !projects.iter().any(|project| project.id == *project_id)
+            {
+                return Err(ServiceError::Matching {
+                    message: format!(
+                        "pinned assignment of team '{team_id}' to project '{project_id}' does not belong to this event."
+                    ),
+                });
+            }
+        }
+
+        let mut matching_teams = Vec::with_capacity(teams.len());
+        for team in &teams {
+            // Preferences are ordered by score, i.e. by rank
+            let preferences =
+                ProjectPreferenceRepository::fetch_all_by_team_id(self.db_repo.conn(), team.id)
+                    .await?
+                    .into_iter()
+                    .map(|pref| pref.project_id)
+                    .collect();
+
+            matching_teams.push(MatchingTeam {
+                id: team.id,
+                preferences,
+                pinned: parameters.pinned.get(&team.id).copied(),
             });
-        };
+        }
 
-        let solution = matching.solve();
+        Ok(MatchingInputs {
+            parameters,
+            max_teams_per_project: default_max_teams,
+            teams: matching_teams,
+            projects: matching_projects,
+        })
+    }
 
-        match solution {
-            Ok(solution) => Ok(solution),
-            Err(minilp::Error::Infeasible) => Err(ServiceError::Matching {
-                message: ("no feasible solution found.".to_string()),
-            }),
-            Err(minilp::Error::Unbounded) => Err(ServiceError::Matching {
-                message: ("problem is unbounded.".to_string()),
-            }),
+    fn solve_matching(inputs: &MatchingInputs) -> ServiceResult<HashMap<Uuid, Uuid>> {
+        let matching = GroupAssignment::new(
+            inputs.teams.clone(),
+            inputs.projects.clone(),
+            inputs.parameters.objective,
+        );
+
+        matching
+            .solve()
+            .ok_or_else(|| ServiceError::MatchingInfeasible {
+                diagnostics: matching::diagnose(inputs, None),
+            })
+    }
+
+    /// Projects without an explicit maximum fall back to the event's maximum.
+    fn validate_team_limits(
+        min_teams: u32,
+        max_teams: Option<u32>,
+        event: &db_event::Model,
+    ) -> ServiceResult<()> {
+        let max_teams = max_teams.unwrap_or(event.max_teams_per_project as u32);
+
+        if min_teams > max_teams {
+            return Err(ServiceError::ProjectTeamLimits {
+                min_teams,
+                max_teams,
+            });
         }
+
+        Ok(())
     }
 
     async fn generate_slug<C: ConnectionTrait>(
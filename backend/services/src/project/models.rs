@@ -1,5 +1,8 @@
-use hackathon_portal_repositories::db::db_project;
+use crate::ServiceError;
+use chrono::NaiveDateTime;
+use hackathon_portal_repositories::db::db_project_matching;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -10,18 +13,38 @@ pub struct Project {
     pub name: String,
     pub slug: String,
     pub content: String,
+    pub min_teams: u32,
+    pub max_teams: Option<u32>,
+    pub requirements: Vec<ProjectRequirement>,
+    /// Users owning the project, typically the stakeholders who pitched it
+    pub owners: Vec<Uuid>,
+    pub attachments: Vec<ProjectAttachment>,
 }
 
-impl From<db_project::Model> for Project {
-    fn from(event: db_project::Model) -> Self {
-        Self {
-            id: event.id,
-            event_id: event.event_id,
-            name: event.name,
-            slug: event.slug,
-            content: event.content,
-        }
-    }
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum ProjectRequirementKind {
+    Skill,
+    Hardware,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ProjectRequirement {
+    pub kind: ProjectRequirementKind,
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ProjectAttachment {
+    pub upload_id: Uuid,
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ProjectAttachmentForCreate {
+    pub upload_id: Uuid,
+    pub name: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
@@ -29,10 +52,171 @@ pub struct ProjectForCreate {
     pub event_id: Uuid,
     pub name: String,
     pub content: String,
+    pub min_teams: Option<u32>,
+    pub max_teams: Option<u32>,
+    pub requirements: Option<Vec<ProjectRequirement>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
 pub struct ProjectForUpdate {
     pub name: Option<String>,
     pub content: Option<String>,
+    pub min_teams: Option<u32>,
+    /// Set to `null` to fall back to the event's maximum number of teams per project
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "::serde_with::rust::double_option"
+    )]
+    #[schema(value_type = Option<u32>)]
+    pub max_teams: Option<Option<u32>>,
+    pub requirements: Option<Vec<ProjectRequirement>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
+pub enum MatchingObjective {
+    /// Minimize the sum of all preference ranks
+    #[default]
+    TotalRank,
+    /// Minimize the worst preference rank of any team, then the sum of all ranks
+    WorstRank,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ToSchema)]
+pub struct MatchingParameters {
+    #[serde(default)]
+    pub objective: MatchingObjective,
+    /// Mapping from team id to the project it must be assigned to
+    #[serde(default)]
+    pub pinned: HashMap<Uuid, Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MatchingTeam {
+    pub id: Uuid,
+    /// Projects ordered by preference, the first entry being the most preferred one
+    pub preferences: Vec<Uuid>,
+    pub pinned: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MatchingProject {
+    pub id: Uuid,
+    pub min_teams: usize,
+    pub max_teams: usize,
+}
+
+/// Snapshot of everything the solver was given, so runs stay comparable after
+/// preferences or projects change.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MatchingInputs {
+    pub parameters: MatchingParameters,
+    /// Default maximum number of teams per project of the event
+    #[serde(default)]
+    pub max_teams_per_project: usize,
+    pub teams: Vec<MatchingTeam>,
+    pub projects: Vec<MatchingProject>,
+}
+
+impl MatchingInputs {
+    /// Zero-based rank of the project in the team's preferences. Unlisted projects rank last,
+    /// teams without preferences have no rank.
+    #[must_use]
+    pub fn rank(&self, team_id: Uuid, project_id: Uuid) -> Option<usize> {
+        let team = self.teams.iter().find(|team| team.id == team_id)?;
+
+        if team.preferences.is_empty() {
+            return None;
+        }
+
+        let rank = team
+            .preferences
+            .iter()
+            .position(|p| *p == project_id)
+            .unwrap_or(self.projects.len());
+
+        Some(rank)
+    }
+
+    /// Returns the total and the worst rank of all ranked teams.
+    #[must_use]
+    pub fn evaluate(&self, assignments: &HashMap<Uuid, Uuid>) -> (usize, Option<usize>) {
+        let ranks = assignments
+            .iter()
+            .filter_map(|(team_id, project_id)| self.rank(*team_id, *project_id))
+            .collect::<Vec<_>>();
+
+        (ranks.iter().sum(), ranks.iter().max().copied())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ProjectMatching {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub inputs: MatchingInputs,
+    /// Mapping from team id to project id as computed by the solver
+    pub solution: HashMap<Uuid, Uuid>,
+    /// Mapping from team id to project id including manual overrides
+    pub assignments: HashMap<Uuid, Uuid>,
+    pub total_rank: u32,
+    pub worst_rank: Option<u32>,
+    pub created_at: NaiveDateTime,
+    pub applied_at: Option<NaiveDateTime>,
+}
+
+impl TryFrom<db_project_matching::Model> for ProjectMatching {
+    type Error = ServiceError;
+
+    fn try_from(value: db_project_matching::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            event_id: value.event_id,
+            inputs: serde_json::from_value(value.inputs)?,
+            solution: serde_json::from_value(value.solution)?,
+            assignments: serde_json::from_value(value.assignments)?,
+            total_rank: value.total_rank as u32,
+            worst_rank: value.worst_rank.map(|rank| rank as u32),
+            created_at: value.created_at,
+            applied_at: value.applied_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ProjectMatchingForUpdate {
+    /// Mapping from team id to project id, `null` unassigns the team
+    pub assignments: HashMap<Uuid, Option<Uuid>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct ProjectDemand {
+    pub project_id: Uuid,
+    pub min_teams: usize,
+    pub max_teams: usize,
+    /// Number of teams having this project as their first choice
+    pub first_choices: usize,
+    /// Number of teams listing this project at any rank
+    pub preferences: usize,
+    pub pinned: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct MatchingDiagnostics {
+    pub feasible: bool,
+    pub team_count: usize,
+    pub project_count: usize,
+    pub max_teams_per_project: usize,
+    /// Sum of the maximum number of teams over all projects
+    pub capacity: usize,
+    /// Sum of the minimum number of teams over all projects
+    pub required: usize,
+    pub teams_without_preferences: Vec<Uuid>,
+    /// Projects whose first choices exceed their maximum number of teams, most demanded first
+    pub oversubscribed_projects: Vec<ProjectDemand>,
+    /// Human-readable reasons why the problem cannot be solved
+    pub issues: Vec<String>,
+    /// Zero-based preference rank of each team in the solution, `null` if the team has no
+    /// preferences. Empty if the problem is infeasible.
+    pub team_ranks: HashMap<Uuid, Option<usize>>,
 }
@@ -1,23 +1,33 @@
 pub mod models;
+pub mod template;
 
 use crate::authorization::AuthorizationService;
 use crate::crypto::CryptoService;
 use crate::infrastructure::models::IngressConfig;
-use crate::team::models::{Team, TeamForCreate, TeamForUpdate};
+use crate::team::models::{
+    Team, TeamAIAPIKeyForUpdate, TeamAIAPIKeyProvisionResult, TeamAIAPIKeyProvisionStatus,
+    TeamAIAPIKeysForProvision, TeamAIUsage, TeamCredentialLogEntry, TeamCredentialsForGenerate,
+    TeamForCreate, TeamForUpdate,
+};
+use crate::team::template::{render_override_or_template, TemplateContext};
 use crate::upload::UploadService;
 use crate::{ServiceError, ServiceResult};
+use chrono::Utc;
 use futures::future::try_join_all;
+use hackathon_portal_repositories::ai_gateway::AIGatewayRepository;
 use hackathon_portal_repositories::db::{
-    db_event, db_project_preference, db_sidequest_score, db_team, db_team_role_assignment,
-    EventRepository, MediaUsage, ProjectPreferenceRepository, TeamRepository, TeamRole,
+    db_event, db_project, db_project_preference, db_sidequest_score, db_team,
+    db_team_credential_log, db_team_health_check, db_team_role_assignment, EventRepository,
+    MediaUsage, ProjectPreferenceRepository, ProjectRepository, TeamCredentialAction,
+    TeamCredentialLogRepository, TeamRepository, TeamRole,
 };
-use hackathon_portal_repositories::lite_llm::LiteLLMRepository;
 use hackathon_portal_repositories::DbRepository;
 use sea_orm::prelude::*;
 use sea_orm::{ActiveModelTrait, IntoActiveModel, Set, TransactionTrait};
 use slug::slugify;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tracing::warn;
 
 #[derive(Clone)]
 pub struct TeamService {
@@ -25,24 +35,26 @@ pub struct TeamService {
     upload_service: Arc<UploadService>,
     crypto_service: Arc<CryptoService>,
     db_repo: DbRepository,
-    lite_llm_repo: LiteLLMRepository,
+    ai_gateway_repo: Arc<dyn AIGatewayRepository>,
 }
 
 impl TeamService {
+    const PASSWORD_LENGTH: usize = 24;
+
     #[must_use]
     pub fn new(
         authorization_service: Arc<AuthorizationService>,
         upload_service: Arc<UploadService>,
         crypto_service: Arc<CryptoService>,
         db_repo: DbRepository,
-        lite_llm_repo: LiteLLMRepository,
+        ai_gateway_repo: Arc<dyn AIGatewayRepository>,
     ) -> Self {
         Self {
             authorization_service,
             upload_service,
             crypto_service,
             db_repo,
-            lite_llm_repo,
+            ai_gateway_repo,
         }
     }
 
@@ -95,14 +107,21 @@ impl TeamService {
             .map(|e| (e.id, e))
             .collect::<HashMap<_, _>>();
 
+        let projects = ProjectRepository::fetch_all(self.db_repo.conn())
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect::<HashMap<_, _>>();
+
         let teams = TeamRepository::fetch_all(self.db_repo.conn()).await?;
 
         try_join_all(teams.into_iter().map(|team| {
             let event = events
                 .get(&team.event_id)
                 .expect("Foreign key constraint ensures event exists");
+            let project = team.project_id.and_then(|id| projects.get(&id));
 
-            self.assemble_team(team, event)
+            self.assemble_team_with_project(team, event, project)
         }))
         .await
     }
@@ -111,11 +130,17 @@ impl TeamService {
         let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
         let teams = TeamRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
 
-        try_join_all(
-            teams
-                .into_iter()
-                .map(|team| self.assemble_team(team, &event)),
-        )
+        let projects = ProjectRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id)
+            .await?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect::<HashMap<_, _>>();
+
+        try_join_all(teams.into_iter().map(|team| {
+            let project = team.project_id.and_then(|id| projects.get(&id));
+
+            self.assemble_team_with_project(team, &event, project)
+        }))
         .await
     }
 
@@ -262,7 +287,8 @@ impl TeamService {
         Ok(finalists)
     }
 
-    /// Cascade deletes team role assignments and project preferences.
+    /// Cascade deletes team role assignments, project preferences, the credential log and health
+    /// checks.
     /// Fails on any other related resources.
     pub async fn delete_team(&self, team_id: Uuid) -> ServiceResult<()> {
         let team = TeamRepository::fetch_by_id(self.db_repo.conn(), team_id).await?;
@@ -279,6 +305,16 @@ impl TeamService {
             .exec(&txn)
             .await?;
 
+        db_team_credential_log::Entity::delete_many()
+            .filter(db_team_credential_log::Column::TeamId.eq(team_id))
+            .exec(&txn)
+            .await?;
+
+        db_team_health_check::Entity::delete_many()
+            .filter(db_team_health_check::Column::TeamId.eq(team_id))
+            .exec(&txn)
+            .await?;
+
         let sidequest_scores = team
             .find_related(db_sidequest_score::Entity)
             .count(&txn)
@@ -345,16 +381,21 @@ impl TeamService {
             return Err(ServiceError::ProjectPreferenceDuplicate);
         }
 
-        if set.len() != 3 {
-            return Err(ServiceError::ProjectPreferenceWrongCount {
-                expected: 3,
-                actual: set.len(),
-            });
-        }
-
         let mut new_pps = Vec::new();
         let txn = self.db_repo.conn().begin().await?;
 
+        let team = TeamRepository::fetch_by_id(&txn, team_id).await?;
+        let projects = ProjectRepository::fetch_all_by_event_id(&txn, team.event_id).await?;
+
+        if let Some(foreign) = pps
+            .iter()
+            .find(|pp| !projects.iter().any(|project| project.id == **pp))
+        {
+            return Err(ServiceError::ProjectPreferenceInvalid {
+                project_id: foreign.to_string(),
+            });
+        }
+
         db_project_preference::Entity::delete_many()
             .filter(db_project_preference::Column::TeamId.eq(team_id))
             .exec(&txn)
@@ -382,6 +423,23 @@ impl TeamService {
         &self,
         team_model: db_team::Model,
         event_model: &db_event::Model,
+    ) -> ServiceResult<Team> {
+        let project_model = if let Some(project_id) = team_model.project_id {
+            Some(ProjectRepository::fetch_by_id(self.db_repo.conn(), project_id).await?)
+        } else {
+            None
+        };
+
+        self.assemble_team_with_project(team_model, event_model, project_model.as_ref())
+            .await
+    }
+
+    /// Listings prefetch the projects of all teams at once.
+    async fn assemble_team_with_project(
+        &self,
+        team_model: db_team::Model,
+        event_model: &db_event::Model,
+        project_model: Option<&db_project::Model>,
     ) -> ServiceResult<Team> {
         let photo_url = if let Some(photo_id) = team_model.photo_id {
             Some(self.upload_service.generate_download_url(photo_id).await?)
@@ -389,28 +447,38 @@ impl TeamService {
             None
         };
 
-        let managed_address = apply_override_and_template(
+        let ctx = TemplateContext::new(&team_model, project_model, event_model);
+
+        let managed_address = render_or_warn(
+            &team_model,
+            "managed_address_template",
             team_model.managed_address_override.as_deref(),
             event_model.managed_address_template.as_deref(),
-            &team_model,
+            &ctx,
         );
 
-        let direct_address = apply_override_and_template(
+        let direct_address = render_or_warn(
+            &team_model,
+            "direct_address_template",
             team_model.direct_address_override.as_deref(),
             event_model.direct_address_template.as_deref(),
-            &team_model,
+            &ctx,
         );
 
-        let private_address = apply_override_and_template(
+        let private_address = render_or_warn(
+            &team_model,
+            "private_address_template",
             team_model.private_address_override.as_deref(),
             event_model.private_address_template.as_deref(),
-            &team_model,
+            &ctx,
         );
 
-        let ssh_config = apply_override_and_template(
+        let ssh_config = render_or_warn(
+            &team_model,
+            "ssh_config_template",
             team_model.ssh_config_override.as_deref(),
             event_model.ssh_config_template.as_deref(),
-            &team_model,
+            &ctx,
         );
 
         let ingress_config = serde_json::from_value::<IngressConfig>(team_model.ingress_config)?;
@@ -420,16 +488,6 @@ impl TeamService {
         } else {
             None
         };
-        let password = team_model
-            .password
-            .as_ref()
-            .map(|p| self.crypto_service.decrypt(p))
-            .transpose()?;
-        let ai_api_key = team_model
-            .ai_api_key
-            .as_ref()
-            .map(|k| self.crypto_service.decrypt(k))
-            .transpose()?;
 
         let team = Team {
             id: team_model.id,
@@ -440,8 +498,14 @@ impl TeamService {
             index: team_model.index,
             photo_id: team_model.photo_id,
             photo_url,
-            password,
-            ai_api_key,
+            // Credentials are only decrypted when they are revealed
+            password: None,
+            previous_password: None,
+            ssh_public_key: team_model.ssh_public_key,
+            ssh_private_key: None,
+            previous_ssh_public_key: team_model.previous_ssh_public_key,
+            previous_ssh_private_key: None,
+            ai_api_key: None,
             extra_score: team_model.extra_score,
             comment: team_model.comment,
             managed_address,
@@ -461,6 +525,238 @@ impl TeamService {
         Ok(team)
     }
 
+    /// Generates a new password and optionally an SSH keypair. Existing credentials are kept as
+    /// previous credentials until the rotation is confirmed.
+    pub async fn generate_team_credentials(
+        &self,
+        user_id: Option<Uuid>,
+        team_id: Uuid,
+        credentials_fg: &TeamCredentialsForGenerate,
+    ) -> ServiceResult<()> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let team = TeamRepository::fetch_by_id(&txn, team_id).await?;
+        self.generate_credentials(&txn, user_id, team, credentials_fg)
+            .await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    /// Generates credentials for all teams of the event, see `generate_team_credentials`.
+    pub async fn generate_event_team_credentials(
+        &self,
+        user_id: Option<Uuid>,
+        event_id: Uuid,
+        credentials_fg: &TeamCredentialsForGenerate,
+    ) -> ServiceResult<u64> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let teams = TeamRepository::fetch_all_by_event_id(&txn, event_id).await?;
+        let affected = teams.len() as u64;
+
+        for team in teams {
+            self.generate_credentials(&txn, user_id, team, credentials_fg)
+                .await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(affected)
+    }
+
+    /// Drops the previous credentials once the new ones are deployed on the team server.
+    pub async fn confirm_team_credentials_rotation(
+        &self,
+        user_id: Option<Uuid>,
+        team_id: Uuid,
+    ) -> ServiceResult<()> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let team = TeamRepository::fetch_by_id(&txn, team_id).await?;
+
+        let mut active_team = team.into_active_model();
+        active_team.previous_password = Set(None);
+        active_team.previous_ssh_public_key = Set(None);
+        active_team.previous_ssh_private_key = Set(None);
+        active_team.update(&txn).await?;
+
+        Self::log_credential_action(
+            &txn,
+            team_id,
+            user_id,
+            TeamCredentialAction::ConfirmRotation,
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    /// Returns the team including its decrypted credentials, every call is audit logged.
+    pub async fn reveal_team_credentials(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+    ) -> ServiceResult<Team> {
+        let (team_model, event) =
+            TeamRepository::fetch_by_id_with_event(self.db_repo.conn(), team_id).await?;
+
+        let team = self.assemble_team(team_model.clone(), &event).await?;
+        let team = self.decrypt_credentials(team, &team_model)?;
+
+        Self::log_credential_action(
+            self.db_repo.conn(),
+            team_id,
+            Some(user_id),
+            TeamCredentialAction::Reveal,
+        )
+        .await?;
+
+        Ok(team)
+    }
+
+    /// Returns all teams of the event including their decrypted credentials, a reveal is
+    /// audit logged for every team. Reveals without a user come from the management API.
+    pub async fn reveal_teams_credentials(
+        &self,
+        user_id: Option<Uuid>,
+        event_id: Uuid,
+    ) -> ServiceResult<Vec<Team>> {
+        let team_models = TeamRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id)
+            .await?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect::<HashMap<_, _>>();
+
+        let teams = self
+            .get_teams(event_id)
+            .await?
+            .into_iter()
+            .filter_map(|team| {
+                let team_model = team_models.get(&team.id)?;
+                Some(self.decrypt_credentials(team, team_model))
+            })
+            .collect::<ServiceResult<Vec<_>>>()?;
+
+        let txn = self.db_repo.conn().begin().await?;
+
+        for team in &teams {
+            Self::log_credential_action(&txn, team.id, user_id, TeamCredentialAction::Reveal)
+                .await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(teams)
+    }
+
+    pub async fn get_team_credential_log(
+        &self,
+        team_id: Uuid,
+    ) -> ServiceResult<Vec<TeamCredentialLogEntry>> {
+        let entries =
+            TeamCredentialLogRepository::fetch_all_by_team_id(self.db_repo.conn(), team_id).await?;
+
+        Ok(entries
+            .into_iter()
+            .map(TeamCredentialLogEntry::from)
+            .collect())
+    }
+
+    async fn generate_credentials<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        user_id: Option<Uuid>,
+        team: db_team::Model,
+        credentials_fg: &TeamCredentialsForGenerate,
+    ) -> ServiceResult<()> {
+        let team_id = team.id;
+        let is_rotation = team.password.is_some()
+            || (credentials_fg.ssh_keypair && team.ssh_private_key.is_some());
+
+        let ssh_keypair = if credentials_fg.ssh_keypair {
+            Some(CryptoService::generate_ssh_keypair(&format!(
+                "team-{}",
+                team.slug
+            ))?)
+        } else {
+            None
+        };
+
+        let password = CryptoService::generate_password(Self::PASSWORD_LENGTH);
+
+        // If an earlier rotation was not confirmed, its previous credentials are the ones still
+        // deployed on the team server
+        let mut active_team = team.clone().into_active_model();
+        active_team.previous_password = Set(team.previous_password.or(team.password));
+        active_team.password = Set(Some(self.crypto_service.encrypt(&password)?));
+
+        if let Some((public_key, private_key)) = ssh_keypair {
+            if team.previous_ssh_private_key.is_none() {
+                active_team.previous_ssh_public_key = Set(team.ssh_public_key);
+                active_team.previous_ssh_private_key = Set(team.ssh_private_key);
+            }
+
+            active_team.ssh_public_key = Set(Some(public_key));
+            active_team.ssh_private_key = Set(Some(self.crypto_service.encrypt(&private_key)?));
+        }
+
+        active_team.update(db).await?;
+
+        let action = if is_rotation {
+            TeamCredentialAction::Rotate
+        } else {
+            TeamCredentialAction::Generate
+        };
+
+        Self::log_credential_action(db, team_id, user_id, action).await?;
+
+        Ok(())
+    }
+
+    async fn log_credential_action<C: ConnectionTrait>(
+        db: &C,
+        team_id: Uuid,
+        user_id: Option<Uuid>,
+        action: TeamCredentialAction,
+    ) -> ServiceResult<()> {
+        let entry = db_team_credential_log::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            team_id: Set(team_id),
+            user_id: Set(user_id),
+            action: Set(action),
+            created_at: Set(Utc::now().naive_utc()),
+        };
+
+        entry.insert(db).await?;
+
+        Ok(())
+    }
+
+    fn decrypt_credentials(
+        &self,
+        mut team: Team,
+        team_model: &db_team::Model,
+    ) -> ServiceResult<Team> {
+        team.password = self.decrypt_optional(team_model.password.as_ref())?;
+        team.previous_password = self.decrypt_optional(team_model.previous_password.as_ref())?;
+        team.ssh_private_key = self.decrypt_optional(team_model.ssh_private_key.as_ref())?;
+        team.previous_ssh_private_key =
+            self.decrypt_optional(team_model.previous_ssh_private_key.as_ref())?;
+        team.ai_api_key = self.decrypt_optional(team_model.ai_api_key.as_ref())?;
+
+        Ok(team)
+    }
+
+    fn decrypt_optional(&self, ciphertext: Option<&Vec<u8>>) -> ServiceResult<Option<String>> {
+        ciphertext
+            .map(|c| self.crypto_service.decrypt(c))
+            .transpose()
+    }
+
     async fn generate_slug<C: ConnectionTrait>(
         &self,
         db: &C,
@@ -480,57 +776,224 @@ impl TeamService {
         Ok(slug)
     }
 
+    /// Creates a `LiteLLM` team with its own budget and a key for it. If the team already has a
+    /// `LiteLLM` team, its budget is updated and the key regenerated instead.
     pub async fn create_team_ai_api_key(
         &self,
         team_id: Uuid,
         budget: f64,
         event_id: Uuid,
     ) -> ServiceResult<String> {
-        let event: db_event::Model =
-            EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
         let team = TeamRepository::fetch_by_id(self.db_repo.conn(), team_id).await?;
 
-        let master_api_key = event
-            .master_ai_api_key
-            .ok_or(ServiceError::MissingMasterAIAPIKey)?;
+        if team.ai_team_id.is_some() {
+            self.update_team_ai_api_key(
+                team_id,
+                &TeamAIAPIKeyForUpdate {
+                    budget: Some(budget),
+                },
+            )
+            .await?;
+
+            return self.regenerate_team_ai_api_key(team_id).await;
+        }
 
-        let api_key = self.crypto_service.decrypt(&master_api_key)?;
+        let api_key = self.master_ai_api_key(&event)?;
+        let models = event.ai_models.unwrap_or_default();
 
         let generated_key = self
-            .lite_llm_repo
-            .generate_team_key(team.index, budget, &api_key)
+            .ai_gateway_repo
+            .generate_team_key(team.index, budget, &models, &api_key)
             .await
             .map_err(ServiceError::Repository)?;
 
         let mut active_team = team.into_active_model();
-        let enc_key = self.crypto_service.encrypt(&generated_key)?;
+        let enc_key = self.crypto_service.encrypt(&generated_key.key)?;
         active_team.ai_api_key = Set(Some(enc_key));
+        active_team.ai_team_id = Set(Some(generated_key.team_id));
 
         active_team.update(self.db_repo.conn()).await?;
 
+        Ok(generated_key.key)
+    }
+
+    /// Provisions AI API keys for all teams of the event lacking one. Safe to re-run, teams
+    /// which already have a key or whose key was revoked are skipped. A failure for one team
+    /// does not abort the others.
+    pub async fn provision_event_ai_api_keys(
+        &self,
+        event_id: Uuid,
+        keys_fp: &TeamAIAPIKeysForProvision,
+    ) -> ServiceResult<Vec<TeamAIAPIKeyProvisionResult>> {
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), event_id).await?;
+
+        let budget = keys_fp
+            .budget
+            .or(event.ai_default_budget)
+            .ok_or(ServiceError::MissingAIBudget)?;
+
+        // Fail early instead of once per team
+        self.master_ai_api_key(&event)?;
+
+        let mut teams =
+            TeamRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
+        teams.sort_by_key(|team| team.index);
+
+        let mut results = Vec::with_capacity(teams.len());
+
+        for team in teams {
+            let (status, error) = if team.index == 0 {
+                (TeamAIAPIKeyProvisionStatus::NotIndexed, None)
+            } else if team.ai_api_key.is_some() {
+                (TeamAIAPIKeyProvisionStatus::AlreadyProvisioned, None)
+            } else if team.ai_team_id.is_some() {
+                (TeamAIAPIKeyProvisionStatus::Revoked, None)
+            } else {
+                match self.create_team_ai_api_key(team.id, budget, event_id).await {
+                    Ok(_) => (TeamAIAPIKeyProvisionStatus::Provisioned, None),
+                    Err(e) => (TeamAIAPIKeyProvisionStatus::Failed, Some(e.to_string())),
+                }
+            };
+
+            results.push(TeamAIAPIKeyProvisionResult {
+                team_id: team.id,
+                team_slug: team.slug,
+                status,
+                error,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Also applies the event's current allowed models to the team.
+    pub async fn update_team_ai_api_key(
+        &self,
+        team_id: Uuid,
+        key_fu: &TeamAIAPIKeyForUpdate,
+    ) -> ServiceResult<TeamAIUsage> {
+        let (team, ai_team_id, api_key) = self.fetch_ai_team(team_id).await?;
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), team.event_id).await?;
+        let models = event.ai_models.unwrap_or_default();
+
+        self.ai_gateway_repo
+            .update_team(&ai_team_id, key_fu.budget, Some(&models), &api_key)
+            .await?;
+
+        self.get_team_ai_usage(team_id)
+            .await?
+            .ok_or(ServiceError::MissingTeamAIAPIKey)
+    }
+
+    /// Revokes the team's key, the `LiteLLM` team and its spend are kept.
+    pub async fn revoke_team_ai_api_key(&self, team_id: Uuid) -> ServiceResult<()> {
+        let team = TeamRepository::fetch_by_id(self.db_repo.conn(), team_id).await?;
+
+        let Some(team_key) = self.decrypt_optional(team.ai_api_key.as_ref())? else {
+            return Ok(());
+        };
+
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), team.event_id).await?;
+        let api_key = self.master_ai_api_key(&event)?;
+
+        self.ai_gateway_repo.delete_key(&team_key, &api_key).await?;
+
+        let mut active_team = team.into_active_model();
+        active_team.ai_api_key = Set(None);
+        active_team.update(self.db_repo.conn()).await?;
+
+        Ok(())
+    }
+
+    /// Revokes the team's current key (if any) and generates a new one sharing the same budget.
+    pub async fn regenerate_team_ai_api_key(&self, team_id: Uuid) -> ServiceResult<String> {
+        let (team, ai_team_id, api_key) = self.fetch_ai_team(team_id).await?;
+        let previous_key = self.decrypt_optional(team.ai_api_key.as_ref())?;
+
+        // The previous key is only revoked once the new one is stored, so a failure in between
+        // never leaves the team without a working key
+        let generated_key = self
+            .ai_gateway_repo
+            .generate_key(&ai_team_id, &api_key)
+            .await?;
+
+        let mut active_team = team.into_active_model();
+        active_team.ai_api_key = Set(Some(self.crypto_service.encrypt(&generated_key)?));
+        active_team.update(self.db_repo.conn()).await?;
+
+        if let Some(previous_key) = previous_key {
+            self.ai_gateway_repo
+                .delete_key(&previous_key, &api_key)
+                .await?;
+        }
+
         Ok(generated_key)
     }
-}
 
-fn apply_template(template: &str, team: &db_team::Model) -> String {
-    // TODO: More robust/efficient templating
+    /// Returns `None` if no AI API key was ever created for the team.
+    pub async fn get_team_ai_usage(&self, team_id: Uuid) -> ServiceResult<Option<TeamAIUsage>> {
+        let team = TeamRepository::fetch_by_id(self.db_repo.conn(), team_id).await?;
+
+        let Some(ai_team_id) = &team.ai_team_id else {
+            return Ok(None);
+        };
 
-    template
-        .replace("{team_id}", &team.id.to_string())
-        .replace("{team_name}", &team.name)
-        .replace("{team_slug}", &team.slug)
-        .replace("{team_index}", &team.index.to_string())
-        .replace("{team_index_padded}", &format!("{:02}", team.index))
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), team.event_id).await?;
+        let api_key = self.master_ai_api_key(&event)?;
+
+        let usage = self
+            .ai_gateway_repo
+            .get_team_usage(ai_team_id, &api_key)
+            .await?;
+
+        Ok(Some(TeamAIUsage {
+            spend: usage.spend,
+            max_budget: usage.max_budget,
+            models: usage.models,
+            has_key: team.ai_api_key.is_some(),
+        }))
+    }
+
+    /// Fetches a team that already has a `LiteLLM` team together with the event's master key.
+    async fn fetch_ai_team(
+        &self,
+        team_id: Uuid,
+    ) -> ServiceResult<(db_team::Model, String, String)> {
+        let team = TeamRepository::fetch_by_id(self.db_repo.conn(), team_id).await?;
+
+        let ai_team_id = team
+            .ai_team_id
+            .clone()
+            .ok_or(ServiceError::MissingTeamAIAPIKey)?;
+
+        let event = EventRepository::fetch_by_id(self.db_repo.conn(), team.event_id).await?;
+        let api_key = self.master_ai_api_key(&event)?;
+
+        Ok((team, ai_team_id, api_key))
+    }
+
+    fn master_ai_api_key(&self, event: &db_event::Model) -> ServiceResult<String> {
+        let master_api_key = event
+            .master_ai_api_key
+            .as_ref()
+            .ok_or(ServiceError::MissingMasterAIAPIKey)?;
+
+        self.crypto_service.decrypt(master_api_key)
+    }
 }
 
-fn apply_override_and_template(
+/// A template that fails to render leaves the team without the value instead of failing whole
+/// listings.
+fn render_or_warn(
+    team_model: &db_team::Model,
+    field: &str,
     override_value: Option<&str>,
     template: Option<&str>,
-    team: &db_team::Model,
+    ctx: &TemplateContext,
 ) -> Option<String> {
-    match (override_value, template) {
-        (Some(overridden), _) => Some(overridden.to_string()),
-        (None, Some(template)) => Some(apply_template(template, team)),
-        (None, None) => None,
-    }
+    render_override_or_template(field, override_value, template, ctx).unwrap_or_else(|e| {
+        warn!(team = ?team_model.id, field, error = %e, "Failed to render team template");
+        None
+    })
 }
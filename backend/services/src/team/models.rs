@@ -1,4 +1,6 @@
 use crate::infrastructure::models::IngressConfig;
+use chrono::NaiveDateTime;
+use hackathon_portal_repositories::db::{db_team_credential_log, TeamCredentialAction};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -14,6 +16,12 @@ pub struct Team {
     pub photo_id: Option<Uuid>,
     pub photo_url: Option<String>,
     pub password: Option<String>,
+    /// Password still deployed on the team server until the rotation is confirmed
+    pub previous_password: Option<String>,
+    pub ssh_public_key: Option<String>,
+    pub ssh_private_key: Option<String>,
+    pub previous_ssh_public_key: Option<String>,
+    pub previous_ssh_private_key: Option<String>,
     pub ai_api_key: Option<String>,
     pub extra_score: Option<f64>,
     pub comment: Option<String>,
@@ -54,3 +62,76 @@ pub struct TeamForUpdate {
     pub ingress_config: Option<IngressConfig>,
     pub finalist: Option<bool>,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamCredentialsForGenerate {
+    /// Also generate an Ed25519 SSH keypair
+    #[serde(default)]
+    pub ssh_keypair: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamCredentialLogEntry {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    /// `None` if performed through the CLI
+    pub user_id: Option<Uuid>,
+    pub action: TeamCredentialAction,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<db_team_credential_log::Model> for TeamCredentialLogEntry {
+    fn from(value: db_team_credential_log::Model) -> Self {
+        Self {
+            id: value.id,
+            team_id: value.team_id,
+            user_id: value.user_id,
+            action: value.action,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamAIAPIKeyForUpdate {
+    /// Leave empty to keep the current budget
+    pub budget: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamAIUsage {
+    /// Spend in USD across all keys the team ever had
+    pub spend: f64,
+    pub max_budget: Option<f64>,
+    /// Models the team may use, empty allows all models
+    pub models: Vec<String>,
+    /// Whether the team currently has a key, `false` if it was revoked
+    pub has_key: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamAIAPIKeysForProvision {
+    /// Budget in USD per team, defaults to the event's default AI budget
+    pub budget: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum TeamAIAPIKeyProvisionStatus {
+    Provisioned,
+    /// The team already has a key
+    AlreadyProvisioned,
+    /// The team's key was revoked, it has to be regenerated explicitly
+    Revoked,
+    /// The team has no index yet, which the key alias is derived from
+    NotIndexed,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct TeamAIAPIKeyProvisionResult {
+    pub team_id: Uuid,
+    pub team_slug: String,
+    pub status: TeamAIAPIKeyProvisionStatus,
+    /// Error message if provisioning failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,293 @@
+use crate::{ServiceError, ServiceResult};
+use hackathon_portal_repositories::db::{db_event, db_project, db_team};
+use minijinja::value::Value;
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+/// Placeholders supported by the original `{placeholder}` syntax. They are
+/// rewritten to their Jinja equivalents before rendering.
+const LEGACY_PLACEHOLDERS: [(&str, &str); 5] = [
+    ("{team_id}", "{{ team.id }}"),
+    ("{team_name}", "{{ team.name }}"),
+    ("{team_slug}", "{{ team.slug }}"),
+    ("{team_index}", "{{ team.index }}"),
+    ("{team_index_padded}", "{{ team.index_padded }}"),
+];
+
+static RENDER_ENV: LazyLock<Environment<'static>> =
+    LazyLock::new(|| create_environment(UndefinedBehavior::Lenient));
+
+static VALIDATION_ENV: LazyLock<Environment<'static>> =
+    LazyLock::new(|| create_environment(UndefinedBehavior::Strict));
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TemplateTeam {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub index: i32,
+    pub index_padded: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TemplateProject {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TemplateEvent {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TemplateContext {
+    pub team: TemplateTeam,
+    pub project: Option<TemplateProject>,
+    pub event: TemplateEvent,
+}
+
+impl TemplateContext {
+    #[must_use]
+    pub fn new(
+        team: &db_team::Model,
+        project: Option<&db_project::Model>,
+        event: &db_event::Model,
+    ) -> Self {
+        Self {
+            team: TemplateTeam {
+                id: team.id,
+                name: team.name.clone(),
+                slug: team.slug.clone(),
+                index: team.index,
+                index_padded: format!("{:02}", team.index),
+            },
+            project: project.map(|project| TemplateProject {
+                id: project.id,
+                name: project.name.clone(),
+                slug: project.slug.clone(),
+            }),
+            event: TemplateEvent {
+                id: event.id,
+                name: event.name.clone(),
+                slug: event.slug.clone(),
+            },
+        }
+    }
+
+    /// Context used to validate templates before they are stored. Templates have to render both
+    /// for teams with and without a project.
+    fn sample(event: &TemplateEvent, with_project: bool) -> Self {
+        Self {
+            team: TemplateTeam {
+                id: Uuid::nil(),
+                name: "Sample Team".to_string(),
+                slug: "sample-team".to_string(),
+                index: 1,
+                index_padded: "01".to_string(),
+            },
+            project: with_project.then(|| TemplateProject {
+                id: Uuid::nil(),
+                name: "Sample Project".to_string(),
+                slug: "sample-project".to_string(),
+            }),
+            event: event.clone(),
+        }
+    }
+}
+
+fn create_environment(undefined_behavior: UndefinedBehavior) -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(undefined_behavior);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("pad", pad);
+    env
+}
+
+/// Left-pads a value with zeros, e.g. `{{ team.index | pad(3) }}` yields `007`.
+#[allow(clippy::needless_pass_by_value)]
+fn pad(value: Value, width: Option<usize>) -> String {
+    let width = width.unwrap_or(2);
+
+    format!("{:0>width$}", value.to_string())
+}
+
+fn migrate_legacy_placeholders(template: &str) -> String {
+    LEGACY_PLACEHOLDERS
+        .iter()
+        .fold(template.to_string(), |template, (legacy, jinja)| {
+            // Skip occurrences that are already part of a Jinja expression, e.g. `{{team_id}}`
+            let doubled = format!("{{{legacy}}}");
+
+            if template.contains(&doubled) {
+                template
+            } else {
+                template.replace(legacy, jinja)
+            }
+        })
+}
+
+fn render_with(
+    env: &Environment<'static>,
+    field: &str,
+    template: &str,
+    ctx: &TemplateContext,
+) -> ServiceResult<String> {
+    let template = migrate_legacy_placeholders(template);
+
+    env.render_str(&template, ctx)
+        .map_err(|e| ServiceError::InvalidTemplate {
+            field: field.to_string(),
+            message: e.to_string(),
+        })
+}
+
+pub fn render_template(
+    field: &str,
+    template: &str,
+    ctx: &TemplateContext,
+) -> ServiceResult<String> {
+    render_with(&RENDER_ENV, field, template, ctx)
+}
+
+pub fn validate_template(
+    field: &str,
+    template: &str,
+    event: &db_event::Model,
+) -> ServiceResult<()> {
+    let event = TemplateEvent {
+        id: event.id,
+        name: event.name.clone(),
+        slug: event.slug.clone(),
+    };
+
+    validate_with_samples(field, template, &event)
+}
+
+fn validate_with_samples(field: &str, template: &str, event: &TemplateEvent) -> ServiceResult<()> {
+    for with_project in [true, false] {
+        render_with(
+            &VALIDATION_ENV,
+            field,
+            template,
+            &TemplateContext::sample(event, with_project),
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn render_override_or_template(
+    field: &str,
+    override_value: Option<&str>,
+    template: Option<&str>,
+    ctx: &TemplateContext,
+) -> ServiceResult<Option<String>> {
+    match (override_value, template) {
+        (Some(overridden), _) => Ok(Some(overridden.to_string())),
+        (None, Some(template)) => render_template(field, template, ctx).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(index: i32, project: Option<&str>) -> TemplateContext {
+        TemplateContext {
+            team: TemplateTeam {
+                id: Uuid::nil(),
+                name: "Team & Co".to_string(),
+                slug: "team-co".to_string(),
+                index,
+                index_padded: format!("{index:02}"),
+            },
+            project: project.map(|slug| TemplateProject {
+                id: Uuid::nil(),
+                name: slug.to_string(),
+                slug: slug.to_string(),
+            }),
+            event: TemplateEvent {
+                id: Uuid::nil(),
+                name: "VIScon 2025".to_string(),
+                slug: "viscon-2025".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn legacy_placeholders() -> ServiceResult<()> {
+        let rendered = render_template("t", "team-{team_index_padded}.{team_slug}", &ctx(7, None))?;
+
+        assert_eq!(rendered, "team-07.team-co");
+
+        Ok(())
+    }
+
+    #[test]
+    fn arithmetic_and_padding() -> ServiceResult<()> {
+        let rendered = render_template(
+            "t",
+            "10.0.{{ team.index }}.{{ team.index + 100 }}:{{ team.index | pad(3) }}",
+            &ctx(7, None),
+        )?;
+
+        assert_eq!(rendered, "10.0.7.107:007");
+
+        Ok(())
+    }
+
+    #[test]
+    fn conditionals_on_project() -> ServiceResult<()> {
+        let template =
+            "{% if project %}{{ project.slug }}{% else %}none{% endif %}.{{ event.slug }}";
+
+        assert_eq!(
+            render_template("t", template, &ctx(1, Some("robots")))?,
+            "robots.viscon-2025"
+        );
+        assert_eq!(
+            render_template("t", template, &ctx(1, None))?,
+            "none.viscon-2025"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn escaping() -> ServiceResult<()> {
+        let rendered = render_template("t", "{{ team.name | urlencode }}", &ctx(1, None))?;
+
+        assert_eq!(rendered, "Team%20%26%20Co");
+
+        Ok(())
+    }
+
+    #[test]
+    fn validation_rejects_invalid_templates() {
+        let sample = ctx(1, Some("robots"));
+
+        assert!(render_with(&VALIDATION_ENV, "t", "{{ team.index", &sample).is_err());
+        assert!(render_with(&VALIDATION_ENV, "t", "{{ team.nonexistent }}", &sample).is_err());
+        assert!(render_with(&VALIDATION_ENV, "t", "{{ team.slug }}", &sample).is_ok());
+    }
+
+    #[test]
+    fn validation_requires_templates_without_project() {
+        let event = ctx(1, None).event;
+
+        assert!(validate_with_samples("t", "{{ project.slug }}.x", &event).is_err());
+        assert!(validate_with_samples(
+            "t",
+            "{% if project %}{{ project.slug }}{% else %}none{% endif %}.x",
+            &event
+        )
+        .is_ok());
+    }
+}
@@ -31,7 +31,14 @@ impl UploadService {
 
     fn validate_content_type(usage: MediaUsage, mime: &Mime) -> ServiceResult<()> {
         match (usage, (mime.type_(), mime.subtype())) {
-            (MediaUsage::TeamPhoto, (mime::IMAGE, mime::JPEG | mime::PNG)) => Ok(()),
+            (
+                MediaUsage::TeamPhoto | MediaUsage::ProjectAttachment,
+                (mime::IMAGE, mime::JPEG | mime::PNG),
+            )
+            | (
+                MediaUsage::ProjectAttachment,
+                (mime::APPLICATION, mime::PDF) | (mime::TEXT, mime::PLAIN),
+            ) => Ok(()),
             _ => Err(ServiceError::UploadContentTypeNotAllowed),
         }
     }
@@ -39,6 +46,7 @@ impl UploadService {
     fn validate_content_length(usage: MediaUsage, size: i64) -> ServiceResult<()> {
         let limit_mb = match usage {
             MediaUsage::TeamPhoto => 10,
+            MediaUsage::ProjectAttachment => 25,
         };
 
         let limit = limit_mb * Self::MB;
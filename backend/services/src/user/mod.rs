@@ -2,12 +2,16 @@ pub mod models;
 
 use crate::user::models::{User, UserForCreate, UserForUpdate};
 use crate::ServiceResult;
+use chrono::{NaiveDateTime, Utc};
 use hackathon_portal_repositories::db::{
-    db_event_user_discord_id, db_user, EventUserRepository, UserRepository,
+    db_discord_unlinked_member, db_event_user_discord_id, db_user, DiscordUnlinkedMemberRepository,
+    EventUserRepository, OrFailExt, UserRepository,
 };
 use hackathon_portal_repositories::DbRepository;
 use sea_orm::prelude::*;
+use sea_orm::sea_query::OnConflict;
 use sea_orm::{IntoActiveModel, QuerySelect, SelectColumns, Set, TransactionTrait};
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct UserService {
@@ -113,21 +117,47 @@ impl UserService {
         Ok(event_user.map(|e| e.discord_id))
     }
 
-    /// Links the user's Discord ID to their user account for a specific event.
+    /// Returns the linked Discord IDs of an event by user ID.
+    pub async fn get_event_discord_ids(
+        &self,
+        event_id: Uuid,
+    ) -> ServiceResult<HashMap<Uuid, String>> {
+        let event_users =
+            EventUserRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id).await?;
+
+        Ok(event_users
+            .into_iter()
+            .map(|e| (e.user_id, e.discord_id))
+            .collect())
+    }
+
+    /// Links the user's Discord ID to their user account for a specific event. A previously
+    /// linked Discord account is unlinked.
     pub async fn update_discord_id(
         &self,
         user_id: Uuid,
         event_id: Uuid,
         discord_id: String,
     ) -> ServiceResult<()> {
-        let existing =
-            EventUserRepository::fetch_by_id_opt(self.db_repo.conn(), event_id, user_id).await?;
+        let txn = self.db_repo.conn().begin().await?;
+
+        let existing = EventUserRepository::fetch_by_id_opt(&txn, event_id, user_id).await?;
+
+        // The account is linked again, its roles must not be removed
+        db_discord_unlinked_member::Entity::delete_by_id((event_id, discord_id.clone()))
+            .exec(&txn)
+            .await?;
 
         if let Some(existing) = existing {
+            if existing.discord_id != discord_id {
+                Self::record_unlinked_discord_id(&txn, event_id, existing.discord_id.clone())
+                    .await?;
+            }
+
             // Update existing record
             let mut active = existing.into_active_model();
             active.discord_id = Set(discord_id);
-            active.update(self.db_repo.conn()).await?;
+            active.update(&txn).await?;
         } else {
             // Insert new record
             let new = db_event_user_discord_id::ActiveModel {
@@ -135,9 +165,77 @@ impl UserService {
                 event_id: Set(event_id),
                 discord_id: Set(discord_id),
             };
-            new.insert(self.db_repo.conn()).await?;
+            new.insert(&txn).await?;
         }
 
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    /// Unlinks the user's Discord account, the next Discord sync removes its managed roles.
+    pub async fn delete_discord_id(&self, user_id: Uuid, event_id: Uuid) -> ServiceResult<()> {
+        let txn = self.db_repo.conn().begin().await?;
+
+        let existing = EventUserRepository::fetch_by_id_opt(&txn, event_id, user_id)
+            .await?
+            .or_fail(db_event_user_discord_id::Entity.table_name(), user_id)?;
+
+        Self::record_unlinked_discord_id(&txn, event_id, existing.discord_id.clone()).await?;
+        existing.delete(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    /// Returns the unlinked Discord IDs of an event whose managed roles were not yet removed.
+    pub async fn get_unlinked_discord_ids(&self, event_id: Uuid) -> ServiceResult<Vec<String>> {
+        let unlinked =
+            DiscordUnlinkedMemberRepository::fetch_all_by_event_id(self.db_repo.conn(), event_id)
+                .await?;
+
+        Ok(unlinked.into_iter().map(|u| u.discord_id).collect())
+    }
+
+    /// Forgets the Discord IDs unlinked before a successful sync.
+    pub async fn clear_unlinked_discord_ids(
+        &self,
+        event_id: Uuid,
+        before: NaiveDateTime,
+    ) -> ServiceResult<()> {
+        db_discord_unlinked_member::Entity::delete_many()
+            .filter(db_discord_unlinked_member::Column::EventId.eq(event_id))
+            .filter(db_discord_unlinked_member::Column::UnlinkedAt.lt(before))
+            .exec(self.db_repo.conn())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_unlinked_discord_id<C: ConnectionTrait>(
+        db: &C,
+        event_id: Uuid,
+        discord_id: String,
+    ) -> ServiceResult<()> {
+        let unlinked = db_discord_unlinked_member::ActiveModel {
+            event_id: Set(event_id),
+            discord_id: Set(discord_id),
+            unlinked_at: Set(Utc::now().naive_utc()),
+        };
+
+        db_discord_unlinked_member::Entity::insert(unlinked)
+            .on_conflict(
+                OnConflict::columns([
+                    db_discord_unlinked_member::Column::EventId,
+                    db_discord_unlinked_member::Column::DiscordId,
+                ])
+                .update_column(db_discord_unlinked_member::Column::UnlinkedAt)
+                .to_owned(),
+            )
+            .exec(db)
+            .await?;
+
         Ok(())
     }
 }
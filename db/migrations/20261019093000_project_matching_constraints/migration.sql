@@ -0,0 +1,3 @@
+-- AlterTable
+ALTER TABLE "project" ADD COLUMN     "max_teams" INTEGER,
+ADD COLUMN     "min_teams" INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,17 @@
+-- CreateTable
+CREATE TABLE "project_matching" (
+    "id" UUID NOT NULL DEFAULT gen_random_uuid(),
+    "event_id" UUID NOT NULL,
+    "inputs" JSONB NOT NULL,
+    "solution" JSONB NOT NULL,
+    "assignments" JSONB NOT NULL,
+    "total_rank" INTEGER NOT NULL,
+    "worst_rank" INTEGER,
+    "created_at" TIMESTAMP(3) NOT NULL,
+    "applied_at" TIMESTAMP(3),
+
+    CONSTRAINT "project_matching_pkey" PRIMARY KEY ("id")
+);
+
+-- AddForeignKey
+ALTER TABLE "project_matching" ADD CONSTRAINT "project_matching_event_id_fkey" FOREIGN KEY ("event_id") REFERENCES "event"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,34 @@
+-- AlterEnum
+ALTER TYPE "media_usage" ADD VALUE 'PROJECT_ATTACHMENT';
+
+-- AlterTable
+ALTER TABLE "project" ADD COLUMN     "requirements" JSONB NOT NULL DEFAULT '[]';
+
+-- CreateTable
+CREATE TABLE "project_owner" (
+    "project_id" UUID NOT NULL,
+    "user_id" UUID NOT NULL,
+
+    CONSTRAINT "project_owner_pkey" PRIMARY KEY ("project_id","user_id")
+);
+
+-- CreateTable
+CREATE TABLE "project_attachment" (
+    "project_id" UUID NOT NULL,
+    "upload_id" UUID NOT NULL,
+    "name" TEXT NOT NULL,
+
+    CONSTRAINT "project_attachment_pkey" PRIMARY KEY ("project_id","upload_id")
+);
+
+-- AddForeignKey
+ALTER TABLE "project_owner" ADD CONSTRAINT "project_owner_project_id_fkey" FOREIGN KEY ("project_id") REFERENCES "project"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "project_owner" ADD CONSTRAINT "project_owner_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "user"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "project_attachment" ADD CONSTRAINT "project_attachment_project_id_fkey" FOREIGN KEY ("project_id") REFERENCES "project"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "project_attachment" ADD CONSTRAINT "project_attachment_upload_id_fkey" FOREIGN KEY ("upload_id") REFERENCES "upload"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,2 @@
+-- AlterTable
+ALTER TABLE "team" ALTER COLUMN "ingress_config" SET DEFAULT '{"version":2,"mode":"Managed","config":{"routes":[{"name":"default","subdomain":null,"path_prefix":null,"server_port":8080,"access_control_mode":"AuthenticationAuthorization"}]}}';
@@ -0,0 +1,26 @@
+-- CreateEnum
+CREATE TYPE "team_credential_action" AS ENUM ('GENERATE', 'ROTATE', 'CONFIRM_ROTATION', 'REVEAL');
+
+-- AlterTable
+ALTER TABLE "team" ADD COLUMN     "previous_password" BYTEA,
+ADD COLUMN     "previous_ssh_private_key" BYTEA,
+ADD COLUMN     "previous_ssh_public_key" TEXT,
+ADD COLUMN     "ssh_private_key" BYTEA,
+ADD COLUMN     "ssh_public_key" TEXT;
+
+-- CreateTable
+CREATE TABLE "team_credential_log" (
+    "id" UUID NOT NULL DEFAULT gen_random_uuid(),
+    "team_id" UUID NOT NULL,
+    "user_id" UUID,
+    "action" "team_credential_action" NOT NULL,
+    "created_at" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "team_credential_log_pkey" PRIMARY KEY ("id")
+);
+
+-- AddForeignKey
+ALTER TABLE "team_credential_log" ADD CONSTRAINT "team_credential_log_team_id_fkey" FOREIGN KEY ("team_id") REFERENCES "team"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- AddForeignKey
+ALTER TABLE "team_credential_log" ADD CONSTRAINT "team_credential_log_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "user"("id") ON DELETE SET NULL ON UPDATE CASCADE;
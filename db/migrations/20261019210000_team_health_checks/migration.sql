@@ -0,0 +1,20 @@
+-- CreateEnum
+CREATE TYPE "team_health_status" AS ENUM ('UP', 'DOWN');
+
+-- CreateTable
+CREATE TABLE "team_health_check" (
+    "team_id" UUID NOT NULL,
+    "port" INTEGER NOT NULL,
+    "url" TEXT NOT NULL,
+    "status" "team_health_status" NOT NULL,
+    "status_code" INTEGER,
+    "latency_ms" INTEGER,
+    "error" TEXT,
+    "checked_at" TIMESTAMP(3) NOT NULL,
+    "status_changed_at" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "team_health_check_pkey" PRIMARY KEY ("team_id","port")
+);
+
+-- AddForeignKey
+ALTER TABLE "team_health_check" ADD CONSTRAINT "team_health_check_team_id_fkey" FOREIGN KEY ("team_id") REFERENCES "team"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,5 @@
+-- AlterTable
+ALTER TABLE "event" ADD COLUMN     "ai_models" TEXT[] DEFAULT ARRAY['openai/*']::TEXT[];
+
+-- AlterTable
+ALTER TABLE "team" ADD COLUMN     "ai_team_id" TEXT;
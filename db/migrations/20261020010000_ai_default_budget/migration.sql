@@ -0,0 +1,2 @@
+-- AlterTable
+ALTER TABLE "event" ADD COLUMN     "ai_default_budget" DOUBLE PRECISION;
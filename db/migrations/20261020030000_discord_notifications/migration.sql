@@ -0,0 +1,11 @@
+-- CreateTable
+CREATE TABLE "discord_notification" (
+    "event_id" UUID NOT NULL,
+    "key" TEXT NOT NULL,
+    "sent_at" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "discord_notification_pkey" PRIMARY KEY ("event_id","key")
+);
+
+-- AddForeignKey
+ALTER TABLE "discord_notification" ADD CONSTRAINT "discord_notification_event_id_fkey" FOREIGN KEY ("event_id") REFERENCES "event"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,21 @@
+-- CreateEnum
+CREATE TYPE "discord_sync_outcome" AS ENUM ('SUCCEEDED', 'PARTIALLY_FAILED', 'FAILED', 'SKIPPED', 'NOT_IN_GUILD');
+
+-- CreateTable
+CREATE TABLE "discord_sync_status" (
+    "event_id" UUID NOT NULL,
+    "outcome" "discord_sync_outcome" NOT NULL,
+    "bot_in_guild" BOOLEAN,
+    "started_at" TIMESTAMP(3) NOT NULL,
+    "duration_ms" INTEGER NOT NULL,
+    "created" INTEGER NOT NULL DEFAULT 0,
+    "updated" INTEGER NOT NULL DEFAULT 0,
+    "deleted" INTEGER NOT NULL DEFAULT 0,
+    "member_failures" JSONB NOT NULL DEFAULT '[]',
+    "error" TEXT,
+
+    CONSTRAINT "discord_sync_status_pkey" PRIMARY KEY ("event_id")
+);
+
+-- AddForeignKey
+ALTER TABLE "discord_sync_status" ADD CONSTRAINT "discord_sync_status_event_id_fkey" FOREIGN KEY ("event_id") REFERENCES "event"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,11 @@
+-- CreateTable
+CREATE TABLE "discord_unlinked_member" (
+    "event_id" UUID NOT NULL,
+    "discord_id" TEXT NOT NULL,
+    "unlinked_at" TIMESTAMP(3) NOT NULL,
+
+    CONSTRAINT "discord_unlinked_member_pkey" PRIMARY KEY ("event_id","discord_id")
+);
+
+-- AddForeignKey
+ALTER TABLE "discord_unlinked_member" ADD CONSTRAINT "discord_unlinked_member_event_id_fkey" FOREIGN KEY ("event_id") REFERENCES "event"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
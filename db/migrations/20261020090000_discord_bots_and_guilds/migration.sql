@@ -0,0 +1,24 @@
+-- AlterTable
+ALTER TABLE "event" ADD COLUMN     "discord_bot_token" BYTEA,
+ADD COLUMN     "discord_client_id" TEXT,
+ADD COLUMN     "discord_client_secret" BYTEA;
+
+-- CreateTable
+CREATE TABLE "discord_guild" (
+    "event_id" UUID NOT NULL,
+    "guild_id" TEXT NOT NULL,
+    "discord_config" TEXT,
+
+    CONSTRAINT "discord_guild_pkey" PRIMARY KEY ("event_id","guild_id")
+);
+
+-- AddForeignKey
+ALTER TABLE "discord_guild" ADD CONSTRAINT "discord_guild_event_id_fkey" FOREIGN KEY ("event_id") REFERENCES "event"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
+
+-- The sync status is kept per guild, it is recorded again by the next sync
+DELETE FROM "discord_sync_status";
+
+-- AlterTable
+ALTER TABLE "discord_sync_status" DROP CONSTRAINT "discord_sync_status_pkey",
+ADD COLUMN     "guild_id" TEXT NOT NULL,
+ADD CONSTRAINT "discord_sync_status_pkey" PRIMARY KEY ("event_id", "guild_id");
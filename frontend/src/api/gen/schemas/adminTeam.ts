@@ -7,8 +7,6 @@
 import type { IngressConfig } from "./ingressConfig";
 
 export interface AdminTeam {
-  /** @nullable */
-  ai_api_key?: string | null;
   /** @nullable */
   comment?: string | null;
   /** @nullable */
@@ -31,8 +29,6 @@ export interface AdminTeam {
   managed_address_override?: string | null;
   name: string;
   /** @nullable */
-  password?: string | null;
-  /** @nullable */
   photo_url?: string | null;
   /** @nullable */
   private_address?: string | null;
@@ -80,6 +80,7 @@ export * from "./ingressModeOneOf";
 export * from "./ingressModeOneOfMode";
 export * from "./ingressModeOneOfThree";
 export * from "./ingressModeOneOfThreeMode";
+export * from "./ingressRoute";
 export * from "./inviteUsersDTO";
 export * from "./managedIngressConfig";
 export * from "./mediaUsage";
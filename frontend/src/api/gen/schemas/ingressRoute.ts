@@ -0,0 +1,25 @@
+/**
+ * Generated by orval v7.9.0 🍺
+ * Do not edit manually.
+ * hackathon-portal-api
+ * OpenAPI spec version: 1.0.0
+ */
+import type { AccessControlMode } from "./accessControlMode";
+
+export interface IngressRoute {
+  access_control_mode: AccessControlMode;
+  /** Unique name of the route within the team, e.g. `frontend` or `api` */
+  name: string;
+  /**
+   * Restricts the route to requests whose path starts with the prefix, e.g. `/api`
+   * @nullable
+   */
+  path_prefix?: string | null;
+  /** @minimum 0 */
+  server_port: number;
+  /**
+   * Subdomain of the team's managed address, e.g. `api` for `api.team-01.example.com`
+   * @nullable
+   */
+  subdomain?: string | null;
+}
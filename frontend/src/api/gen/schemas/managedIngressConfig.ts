@@ -4,10 +4,8 @@
  * hackathon-portal-api
  * OpenAPI spec version: 1.0.0
  */
-import type { AccessControlMode } from "./accessControlMode";
+import type { IngressRoute } from "./ingressRoute";
 
 export interface ManagedIngressConfig {
-  access_control_mode: AccessControlMode;
-  /** @minimum 0 */
-  server_port: number;
+  routes: IngressRoute[];
 }
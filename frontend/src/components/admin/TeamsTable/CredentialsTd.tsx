@@ -1,7 +1,9 @@
-import { useUpdateTeam } from "@/api/gen";
+import { useGetTeamCredentials, useUpdateTeam } from "@/api/gen";
 import { AdminTeam, TeamForUpdate } from "@/api/gen/schemas";
 import { inputProps } from "@/styles/common";
 
+import { useState } from "react";
+
 import { PasswordInput, PasswordInputProps, Table } from "@mantine/core";
 
 type CredentialsTdProps = {
@@ -12,6 +14,11 @@ type CredentialsTdProps = {
 const CredentialsTd = ({ team, refetch }: CredentialsTdProps) => {
   const updateTeamMutation = useUpdateTeam();
 
+  // Credentials are only fetched once revealed, as every reveal is audit logged
+  const [revealed, setRevealed] = useState(false);
+  const { data: credentials, refetch: refetchCredentials } =
+    useGetTeamCredentials(team.id, { query: { enabled: revealed } });
+
   const handleUpdate = async (data: TeamForUpdate) => {
     await updateTeamMutation.mutateAsync({
       teamId: team.id,
@@ -19,6 +26,7 @@ const CredentialsTd = ({ team, refetch }: CredentialsTdProps) => {
     });
 
     refetch?.();
+    refetchCredentials();
   };
 
   return (
@@ -27,8 +35,11 @@ const CredentialsTd = ({ team, refetch }: CredentialsTdProps) => {
         <PasswordInput
           {...(inputProps as PasswordInputProps)}
           size="xs"
-          placeholder="N/A"
-          value={team.password ?? ""}
+          placeholder={revealed ? "N/A" : "Hidden"}
+          readOnly={!revealed}
+          visible={revealed}
+          onVisibilityChange={setRevealed}
+          value={credentials?.vm_password ?? ""}
           onChange={(e) => handleUpdate({ password: e.target.value })}
         />
       </Table.Td>
@@ -36,8 +47,11 @@ const CredentialsTd = ({ team, refetch }: CredentialsTdProps) => {
         <PasswordInput
           {...(inputProps as PasswordInputProps)}
           size="xs"
-          placeholder="N/A"
-          value={team.ai_api_key ?? ""}
+          placeholder={revealed ? "N/A" : "Hidden"}
+          readOnly={!revealed}
+          visible={revealed}
+          onVisibilityChange={setRevealed}
+          value={credentials?.ai_api_key ?? ""}
           onChange={(e) => handleUpdate({ ai_api_key: e.target.value })}
         />
       </Table.Td>
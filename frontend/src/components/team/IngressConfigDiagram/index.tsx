@@ -2,6 +2,7 @@ import {
   AccessControlMode,
   CustomIngressConfig,
   IngressConfig,
+  IngressRoute,
   ManagedIngressConfig,
   Team,
 } from "@/api/gen/schemas";
@@ -43,43 +44,58 @@ const IngressConfigDiagram = ({
   );
 };
 
-const getChartManaged = (team: Team, config: ManagedIngressConfig) => {
-  switch (config.access_control_mode) {
+const getRouteUrl = (team: Team, route: IngressRoute) => {
+  const host = route.subdomain
+    ? `${route.subdomain}.${team.managed_address}`
+    : team.managed_address;
+
+  return `https://${host}${route.path_prefix ?? ""}`;
+};
+
+const getRouteEdges = (team: Team, route: IngressRoute, index: number) => {
+  const url = getRouteUrl(team, route);
+  // Routes to the same port end up at the same server
+  const vm = `vm${route.server_port}`;
+
+  switch (route.access_control_mode) {
     case AccessControlMode.AuthenticationAuthorization:
       return `
-        graph TD
-          inet{Internet};
-          rp(<b>Our Reverse Proxy</b><br>Terminates TLS);
-          auth(<b>Our Authentication & Authorization</b><br>Verifies Login & Permissions);
-          vm(<b>Your Server</b><br>http://0.0.0.0:${config.server_port});
-
-          inet -- https://${team.managed_address} --> rp;
-          rp --> auth;
-          auth -- <b>New Headers</b><br>X-User-Id<br>X-User-Name --> vm;
+          auth${index}(<b>Our Authentication & Authorization</b><br>Verifies Login & Permissions);
+          rp -- ${url} --> auth${index};
+          auth${index} -- <b>New Headers</b><br>X-User-Id<br>X-User-Name --> ${vm};
       `;
     case AccessControlMode.Authentication:
       return `
-        graph TD
-          inet{Internet};
-          rp(<b>Our Reverse Proxy</b><br>Terminates TLS);
-          auth(<b>Our Authentication</b><br>Verifies Login);
-          vm(<b>Your Server</b><br>http://0.0.0.0:${config.server_port});
-
-          inet -- https://${team.managed_address} --> rp;
-          rp --> auth;
-          auth -- <b>New Headers</b><br>X-User-Id<br>X-User-Name --> vm;
+          auth${index}(<b>Our Authentication</b><br>Verifies Login);
+          rp -- ${url} --> auth${index};
+          auth${index} -- <b>New Headers</b><br>X-User-Id<br>X-User-Name --> ${vm};
       `;
     case AccessControlMode.None:
       return `
+          rp -- ${url} --> ${vm};
+      `;
+  }
+};
+
+const getChartManaged = (team: Team, config: ManagedIngressConfig) => {
+  const ports = [...new Set(config.routes.map((route) => route.server_port))];
+
+  return `
         graph TD
           inet{Internet};
           rp(<b>Our Reverse Proxy</b><br>Terminates TLS);
-          vm(<b>Your Server</b><br>http://0.0.0.0:${config.server_port});
-
-          inet -- https://${team.managed_address} --> rp;
-          rp --> vm;
+          ${ports
+            .map(
+              (port) =>
+                `vm${port}(<b>Your Server</b><br>http://0.0.0.0:${port});`,
+            )
+            .join("\n          ")}
+
+          inet -- https --> rp;
+          ${config.routes
+            .map((route, index) => getRouteEdges(team, route, index))
+            .join("")}
       `;
-  }
 };
 
 const getChartCustom = (team: Team, config: CustomIngressConfig) => {
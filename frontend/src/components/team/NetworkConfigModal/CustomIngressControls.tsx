@@ -1,4 +1,4 @@
-import { DEFAULT_MANAGED_CONFIG } from "./GenericIngressControls";
+import { DEFAULT_CUSTOM_CONFIG } from "./GenericIngressControls";
 
 import { CustomIngressConfig, ServerProtocol } from "@/api/gen/schemas";
 import { cardSectionProps } from "@/styles/common";
@@ -21,7 +21,7 @@ const CustomIngressControls = ({
     setConfig(
       produce(config, (draft) => {
         draft.server_port =
-          parseIntStrict(port) ?? DEFAULT_MANAGED_CONFIG.server_port;
+          parseIntStrict(port) ?? DEFAULT_CUSTOM_CONFIG.server_port;
       }),
     );
   };
@@ -40,7 +40,7 @@ const CustomIngressControls = ({
         <NumberInput
           value={config.server_port}
           onChange={(value) => handleUpdateServerPort(value)}
-          placeholder={DEFAULT_MANAGED_CONFIG.server_port.toString()}
+          placeholder={DEFAULT_CUSTOM_CONFIG.server_port.toString()}
           min={1}
           max={65535}
           step={1}
@@ -5,6 +5,7 @@ import {
   AccessControlMode,
   CustomIngressConfig,
   IngressConfig,
+  IngressRoute,
   ManagedIngressConfig,
   ServerProtocol,
 } from "@/api/gen/schemas";
@@ -14,9 +15,14 @@ import { Card, Radio, Stack } from "@mantine/core";
 
 import { produce } from "immer";
 
-export const DEFAULT_MANAGED_CONFIG: ManagedIngressConfig = {
-  access_control_mode: AccessControlMode.AuthenticationAuthorization,
+export const DEFAULT_ROUTE: IngressRoute = {
+  name: "default",
   server_port: 8080,
+  access_control_mode: AccessControlMode.AuthenticationAuthorization,
+};
+
+export const DEFAULT_MANAGED_CONFIG: ManagedIngressConfig = {
+  routes: [DEFAULT_ROUTE],
 };
 
 export const DEFAULT_CUSTOM_CONFIG: CustomIngressConfig = {
@@ -0,0 +1,171 @@
+import { DEFAULT_ROUTE } from "./GenericIngressControls";
+
+import { AccessControlMode, IngressRoute } from "@/api/gen/schemas";
+import {
+  cardSectionProps,
+  iconProps,
+  inputProps,
+  secondaryButtonProps,
+} from "@/styles/common";
+import { parseIntStrict } from "@/utils";
+
+import {
+  Button,
+  Card,
+  Group,
+  NumberInput,
+  NumberInputProps,
+  Radio,
+  Stack,
+  TextInput,
+  TextInputProps,
+} from "@mantine/core";
+
+import { IconX } from "@tabler/icons-react";
+import { produce } from "immer";
+
+type IngressRouteControlsProps = {
+  route: IngressRoute;
+  setRoute: (route: IngressRoute) => void;
+  onRemove?: () => void;
+};
+
+const IngressRouteControls = ({
+  route,
+  setRoute,
+  onRemove,
+}: IngressRouteControlsProps) => {
+  const handleUpdateName = (name: string) => {
+    setRoute(
+      produce(route, (draft) => {
+        draft.name = name;
+      }),
+    );
+  };
+
+  // Empty values are removed, the route then matches the whole managed address
+  const handleUpdateSubdomain = (subdomain: string) => {
+    setRoute(
+      produce(route, (draft) => {
+        draft.subdomain = subdomain || undefined;
+      }),
+    );
+  };
+
+  const handleUpdatePathPrefix = (pathPrefix: string) => {
+    setRoute(
+      produce(route, (draft) => {
+        draft.path_prefix = pathPrefix || undefined;
+      }),
+    );
+  };
+
+  const handleUpdateServerPort = (port: string | number) => {
+    setRoute(
+      produce(route, (draft) => {
+        draft.server_port = parseIntStrict(port) ?? DEFAULT_ROUTE.server_port;
+      }),
+    );
+  };
+
+  const handleUpdateAccessControlMode = (mode: AccessControlMode) => {
+    setRoute(
+      produce(route, (draft) => {
+        draft.access_control_mode = mode;
+      }),
+    );
+  };
+
+  return (
+    <Card.Section {...cardSectionProps}>
+      <Stack gap="sm">
+        <Group align="end">
+          <TextInput
+            {...(inputProps as TextInputProps)}
+            size="sm"
+            flex={1}
+            value={route.name}
+            onChange={(e) => handleUpdateName(e.currentTarget.value)}
+            name="routeName"
+            label="Route Name"
+            description="Unique name of the route, e.g. `frontend` or `api`."
+          />
+          {onRemove && (
+            <Button
+              {...secondaryButtonProps}
+              leftSection={<IconX {...iconProps} />}
+              color="red"
+              onClick={onRemove}
+            >
+              Remove
+            </Button>
+          )}
+        </Group>
+        <Group grow align="start">
+          <TextInput
+            {...(inputProps as TextInputProps)}
+            size="sm"
+            value={route.subdomain ?? ""}
+            onChange={(e) => handleUpdateSubdomain(e.currentTarget.value)}
+            placeholder="api"
+            name="subdomain"
+            label="Subdomain"
+            description="Optional, serves the route on a subdomain of your address."
+          />
+          <TextInput
+            {...(inputProps as TextInputProps)}
+            size="sm"
+            value={route.path_prefix ?? ""}
+            onChange={(e) => handleUpdatePathPrefix(e.currentTarget.value)}
+            placeholder="/api"
+            name="pathPrefix"
+            label="Path Prefix"
+            description="Optional, only serves requests whose path starts with the prefix."
+          />
+        </Group>
+        <NumberInput
+          {...(inputProps as NumberInputProps)}
+          size="sm"
+          value={route.server_port}
+          onChange={(value) => handleUpdateServerPort(value)}
+          placeholder={DEFAULT_ROUTE.server_port.toString()}
+          min={1}
+          max={65535}
+          step={1}
+          name="serverPort"
+          label="Server Port"
+          description="The internal port your application listens on. This must match the port configured in your code (e.g., `process.env.PORT` or `app.listen(8080)`)."
+        />
+        <Radio.Group
+          value={route.access_control_mode}
+          onChange={(value) =>
+            handleUpdateAccessControlMode(value as AccessControlMode)
+          }
+          name="accessControlMode"
+          label="Access Control Mode"
+          description="Control who can access your application and what user information is passed to it."
+        >
+          <Stack my="xs" gap="xs">
+            <Radio
+              value={AccessControlMode.AuthenticationAuthorization}
+              label="Authentication & Authorization (Recommended)"
+              description="Grants access only to authorized users (your team members and hackathon staff). Your application will receive the X-User-Id and X-User-Name headers to identify the user."
+            />
+            <Radio
+              value={AccessControlMode.Authentication}
+              label="Authentication only"
+              description="Grants access to anyone with a Switch edu-ID account. Note that anyone can self-register an edu-ID. Your application will receive the X-User-Id and X-User-Name headers to identify the user."
+            />
+            <Radio
+              value={AccessControlMode.None}
+              label="Disabled"
+              description="Enables public access for anyone on the internet. Your application will not receive any user identity headers."
+            />
+          </Stack>
+        </Radio.Group>
+      </Stack>
+    </Card.Section>
+  );
+};
+
+export default IngressRouteControls;
@@ -1,17 +1,16 @@
-import { DEFAULT_MANAGED_CONFIG } from "./GenericIngressControls";
-
-import { AccessControlMode, ManagedIngressConfig } from "@/api/gen/schemas";
-import { cardSectionProps, inputProps } from "@/styles/common";
-import { parseIntStrict } from "@/utils";
+import { DEFAULT_ROUTE } from "./GenericIngressControls";
+import IngressRouteControls from "./IngressRouteControls";
 
+import { IngressRoute, ManagedIngressConfig } from "@/api/gen/schemas";
 import {
-  Card,
-  NumberInput,
-  NumberInputProps,
-  Radio,
-  Stack,
-} from "@mantine/core";
+  cardSectionProps,
+  iconProps,
+  secondaryButtonProps,
+} from "@/styles/common";
+
+import { Button, Card } from "@mantine/core";
 
+import { IconPlus } from "@tabler/icons-react";
 import { produce } from "immer";
 
 type ManagedIngressControlsProps = {
@@ -23,68 +22,57 @@ const ManagedIngressControls = ({
   config,
   setConfig,
 }: ManagedIngressControlsProps) => {
-  const handleUpdateAccessControlMode = (mode: AccessControlMode) => {
+  const handleUpdateRoute = (index: number, route: IngressRoute) => {
+    setConfig(
+      produce(config, (draft) => {
+        draft.routes[index] = route;
+      }),
+    );
+  };
+
+  const handleAddRoute = () => {
     setConfig(
       produce(config, (draft) => {
-        draft.access_control_mode = mode;
+        draft.routes.push({
+          ...DEFAULT_ROUTE,
+          name: `route-${draft.routes.length + 1}`,
+          subdomain: `route-${draft.routes.length + 1}`,
+        });
       }),
     );
   };
 
-  const handleUpdateServerPort = (port: string | number) => {
+  const handleRemoveRoute = (index: number) => {
     setConfig(
       produce(config, (draft) => {
-        draft.server_port =
-          parseIntStrict(port) ?? DEFAULT_MANAGED_CONFIG.server_port;
+        draft.routes.splice(index, 1);
       }),
     );
   };
 
   return (
     <>
-      <Card.Section {...cardSectionProps}>
-        <Radio.Group
-          value={config.access_control_mode}
-          onChange={(value) =>
-            handleUpdateAccessControlMode(value as AccessControlMode)
+      {config.routes.map((route, index) => (
+        <IngressRouteControls
+          key={index}
+          route={route}
+          setRoute={(route) => handleUpdateRoute(index, route)}
+          // At least one route is required
+          onRemove={
+            config.routes.length > 1
+              ? () => handleRemoveRoute(index)
+              : undefined
           }
-          name="accessControlMode"
-          label="Access Control Mode"
-          description="Control who can access your application and what user information is passed to it."
-        >
-          <Stack my="xs" gap="xs">
-            <Radio
-              value={AccessControlMode.AuthenticationAuthorization}
-              label="Authentication & Authorization (Recommended)"
-              description="Grants access only to authorized users (your team members and hackathon staff). Your application will receive the X-User-Id and X-User-Name headers to identify the user."
-            />
-            <Radio
-              value={AccessControlMode.Authentication}
-              label="Authentication only"
-              description="Grants access to anyone with a Switch edu-ID account. Note that anyone can self-register an edu-ID. Your application will receive the X-User-Id and X-User-Name headers to identify the user."
-            />
-            <Radio
-              value={AccessControlMode.None}
-              label="Disabled"
-              description="Enables public access for anyone on the internet. Your application will not receive any user identity headers."
-            />
-          </Stack>
-        </Radio.Group>
-      </Card.Section>
-      <Card.Section {...cardSectionProps}>
-        <NumberInput
-          {...(inputProps as NumberInputProps)}
-          size="sm"
-          value={config.server_port}
-          onChange={(value) => handleUpdateServerPort(value)}
-          placeholder={DEFAULT_MANAGED_CONFIG.server_port.toString()}
-          min={1}
-          max={65535}
-          step={1}
-          name="serverPort"
-          label="Server Port"
-          description="The internal port your application listens on. This must match the port configured in your code (e.g., `process.env.PORT` or `app.listen(8080)`)."
         />
+      ))}
+      <Card.Section {...cardSectionProps}>
+        <Button
+          {...secondaryButtonProps}
+          leftSection={<IconPlus {...iconProps} />}
+          onClick={handleAddRoute}
+        >
+          Add Route
+        </Button>
       </Card.Section>
     </>
   );